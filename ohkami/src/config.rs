@@ -5,6 +5,9 @@ pub(crate) struct Config {
     #[cfg(feature="__rt_native__")]
     #[cfg(feature="ws")]
     websocket_timeout: std::sync::LazyLock<u64>,
+
+    #[cfg(feature="__rt_native__")]
+    print_routes: std::sync::LazyLock<bool>,
 }
 
 impl Config {
@@ -18,6 +21,11 @@ impl Config {
     pub(crate) fn websocket_timeout(&self) -> u64 {
         *(&*self.websocket_timeout)
     }
+
+    #[cfg(feature="__rt_native__")]
+    pub(crate) fn print_routes(&self) -> bool {
+        *self.print_routes
+    }
 }
 
 impl Config {
@@ -34,6 +42,10 @@ impl Config {
                 .ok().map(|v| v.parse().ok()).flatten()
                 .unwrap_or(42)
             ),
+            #[cfg(feature="__rt_native__")]
+            print_routes: std::sync::LazyLock::new(|| std::env::var("OHKAMI_PRINT_ROUTES")
+                .is_ok_and(|v| matches!(&*v, "1" | "true"))
+            ),
         }
     }
 }
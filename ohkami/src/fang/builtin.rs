@@ -10,9 +10,53 @@ pub use jwt::{JWT, JWTToken};
 mod context;
 pub use context::Context;
 
+mod rejection;
+pub use rejection::OnRejection;
+
+mod etag;
+pub use etag::{ETag, Preconditions};
+
+mod cookie_jar;
+pub use cookie_jar::{CookieJar, CookieJarFang};
+
+mod secure_cookie;
+pub use secure_cookie::{CookieKey, NamedCookie, SignedCookie};
+#[cfg(feature="private_cookie")]
+pub use secure_cookie::PrivateCookie;
+
+#[cfg(feature="sessions")]
+mod sessions;
+#[cfg(feature="sessions")]
+pub use sessions::{Sessions, Session, SessionStore, MemorySessionStore};
+
+#[cfg(feature="csrf")]
+mod csrf;
+#[cfg(feature="csrf")]
+pub use csrf::{CSRF, CSRFToken};
+
+mod cache;
+pub use cache::CacheDefaults;
+#[cfg(feature="__rt_native__")]
+pub use cache::ResponseCache;
+
 pub mod enamel;
 pub use enamel::Enamel;
 
+#[cfg(all(feature="__rt__", any(feature="gzip", feature="br", feature="zstd")))]
+mod compression;
+#[cfg(all(feature="__rt__", any(feature="gzip", feature="br", feature="zstd")))]
+pub use compression::Compression;
+
+#[cfg(any(feature="gzip", feature="br", feature="zstd"))]
+mod decompression;
+#[cfg(any(feature="gzip", feature="br", feature="zstd"))]
+pub use decompression::Decompression;
+
+#[cfg(feature="__rt_native__")]
+mod server_timing;
+#[cfg(feature="__rt_native__")]
+pub use server_timing::{ServerTiming, Timings, Timer};
+
 #[cfg(feature="__rt_native__")]
 mod timeout;
 #[cfg(feature="__rt_native__")]
@@ -68,7 +68,7 @@ where
 
 const _: () = {
     fn unauthorized() -> Response {
-        Response::Unauthorized().with_headers(|h|h
+        crate::typed::reject(crate::Status::Unauthorized, "").with_headers(|h|h
             .WWWAuthenticate("Basic realm=\"Secure Area\"")
         )
     }
@@ -0,0 +1,505 @@
+use crate::{Response, Status};
+use crate::header::CacheControl;
+
+
+/// # Builtin fang for default `Cache-Control`
+///
+/// <br>
+///
+/// Set `Cache-Control` per response status when the handler hasn't set it.
+///
+/// <br>
+///
+/// ---
+/// *example.rs*
+/// ```no_run
+/// use ohkami::prelude::*;
+/// use ohkami::fang::CacheDefaults;
+/// use ohkami::header::CacheControl;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     Ohkami::new((
+///         CacheDefaults::new()
+///             .success(CacheControl::new().public().max_age(60))
+///             .status(Status::NotFound, CacheControl::new().max_age(10))
+///             .otherwise(CacheControl::new().no_store()),
+///         "/".GET(|| async {"Hello, cache!"}),
+///     )).howl("localhost:3000").await
+/// }
+/// ```
+/// ---
+#[derive(Clone, Default)]
+pub struct CacheDefaults {
+    success:   Option<String>,
+    statuses:  Vec<(Status, String)>,
+    otherwise: Option<String>,
+}
+
+impl CacheDefaults {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// for `2xx` responses
+    pub fn success(mut self, cache_control: CacheControl) -> Self {
+        self.success = Some(cache_control.to_string());
+        self
+    }
+
+    /// for responses of `status`, prior to `success`
+    pub fn status(mut self, status: Status, cache_control: CacheControl) -> Self {
+        self.statuses.retain(|(s, _)| *s != status);
+        self.statuses.push((status, cache_control.to_string()));
+        self
+    }
+
+    /// for the other responses
+    pub fn otherwise(mut self, cache_control: CacheControl) -> Self {
+        self.otherwise = Some(cache_control.to_string());
+        self
+    }
+
+    fn of(&self, status: Status) -> Option<&str> {
+        self.statuses.iter()
+            .find(|(s, _)| *s == status)
+            .map(|(_, cc)| cc)
+            .or(if matches!(status.code(), 200..=299) {self.success.as_ref()} else {None})
+            .or(self.otherwise.as_ref())
+            .map(String::as_str)
+    }
+}
+
+impl crate::fang::FangAction for CacheDefaults {
+    async fn back<'a>(&'a self, res: &'a mut Response) {
+        if res.headers.CacheControl().is_none() {
+            if let Some(cache_control) = self.of(res.status) {
+                res.headers.set().CacheControl(cache_control.to_string());
+            }
+        }
+    }
+}
+
+
+#[cfg(feature="__rt_native__")]
+pub use response_cache::ResponseCache;
+
+#[cfg(feature="__rt_native__")]
+mod response_cache {
+    use super::*;
+    use crate::{Fang, FangProc, Request, Method, response::{Content, ResponseHeaders}};
+    use ohkami_lib::CowSlice;
+    use std::{collections::HashMap, time::Instant};
+    use std::sync::{Arc, Mutex};
+
+    /// # Builtin fang for in-process response cache
+    ///
+    /// <br>
+    ///
+    /// Cache `200 OK` payload responses of `GET` / `HEAD` in memory, keyed by the method,
+    /// raw path and query and the request headers listed in the response's `Vary`, following
+    /// their `Cache-Control` as a shared cache:
+    ///
+    /// - serve fresh entries with `Age` for `s-maxage` or `max-age`
+    /// - revalidate stale ones by `If-None-Match` / `If-Modified-Since` if they have
+    ///   `ETag` / `Last-Modified`, and serve them again when the handler answers `304`
+    /// - never store `no-store`, `private`, `Vary: *` and `Set-Cookie` responses, and
+    ///   responses to `Authorization` requests unless `public` or `s-maxage`
+    /// - bypass cached entries for requests of `no-cache` or `no-store`, and don't
+    ///   store the response for `no-store`
+    ///
+    /// <br>
+    ///
+    /// ---
+    /// *example.rs*
+    /// ```no_run
+    /// use ohkami::prelude::*;
+    /// use ohkami::fang::ResponseCache;
+    /// use ohkami::header::CacheControl;
+    ///
+    /// async fn expensive() -> Response {
+    ///     let mut res = Response::OK().with_text("computed");
+    ///     res.headers.set().CacheControl(CacheControl::new().public().max_age(60));
+    ///     res
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     Ohkami::new((ResponseCache::new().capacity(256),
+    ///         "/expensive".GET(expensive),
+    ///     )).howl("localhost:3000").await
+    /// }
+    /// ```
+    /// ---
+    #[derive(Clone)]
+    pub struct ResponseCache {
+        store: Arc<Mutex<Store>>,
+    }
+
+    impl ResponseCache {
+        pub fn new() -> Self {
+            Self { store: Arc::new(Mutex::new(Store {
+                entries:  HashMap::new(),
+                len:      0,
+                capacity: 1024,
+            })) }
+        }
+
+        /// Maximum number of cached responses ( default: 1024 ).
+        /// The oldest one is evicted when exceeded.
+        pub fn capacity(self, capacity: usize) -> Self {
+            self.store.lock().unwrap().capacity = capacity;
+            self
+        }
+    }
+    impl Default for ResponseCache {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    struct Store {
+        entries:  HashMap<Vec<u8>, Vec<Entry>>,
+        len:      usize,
+        capacity: usize,
+    }
+
+    #[derive(Clone)]
+    struct Entry {
+        /// `(name, value)` of the request headers listed in `Vary`
+        vary:      Vec<(String, Option<String>)>,
+        headers:   ResponseHeaders,
+        payload:   Box<[u8]>,
+        stored_at: Instant,
+        /// freshness lifetime in seconds
+        lifetime:  u64,
+    }
+
+    impl Entry {
+        fn age(&self) -> u64 {
+            self.stored_at.elapsed().as_secs()
+        }
+
+        fn is_fresh(&self) -> bool {
+            self.stored_at.elapsed().as_secs_f64() < self.lifetime as f64
+        }
+
+        fn matches(&self, req: &Request) -> bool {
+            self.vary.iter().all(|(name, value)| req.headers.get(name) == value.as_deref())
+        }
+
+        fn to_response(&self) -> Response {
+            Response {
+                status:  Status::OK,
+                headers: self.headers.clone(),
+                content: Content::Payload(CowSlice::Own(self.payload.clone())),
+                trailers: None,
+            }
+        }
+
+        fn serve(&self) -> Response {
+            let mut res = self.to_response();
+            res.headers.set().Age(self.age().to_string());
+            res
+        }
+
+        /// `None` if `res` shouldn't be stored
+        fn of(req: &Request, res: &Response) -> Option<Self> {
+            if res.status != Status::OK
+            || res.headers.SetCookie().next().is_some() {
+                return None
+            }
+            let Content::Payload(payload) = &res.content else {
+                return None
+            };
+
+            let cc = CacheControl::parse(res.headers.CacheControl()?);
+            if cc.is_no_store() || cc.is_private() {
+                return None
+            }
+            if req.headers.Authorization().is_some() && !(cc.is_public() || cc.get_s_maxage().is_some()) {
+                return None
+            }
+            let lifetime = if cc.is_no_cache() {0} else {cc.get_s_maxage().or(cc.get_max_age())?};
+            if lifetime == 0 && res.headers.ETag().is_none() && res.headers.LastModified().is_none() {
+                return None
+            }
+
+            let vary = match res.headers.Vary() {
+                None => vec![],
+                Some(vary) if vary.split(',').any(|v| v.trim() == "*") => return None,
+                Some(vary) => vary.split(',')
+                    .map(str::trim)
+                    .filter(|name| !name.is_empty())
+                    .map(|name| (name.to_string(), req.headers.get(name).map(String::from)))
+                    .collect()
+            };
+
+            Some(Self {
+                vary,
+                headers:   res.headers.clone(),
+                payload:   (**payload).into(),
+                stored_at: Instant::now(),
+                lifetime,
+            })
+        }
+    }
+
+    impl Store {
+        fn get(&self, key: &[u8], req: &Request) -> Option<Entry> {
+            self.entries.get(key)?.iter().find(|e| e.matches(req)).cloned()
+        }
+
+        fn insert(&mut self, key: Vec<u8>, entry: Entry) {
+            let variants = self.entries.entry(key).or_default();
+            match variants.iter_mut().find(|e| e.vary == entry.vary) {
+                Some(existing) => *existing = entry,
+                None => {
+                    variants.push(entry);
+                    self.len += 1;
+                }
+            }
+            while self.len > self.capacity {
+                self.evict_oldest();
+            }
+        }
+
+        fn remove(&mut self, key: &[u8], req: &Request) {
+            if let Some(variants) = self.entries.get_mut(key) {
+                let before = variants.len();
+                variants.retain(|e| !e.matches(req));
+                self.len -= before - variants.len();
+                if variants.is_empty() {
+                    self.entries.remove(key);
+                }
+            }
+        }
+
+        fn evict_oldest(&mut self) {
+            let Some((key, index)) = self.entries.iter()
+                .flat_map(|(key, variants)| variants.iter().enumerate().map(move |(i, e)| (key, i, e.stored_at)))
+                .min_by_key(|(_, _, stored_at)| *stored_at)
+                .map(|(key, i, _)| (key.clone(), i))
+            else {return};
+
+            let variants = self.entries.get_mut(&key).unwrap();
+            variants.remove(index);
+            self.len -= 1;
+            if variants.is_empty() {
+                self.entries.remove(&key);
+            }
+        }
+    }
+
+    impl<Inner: FangProc> Fang<Inner> for ResponseCache {
+        type Proc = ResponseCacheProc<Inner>;
+        fn chain(&self, inner: Inner) -> Self::Proc {
+            ResponseCacheProc { inner, store: self.store.clone() }
+        }
+    }
+
+    pub struct ResponseCacheProc<Inner: FangProc> {
+        store: Arc<Mutex<Store>>,
+        inner: Inner,
+    }
+    impl<Inner: FangProc> FangProc for ResponseCacheProc<Inner> {
+        async fn bite<'b>(&'b self, req: &'b mut Request) -> Response {
+            if !matches!(req.method, Method::GET | Method::HEAD) {
+                return self.inner.bite(req).await
+            }
+
+            /* raw bytes, not to identify differently encoded requests */
+            let key = [
+                req.method.as_str().as_bytes(), b" ",
+                req.path.as_bytes(), b"?", req.query.raw(),
+            ].concat();
+            let req_cc = req.headers.CacheControl().map(CacheControl::parse).unwrap_or_default();
+
+            let cached = (!req_cc.is_no_cache() && !req_cc.is_no_store())
+                .then(|| self.store.lock().unwrap().get(&key, req))
+                .flatten();
+
+            if let Some(entry) = &cached {
+                if entry.is_fresh() {
+                    return entry.serve()
+                }
+            }
+
+            let mut revalidating = false;
+            if let Some(entry) = &cached {
+                if req.headers.IfNoneMatch().is_none() && req.headers.IfModifiedSince().is_none() {
+                    if let Some(etag) = entry.headers.ETag() {
+                        req.headers.set().IfNoneMatch(etag.to_string());
+                        revalidating = true;
+                    } else if let Some(last_modified) = entry.headers.LastModified() {
+                        req.headers.set().IfModifiedSince(last_modified.to_string());
+                        revalidating = true;
+                    }
+                }
+            }
+
+            let res = self.inner.bite(req).await;
+
+            if revalidating {
+                req.headers.set().IfNoneMatch(None).IfModifiedSince(None);
+
+                if res.status == Status::NotModified {
+                    let mut entry = cached.unwrap();
+                    macro_rules! update {
+                        ($($name:ident),*) => {$(
+                            if let Some(value) = res.headers.$name() {
+                                entry.headers.set().$name(value.to_string());
+                            }
+                        )*};
+                    } update!(ETag, CacheControl, Expires, LastModified, Vary);
+
+                    let res = entry.to_response();
+                    match Entry::of(req, &res) {
+                        Some(refreshed) => self.store.lock().unwrap().insert(key, refreshed),
+                        None => self.store.lock().unwrap().remove(&key, req),
+                    }
+                    return res
+                }
+            }
+
+            if !req_cc.is_no_store() {
+                match Entry::of(req, &res) {
+                    Some(entry) => self.store.lock().unwrap().insert(key, entry),
+                    None => if cached.is_some() {self.store.lock().unwrap().remove(&key, req)},
+                }
+            }
+
+            res
+        }
+    }
+}
+
+
+#[cfg(all(test, debug_assertions, feature="__rt_native__", feature="DEBUG"))]
+#[test] fn test_cache_defaults() {
+    use crate::prelude::*;
+    use crate::testing::*;
+
+    crate::__rt__::testing::block_on(async {
+        let t = Ohkami::new((
+            CacheDefaults::new()
+                .success(CacheControl::new().public().max_age(60))
+                .status(Status::NotFound, CacheControl::new().max_age(10))
+                .otherwise(CacheControl::new().no_store()),
+            "/ok".GET(|| async {"ok"}),
+            "/custom".GET(|| async {
+                let mut res = Response::OK().with_text("custom");
+                res.headers.set().CacheControl(CacheControl::new().private());
+                res
+            }),
+            "/missing".GET(|| async {Response::NotFound()}),
+            "/error".GET(|| async {Response::InternalServerError()}),
+        )).test();
+
+        let res = t.oneshot(TestRequest::GET("/ok")).await;
+        assert_eq!(res.header("Cache-Control"), Some("public, max-age=60"));
+
+        let res = t.oneshot(TestRequest::GET("/custom")).await;
+        assert_eq!(res.header("Cache-Control"), Some("private"));
+
+        let res = t.oneshot(TestRequest::GET("/missing")).await;
+        assert_eq!(res.header("Cache-Control"), Some("max-age=10"));
+
+        let res = t.oneshot(TestRequest::GET("/error")).await;
+        assert_eq!(res.header("Cache-Control"), Some("no-store"));
+    });
+}
+
+#[cfg(all(test, debug_assertions, feature="__rt_native__", feature="DEBUG"))]
+#[test] fn test_response_cache() {
+    use crate::prelude::*;
+    use crate::testing::*;
+    use crate::fang::Preconditions;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static FRESH: AtomicUsize = AtomicUsize::new(0);
+    static NO_STORE: AtomicUsize = AtomicUsize::new(0);
+    static STALE: AtomicUsize = AtomicUsize::new(0);
+    static VARY: AtomicUsize = AtomicUsize::new(0);
+
+    async fn fresh() -> Response {
+        let n = FRESH.fetch_add(1, Ordering::SeqCst);
+        let mut res = Response::OK().with_text(format!("fresh {n}"));
+        res.headers.set().CacheControl(CacheControl::new().public().max_age(60));
+        res
+    }
+    async fn no_store() -> Response {
+        let n = NO_STORE.fetch_add(1, Ordering::SeqCst);
+        let mut res = Response::OK().with_text(format!("no-store {n}"));
+        res.headers.set().CacheControl(CacheControl::new().no_store());
+        res
+    }
+    async fn stale(preconditions: Preconditions<'_>) -> Result<Response, Response> {
+        let n = STALE.fetch_add(1, Ordering::SeqCst);
+        preconditions.check("\"v1\"")?;
+        let mut res = Response::OK().with_text(format!("stale {n}"));
+        res.headers.set()
+            .CacheControl(CacheControl::new().max_age(0))
+            .ETag("\"v1\"");
+        Ok(res)
+    }
+    async fn vary() -> Response {
+        let n = VARY.fetch_add(1, Ordering::SeqCst);
+        let mut res = Response::OK().with_text(format!("vary {n}"));
+        res.headers.set()
+            .CacheControl(CacheControl::new().max_age(60))
+            .Vary("Accept-Language");
+        res
+    }
+
+    crate::__rt__::testing::block_on(async {
+        let t = Ohkami::new((ResponseCache::new(),
+            "/fresh".GET(fresh),
+            "/no-store".GET(no_store),
+            "/stale".GET(stale),
+            "/vary".GET(vary),
+        )).test();
+
+        let res = t.oneshot(TestRequest::GET("/fresh")).await;
+        assert_eq!(res.text(), Some("fresh 0"));
+        assert_eq!(res.header("Age"), None);
+        let res = t.oneshot(TestRequest::GET("/fresh")).await;
+        assert_eq!(res.text(), Some("fresh 0"));
+        assert_eq!(res.header("Age"), Some("0"));
+        let res = t.oneshot(TestRequest::GET("/fresh").query("page", "2")).await;
+        assert_eq!(res.text(), Some("fresh 1"));
+        let res = t.oneshot(TestRequest::GET("/fresh").query("q", "a%26b=c")).await;
+        assert_eq!(res.text(), Some("fresh 2"));
+        let res = t.oneshot(TestRequest::GET("/fresh").query("q", "a").query("b", "c")).await;
+        assert_eq!(res.text(), Some("fresh 3"));
+        let res = t.oneshot(TestRequest::GET("/fresh").query("q", "a%26b=c")).await;
+        assert_eq!(res.text(), Some("fresh 2"));
+        let res = t.oneshot(TestRequest::GET("/fresh").header("Cache-Control", "no-cache")).await;
+        assert_eq!(res.text(), Some("fresh 4"));
+        let res = t.oneshot(TestRequest::HEAD("/fresh")).await;
+        assert_eq!(res.status(), Status::OK);
+        assert_eq!(FRESH.load(Ordering::SeqCst), 6);
+
+        let res = t.oneshot(TestRequest::GET("/no-store")).await;
+        assert_eq!(res.text(), Some("no-store 0"));
+        let res = t.oneshot(TestRequest::GET("/no-store")).await;
+        assert_eq!(res.text(), Some("no-store 1"));
+
+        let res = t.oneshot(TestRequest::GET("/stale")).await;
+        assert_eq!(res.text(), Some("stale 0"));
+        let res = t.oneshot(TestRequest::GET("/stale")).await;
+        assert_eq!(res.status(), Status::OK);
+        assert_eq!(res.text(), Some("stale 0"));
+        assert_eq!(res.header("ETag"), Some("\"v1\""));
+        assert_eq!(STALE.load(Ordering::SeqCst), 2);
+
+        let res = t.oneshot(TestRequest::GET("/vary").header("Accept-Language", "en")).await;
+        assert_eq!(res.text(), Some("vary 0"));
+        let res = t.oneshot(TestRequest::GET("/vary").header("Accept-Language", "ja")).await;
+        assert_eq!(res.text(), Some("vary 1"));
+        let res = t.oneshot(TestRequest::GET("/vary").header("Accept-Language", "en")).await;
+        assert_eq!(res.text(), Some("vary 0"));
+        let res = t.oneshot(TestRequest::GET("/vary").header("Accept-Language", "ja")).await;
+        assert_eq!(res.text(), Some("vary 1"));
+    });
+}
@@ -0,0 +1,320 @@
+#![cfg(all(feature="__rt__", any(feature="gzip", feature="br", feature="zstd")))]
+
+use crate::header::{append, encoding::{Encoding, StreamEncoder, negotiate, is_compressible}};
+use crate::response::{Content, BodyStream};
+use crate::{Fang, FangProc, Request, Response, Status};
+use ohkami_lib::Stream;
+use std::{borrow::Cow, pin::Pin, task::{Context, Poll}};
+
+
+/// # Builtin fang for response compression
+/// 
+/// <br>
+/// 
+/// Compress responses with the encoding selected by `Accept-Encoding`
+/// of the request ( with q-values ) from the enabled ones:
+/// 
+/// - `br` by `br` feature
+/// - `zstd` by `zstd` feature
+/// - `gzip`, `deflate` by `gzip` feature
+/// 
+/// and set `Content-Encoding` and `Vary: Accept-Encoding`.
+/// 
+/// Payloads smaller than `min_size` ( default: 1024 bytes ) are left as they are,
+/// as well as non-compressible `Content-Type`s ( images, archives, ... ),
+/// already encoded or partial responses and `Cache-Control: no-transform`.
+/// 
+/// Streamed bodies ( `Response::with_body_stream`, SSE's `DataStream` ) are
+/// compressed chunk by chunk, flushing each one so that it reaches the client
+/// without waiting for the rest. `NamedFile` is not compressed to keep it zero-copy.
+/// 
+/// <br>
+/// 
+/// ---
+/// *example.rs*
+/// ```no_run
+/// use ohkami::prelude::*;
+/// use ohkami::fang::Compression;
+/// 
+/// #[tokio::main]
+/// async fn main() {
+///     Ohkami::new((Compression::new(),
+///         "/".GET(|| async {"Hello, world!".repeat(100)}),
+///     )).howl("localhost:3000").await
+/// }
+/// ```
+/// ---
+#[derive(Clone)]
+pub struct Compression {
+    min_size: usize,
+}
+
+impl Compression {
+    pub fn new() -> Self {
+        Self { min_size: 1024 }
+    }
+
+    /// Minimum size in bytes of a payload to compress ( default: 1024 ).
+    /// Streamed bodies are always compressed.
+    pub fn min_size(mut self, bytes: usize) -> Self {
+        self.min_size = bytes;
+        self
+    }
+}
+impl Default for Compression {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Compression {
+    fn compress(&self, res: &mut Response, encoding: Option<Encoding>) {
+        if !matches!(res.status.code(), 200..=299)
+        || matches!(res.status, Status::NoContent | Status::PartialContent)
+        || res.headers.ContentEncoding().is_some()
+        || res.headers.CacheControl().is_some_and(|cc| cc.split(',').any(|d| d.trim().eq_ignore_ascii_case("no-transform")))
+        || !res.headers.ContentType().is_some_and(is_compressible) {
+            return
+        }
+
+        match &res.content {
+            Content::Payload(bytes) if bytes.len() >= self.min_size => (),
+            #[cfg(feature="sse")]
+            Content::Stream(_) => (),
+            Content::BodyStream(_) => (),
+            _ => return
+        }
+
+        res.headers.set().Vary(append("Accept-Encoding"));
+
+        let Some(encoding) = encoding else {
+            return
+        };
+
+        res.content = match res.content.take() {
+            Content::Payload(bytes) => match encoding.encode(&bytes) {
+                Ok(compressed) if compressed.len() < bytes.len() => {
+                    res.headers.set().ContentLength(ohkami_lib::num::itoa(compressed.len()));
+                    Content::Payload(compressed.into())
+                }
+                Ok(_) => {
+                    res.content = Content::Payload(bytes);
+                    return
+                }
+                Err(e) => {
+                    crate::warning!("[Compression] failed to compress with {}: {e}", encoding.as_str());
+                    res.content = Content::Payload(bytes);
+                    return
+                }
+            },
+
+            #[cfg(feature="sse")]
+            Content::Stream(stream) => {
+                use ohkami_lib::StreamExt;
+                let stream: BodyStream = Box::pin(stream.map(|data| Ok(Cow::Owned(crate::sse::message(&data)))));
+                Content::BodyStream(Box::pin(Compressed::new(stream, encoding)))
+            }
+
+            Content::BodyStream(stream) => {
+                res.headers.set()
+                    .ContentLength(None)
+                    .TransferEncoding("chunked");
+                Content::BodyStream(Box::pin(Compressed::new(stream, encoding)))
+            }
+
+            _ => unreachable!()
+        };
+
+        res.headers.set().ContentEncoding(encoding.as_str());
+        if let Some(etag) = res.headers.ETag().filter(|etag| !etag.starts_with("W/")) {
+            /* the compressed representation is not byte-for-byte identical to the original */
+            let weak = format!("W/{etag}");
+            res.headers.set().ETag(weak);
+        }
+    }
+}
+
+impl<Inner: FangProc> Fang<Inner> for Compression {
+    type Proc = CompressionProc<Inner>;
+    fn chain(&self, inner: Inner) -> Self::Proc {
+        CompressionProc { inner, compression: self.clone() }
+    }
+}
+
+pub struct CompressionProc<Inner: FangProc> {
+    compression: Compression,
+    inner:       Inner,
+}
+impl<Inner: FangProc> FangProc for CompressionProc<Inner> {
+    async fn bite<'b>(&'b self, req: &'b mut Request) -> Response {
+        let encoding = negotiate(
+            req.headers.AcceptEncoding(),
+            Encoding::AVAILABLE.iter().copied()
+        );
+
+        let mut res = self.inner.bite(req).await;
+        self.compression.compress(&mut res, encoding);
+        res
+    }
+}
+
+/// body stream compressed chunk by chunk
+struct Compressed {
+    inner:   BodyStream,
+    /// `None` after finished or failed
+    encoder: Option<StreamEncoder>,
+}
+impl Compressed {
+    fn new(inner: BodyStream, encoding: Encoding) -> Self {
+        let encoder = StreamEncoder::new(encoding)
+            .expect("`Encoding::AVAILABLE` contains only enabled encodings");
+        Self { inner, encoder: Some(encoder) }
+    }
+}
+impl Stream for Compressed {
+    type Item = Result<Cow<'static, [u8]>, Box<dyn std::error::Error + Send + Sync>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            let Some(encoder) = this.encoder.as_mut() else {
+                return Poll::Ready(None)
+            };
+            let compressed = match std::task::ready!(this.inner.as_mut().poll_next(cx)) {
+                Some(Ok(chunk)) => encoder.encode(&chunk).map_err(Into::into),
+                Some(Err(e))    => Err(e),
+                None            => this.encoder.take().unwrap().finish().map_err(Into::into),
+            };
+            match compressed {
+                Ok(bytes) if bytes.is_empty() => continue,
+                Ok(bytes) => return Poll::Ready(Some(Ok(Cow::Owned(bytes)))),
+                Err(e) => {
+                    this.encoder = None;
+                    return Poll::Ready(Some(Err(e)))
+                }
+            }
+        }
+    }
+}
+
+
+#[cfg(all(test, debug_assertions, feature="__rt_native__", feature="DEBUG", feature="gzip"))]
+#[test] fn test_compression() {
+    use crate::prelude::*;
+    use crate::testing::*;
+    use std::io::Read;
+
+    fn gunzip(bytes: &[u8]) -> String {
+        let mut decoded = String::new();
+        flate2::read::GzDecoder::new(bytes).read_to_string(&mut decoded).unwrap();
+        decoded
+    }
+
+    let t = Ohkami::new((Compression::new(),
+        "/text".GET(|| async {"Hello, world!".repeat(100)}),
+        "/small".GET(|| async {"Hello, world!"}),
+        "/image".GET(|| async {
+            Response::OK().with_payload("image/png", vec![0; 2048])
+        }),
+        "/no-transform".GET(|| async {
+            Response::OK()
+                .with_text("Hello, world!".repeat(100))
+                .with_headers(|h| h.CacheControl("no-transform"))
+        }),
+        "/stream".GET(|| async {
+            Response::OK()
+                .with_headers(|h| h.ContentType("text/csv"))
+                .with_body_stream(crate::util::stream::queue(|mut q| async move {
+                    q.push(Ok::<_, std::io::Error>("id,name\n".as_bytes()));
+                    q.push(Ok("1,ohkami\n".as_bytes()));
+                }))
+        }),
+    )).test();
+
+    crate::__rt__::testing::block_on(async {
+        {
+            let req = TestRequest::GET("/text")
+                .header("Accept-Encoding", "gzip");
+            let res = t.oneshot(req).await.collect_body().await;
+            assert_eq!(res.status(), Status::OK);
+            assert_eq!(res.header("Content-Encoding"), Some("gzip"));
+            assert_eq!(res.header("Vary"), Some("Accept-Encoding"));
+            let body = res.content("text/plain; charset=UTF-8").unwrap();
+            assert_eq!(res.header("Content-Length"), Some(&*body.len().to_string()));
+            assert_eq!(gunzip(body), "Hello, world!".repeat(100));
+        }
+        {
+            let req = TestRequest::GET("/text")
+                .header("Accept-Encoding", "gzip;q=0, identity");
+            let res = t.oneshot(req).await.collect_body().await;
+            assert_eq!(res.header("Content-Encoding"), None);
+            assert_eq!(res.header("Vary"), Some("Accept-Encoding"));
+            assert_eq!(res.text(), Some(&*"Hello, world!".repeat(100)));
+        }
+        {
+            let req = TestRequest::GET("/text");
+            let res = t.oneshot(req).await;
+            assert_eq!(res.header("Content-Encoding"), None);
+            assert_eq!(res.header("Vary"), Some("Accept-Encoding"));
+        }
+        {
+            let req = TestRequest::GET("/small")
+                .header("Accept-Encoding", "gzip");
+            let res = t.oneshot(req).await.collect_body().await;
+            assert_eq!(res.header("Content-Encoding"), None);
+            assert_eq!(res.header("Vary"), None);
+            assert_eq!(res.text(), Some("Hello, world!"));
+        }
+        {
+            let req = TestRequest::GET("/image")
+                .header("Accept-Encoding", "gzip");
+            let res = t.oneshot(req).await.collect_body().await;
+            assert_eq!(res.header("Content-Encoding"), None);
+            assert_eq!(res.content("image/png").map(<[u8]>::len), Some(2048));
+        }
+        {
+            let req = TestRequest::GET("/no-transform")
+                .header("Accept-Encoding", "gzip");
+            let res = t.oneshot(req).await;
+            assert_eq!(res.header("Content-Encoding"), None);
+        }
+        {
+            let req = TestRequest::GET("/stream")
+                .header("Accept-Encoding", "gzip");
+            let res = t.oneshot(req).await.collect_body().await;
+            assert_eq!(res.header("Content-Encoding"), Some("gzip"));
+            assert_eq!(res.header("Transfer-Encoding"), Some("chunked"));
+            assert_eq!(gunzip(res.content("text/csv").unwrap()), "id,name\n1,ohkami\n");
+        }
+    });
+}
+
+#[cfg(all(test, debug_assertions, feature="__rt_native__", feature="DEBUG", feature="gzip", feature="sse"))]
+#[test] fn test_compression_sse() {
+    use crate::prelude::*;
+    use crate::testing::*;
+    use crate::sse::DataStream;
+    use std::io::Read;
+
+    let t = Ohkami::new((Compression::new(),
+        "/sse".GET(|| async {
+            DataStream::<&'static str>::new(|mut s| async move {
+                s.send("Hello,");
+                s.send("world!");
+            })
+        }),
+    )).test();
+
+    crate::__rt__::testing::block_on(async {
+        let req = TestRequest::GET("/sse")
+            .header("Accept-Encoding", "gzip");
+        let res = t.oneshot(req).await.collect_body().await;
+        assert_eq!(res.header("Content-Encoding"), Some("gzip"));
+        assert_eq!(res.header("Content-Type"), Some("text/event-stream"));
+
+        let mut decoded = String::new();
+        flate2::read::GzDecoder::new(res.content("text/event-stream").unwrap())
+            .read_to_string(&mut decoded).unwrap();
+        assert_eq!(decoded, "data: Hello,\n\ndata: world!\n\n");
+    });
+}
@@ -0,0 +1,297 @@
+use crate::{Fang, FangProc, FromRequest, Request, Response};
+use crate::header::SetCookieBuilder;
+use std::sync::{Arc, Mutex};
+
+
+/// # Request-scoped Cookies
+///
+/// Read the request's Cookies, and add, update or remove them in handlers.
+/// The changes are emitted as `Set-Cookie` headers of the response by the
+/// fang of [`CookieJar::fang`].
+///
+/// Cookie values are percent-encoded in `Set-Cookie` and decoded on reading.
+///
+/// <br>
+///
+/// ---
+/// *example.rs*
+/// ```no_run
+/// use ohkami::prelude::*;
+/// use ohkami::fang::CookieJar;
+/// use std::time::Duration;
+///
+/// #[derive(Serialize)]
+/// struct Preferences {
+///     theme: &'static str,
+///     lang:  Option<&'static str>,
+/// }
+///
+/// async fn visit(jar: CookieJar) -> String {
+///     let count = jar.get("visits")
+///         .and_then(|v| v.parse::<u64>().ok())
+///         .unwrap_or(0) + 1;
+///     jar.add_with("visits", count.to_string(), |d| d
+///         .Path("/").HttpOnly().ExpiresIn(Duration::from_secs(60 * 60 * 24))
+///     ).expect("`visits` is a valid Cookie name");
+///
+///     jar.add_all(&Preferences { theme: "dark", lang: None }, |d| d.Path("/"))
+///         .expect("`Preferences` is serialized into Cookies");
+///
+///     format!("visit #{count}")
+/// }
+///
+/// async fn reset(jar: CookieJar) -> &'static str {
+///     jar.remove_with("visits", |d| d.Path("/"))
+///         .expect("`visits` is a valid Cookie name");
+///     "reset"
+/// }
+///
+/// #[tokio::main]
+/// async fn main() {
+///     Ohkami::new((CookieJar::fang(),
+///         "/visit".GET(visit),
+///         "/reset".POST(reset),
+///     )).howl("localhost:3000").await
+/// }
+/// ```
+/// ---
+#[derive(Clone)]
+pub struct CookieJar(
+    Arc<Mutex<Jar>>
+);
+
+struct Jar {
+    /// Cookies of the request, percent-decoded
+    original: Vec<(String, String)>,
+    changes:  Vec<Change>,
+}
+
+struct Change {
+    name:      String,
+    /// `None` for removal
+    value:     Option<String>,
+    setcookie: String,
+}
+
+impl CookieJar {
+    /// The fang to make [`CookieJar`] available in handlers
+    /// and emit its changes as `Set-Cookie`s.
+    pub fn fang() -> CookieJarFang {
+        CookieJarFang
+    }
+
+    fn of(req: &Request) -> Self {
+        let original = req.headers.Cookies()
+            .map(|(name, value)| (
+                name.to_string(),
+                ohkami_lib::percent_decode_utf8(value.as_bytes())
+                    .map(|v| v.into_owned())
+                    .unwrap_or_else(|_| value.to_string())
+            ))
+            .collect();
+        Self(Arc::new(Mutex::new(Jar { original, changes: Vec::new() })))
+    }
+
+    /// The current value of Cookie `name`, reflecting the changes in this jar.
+    pub fn get(&self, name: &str) -> Option<String> {
+        let jar = self.0.lock().unwrap();
+        match jar.changes.iter().find(|c| c.name == name) {
+            Some(change) => change.value.clone(),
+            None => jar.original.iter().find(|(n, _)| n == name).map(|(_, v)| v.clone()),
+        }
+    }
+
+    /// `(name, value)` of the current Cookies, reflecting the changes in this jar.
+    pub fn cookies(&self) -> Vec<(String, String)> {
+        let jar = self.0.lock().unwrap();
+        let mut cookies = jar.original.iter()
+            .filter(|(name, _)| jar.changes.iter().all(|c| c.name != *name))
+            .cloned()
+            .collect::<Vec<_>>();
+        for change in &jar.changes {
+            if let Some(value) = &change.value {
+                cookies.push((change.name.clone(), value.clone()));
+            }
+        }
+        cookies
+    }
+
+    /// Add or update Cookie `name` without any directives.
+    ///
+    /// Fails if `name` is not a valid Cookie name.
+    pub fn add(&self, name: impl Into<String>, value: impl Into<String>) -> Result<(), impl std::error::Error> {
+        self.try_add(name.into(), value.into(), |d| d)
+    }
+
+    /// Add or update Cookie `name` with directives like `Path`, `HttpOnly`
+    /// or `ExpiresIn`.
+    ///
+    /// Fails if `name` is not a valid Cookie name.
+    pub fn add_with(&self,
+        name:       impl Into<String>,
+        value:      impl Into<String>,
+        directives: impl FnOnce(SetCookieBuilder)->SetCookieBuilder,
+    ) -> Result<(), impl std::error::Error> {
+        self.try_add(name.into(), value.into(), directives)
+    }
+
+    /// Add or update the Cookies serialized from a map or map-like struct,
+    /// with the same directives. `None` fields are skipped.
+    pub fn add_all(&self,
+        cookies:    &impl serde::Serialize,
+        directives: impl Fn(SetCookieBuilder)->SetCookieBuilder,
+    ) -> Result<(), impl std::error::Error> {
+        for (name, value) in ohkami_lib::serde_cookie::to_pairs(cookies)? {
+            self.try_add(name, value, &directives)?;
+        }
+        Ok::<_, ohkami_lib::serde_cookie::Error>(())
+    }
+
+    /// Remove Cookie `name` from the client.
+    ///
+    /// Fails if `name` is not a valid Cookie name.
+    pub fn remove(&self, name: impl Into<String>) -> Result<(), impl std::error::Error> {
+        self.remove_with(name, |d| d)
+    }
+
+    /// Remove Cookie `name` from the client. `Path` and `Domain` must be
+    /// the same as those the Cookie was set with.
+    ///
+    /// Fails if `name` is not a valid Cookie name.
+    pub fn remove_with(&self,
+        name:       impl Into<String>,
+        directives: impl FnOnce(SetCookieBuilder)->SetCookieBuilder,
+    ) -> Result<(), impl std::error::Error> {
+        let name = name.into();
+        let setcookie = directives(SetCookieBuilder::new(name.clone(), ""))
+            .MaxAge(0)
+            .ExpiresAt(0)
+            .build()?;
+        self.change(Change { name, value: None, setcookie });
+        Ok::<_, ohkami_lib::serde_cookie::Error>(())
+    }
+
+    pub(crate) fn try_add(&self,
+        name:       String,
+        value:      String,
+        directives: impl FnOnce(SetCookieBuilder)->SetCookieBuilder,
+    ) -> Result<(), ohkami_lib::serde_cookie::Error> {
+        let setcookie = directives(SetCookieBuilder::new(name.clone(), value.clone())).build()?;
+        self.change(Change { name, value: Some(value), setcookie });
+        Ok(())
+    }
+
+    fn change(&self, change: Change) {
+        let mut jar = self.0.lock().unwrap();
+        jar.changes.retain(|c| c.name != change.name);
+        jar.changes.push(change);
+    }
+}
+
+impl<'req> FromRequest<'req> for CookieJar {
+    type Error = std::convert::Infallible;
+
+    fn from_request(req: &'req Request) -> Option<Result<Self, Self::Error>> {
+        match req.context.get::<CookieJar>() {
+            Some(jar) => Some(Ok(jar.clone())),
+            None => {
+                #[cfg(debug_assertions)] {
+                    crate::warning!("`CookieJar` requires `CookieJar::fang()`")
+                }
+                None
+            }
+        }
+    }
+}
+
+/// Fang of [`CookieJar::fang`]
+#[derive(Clone)]
+pub struct CookieJarFang;
+
+impl<Inner: FangProc> Fang<Inner> for CookieJarFang {
+    type Proc = CookieJarProc<Inner>;
+    fn chain(&self, inner: Inner) -> Self::Proc {
+        CookieJarProc { inner }
+    }
+}
+
+pub struct CookieJarProc<Inner: FangProc> {
+    inner: Inner,
+}
+impl<Inner: FangProc> FangProc for CookieJarProc<Inner> {
+    async fn bite<'b>(&'b self, req: &'b mut Request) -> Response {
+        let jar = CookieJar::of(req);
+        req.context.set(jar.clone());
+
+        let mut res = self.inner.bite(req).await;
+
+        for change in std::mem::take(&mut jar.0.lock().unwrap().changes) {
+            res.headers.push_setcookie(change.setcookie);
+        }
+
+        res
+    }
+}
+
+
+#[cfg(all(test, debug_assertions, feature="__rt_native__", feature="DEBUG"))]
+#[test] fn test_cookie_jar() {
+    use crate::prelude::*;
+    use crate::testing::*;
+
+    #[derive(::serde::Serialize)]
+    struct Preferences {
+        theme: &'static str,
+        lang:  Option<&'static str>,
+    }
+
+    async fn visit(jar: CookieJar) -> String {
+        let count = jar.get("visits")
+            .and_then(|v| v.parse::<u64>().ok())
+            .unwrap_or(0) + 1;
+        jar.add_with("visits", count.to_string(), |d| d.Path("/").HttpOnly()).unwrap();
+        jar.add("name", "狼 ohkami").unwrap();
+        jar.remove_with("legacy", |d| d.Path("/")).unwrap();
+        assert!(jar.add("a;b", "1").is_err());
+        assert!(jar.remove("a\r\nb").is_err());
+        jar.add_all(&Preferences { theme: "dark", lang: None }, |d| d.SameSiteLax()).unwrap();
+
+        assert_eq!(jar.get("legacy"), None);
+        assert_eq!(jar.get("name").as_deref(), Some("狼 ohkami"));
+        format!("{count}")
+    }
+
+    async fn list(jar: CookieJar) -> String {
+        jar.cookies().into_iter()
+            .map(|(n, v)| format!("{n}={v}"))
+            .collect::<Vec<_>>()
+            .join(";")
+    }
+
+    crate::__rt__::testing::block_on(async {
+        let t = Ohkami::new((CookieJar::fang(),
+            "/visit".GET(visit),
+            "/list".GET(list),
+        )).test();
+
+        let res = t.oneshot(TestRequest::GET("/visit")
+            .header("Cookie", "visits=41; legacy=1")
+        ).await;
+        assert_eq!(res.text(), Some("42"));
+        assert_eq!(res.headers().filter(|(n, _)| *n == "Set-Cookie").map(|(_, v)| v).collect::<Vec<_>>(), [
+            "visits=42; Path=/; HttpOnly",
+            "name=%E7%8B%BC%20ohkami",
+            "legacy=; Expires=Thu, 01 Jan 1970 00:00:00 GMT; Max-Age=0; Path=/",
+            "theme=dark; SameSite=Lax",
+        ]);
+
+        let res = t.oneshot(TestRequest::GET("/visit")).await;
+        assert_eq!(res.text(), Some("1"));
+
+        let res = t.oneshot(TestRequest::GET("/list")
+            .header("Cookie", "a=1; b=%E7%8B%BC")
+        ).await;
+        assert_eq!(res.text(), Some("a=1;b=狼"));
+        assert_eq!(res.headers().filter(|(n, _)| *n == "Set-Cookie").count(), 0);
+    });
+}
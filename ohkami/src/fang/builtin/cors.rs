@@ -1,12 +1,23 @@
 #![allow(non_snake_case)]
 
 use crate::{header::append, Fang, FangProc, Request, Response, Status};
+use std::sync::Arc;
 
 
 /// # Builtin fang for CORS config
 /// 
 /// <br>
 /// 
+/// Allowed origins are given by
+/// 
+/// - `CORS::new` : `"*"`, an origin, or a wildcard-subdomain pattern like `"https://*.preview.example.com"`
+/// - `CORS::origins` : a list of origins and wildcard-subdomain patterns
+/// - `CORS::origin_fn` : a predicate on the request's `Origin`
+/// 
+/// For patterns, lists and predicates, the matched request's `Origin` is reflected
+/// in `Access-Control-Allow-Origin` with `Vary: Origin`, and no CORS headers are
+/// set for an unmatched one, whose preflight gets `403 Forbidden`.
+/// 
 /// *example.rs*
 /// ```no_run
 /// use ohkami::prelude::*;
@@ -24,46 +35,112 @@ use crate::{header::append, Fang, FangProc, Request, Response, Status};
 ///     )).howl("localhost:8080").await
 /// }
 /// ```
+/// 
+/// *multi_origin.rs*
+/// ```no_run
+/// use ohkami::prelude::*;
+/// use ohkami::fang::CORS;
+/// 
+/// #[tokio::main]
+/// async fn main() {
+///     Ohkami::new((
+///         CORS::origins([
+///             "https://app.example.com",
+///             "https://staging.example.com",
+///             "https://*.preview.example.com",
+///         ])
+///             .AllowCredentials()
+///             .AllowPrivateNetwork(),
+///         "/api"
+///             .GET(|| async {"Hello, CORS!"}),
+///     )).howl("localhost:8080").await
+/// }
+/// ```
 #[derive(Clone)]
 pub struct CORS {
-    pub(crate) AllowOrigin:      AccessControlAllowOrigin,
-    pub(crate) AllowCredentials: bool,
-    pub(crate) AllowMethods:     Option<String>,
-    pub(crate) AllowHeaders:     Option<String>,
-    pub(crate) ExposeHeaders:    Option<String>,
-    pub(crate) MaxAge:           Option<u32>,
+    pub(crate) AllowOrigin:         AccessControlAllowOrigin,
+    pub(crate) AllowCredentials:    bool,
+    pub(crate) AllowMethods:        Option<String>,
+    pub(crate) AllowHeaders:        Option<String>,
+    pub(crate) ExposeHeaders:       Option<String>,
+    pub(crate) MaxAge:              Option<u32>,
+    pub(crate) AllowPrivateNetwork: bool,
 }
 
 #[derive(Clone)]
 pub(crate) enum AccessControlAllowOrigin {
     Any,
     Only(&'static str),
+    /// reflecting the request's `Origin` when it satisfies the predicate
+    Dynamic(Arc<dyn Fn(&str) -> bool + Send + Sync>),
 } impl AccessControlAllowOrigin {
     #[inline(always)] pub(crate) const fn is_any(&self) -> bool {
+        matches!(self, Self::Any)
+    }
+
+    pub(crate) fn from_literal(lit: &'static str) -> Self {
+        match lit {
+            "*" => Self::Any,
+            pattern if pattern.contains('*') => Self::from_patterns([pattern]),
+            origin => Self::Only(origin),
+        }
+    }
+
+    pub(crate) fn from_patterns(patterns: impl IntoIterator<Item = &'static str>) -> Self {
+        let patterns = patterns.into_iter().map(OriginPattern::parse).collect::<Vec<_>>();
+        Self::Dynamic(Arc::new(move |origin| patterns.iter().any(|p| p.matches(origin))))
+    }
+
+    /// `Access-Control-Allow-Origin` value for the request's `Origin`,
+    /// `None` when it's not allowed
+    pub(crate) fn for_origin(&self, origin: Option<&str>) -> Option<String> {
         match self {
-            Self::Any => true,
-            _ => false,
+            Self::Any          => Some(String::from("*")),
+            Self::Only(origin) => Some(String::from(*origin)),
+            Self::Dynamic(f)   => origin.filter(|o| f(o)).map(String::from),
         }
     }
+}
 
-    #[inline(always)] pub(crate) const fn from_literal(lit: &'static str) -> Self {
-        match lit.as_bytes() {
-            b"*"   => Self::Any,
-            origin => Self::Only(unsafe{std::str::from_utf8_unchecked(origin)}),
+enum OriginPattern {
+    Exact(&'static str),
+    /// `{scheme}://*.{domain}`, holding `{scheme}://` and `.{domain}`
+    Subdomain(&'static str, &'static str),
+} impl OriginPattern {
+    fn parse(pattern: &'static str) -> Self {
+        match pattern.split_once("://*.") {
+            Some((scheme, _)) => {
+                let (prefix, suffix) = pattern.split_at(scheme.len() + "://".len());
+                Self::Subdomain(prefix, &suffix[1..])
+            }
+            None => {
+                #[cfg(debug_assertions)] if pattern.contains('*') {
+                    crate::warning!("CORS origin pattern `{pattern}` is used as an exact origin: \
+                        only a wildcard subdomain like `https://*.example.com` is supported");
+                }
+                Self::Exact(pattern)
+            }
         }
     }
 
-    #[inline(always)] pub(crate) const fn as_str(&self) -> &'static str {
+    fn matches(&self, origin: &str) -> bool {
         match self {
-            Self::Any          => "*",
-            Self::Only(origin) => origin,
+            Self::Exact(exact) => origin == *exact,
+            Self::Subdomain(prefix, suffix) => origin
+                .strip_prefix(prefix)
+                .and_then(|rest| rest.strip_suffix(suffix))
+                .is_some_and(|subdomain| {
+                    !subdomain.is_empty() &&
+                    !subdomain.starts_with('.') &&
+                    subdomain.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'.')
+                })
         }
     }
 }
 
 impl CORS {
     /// Create `CORS` fang using given `AllowOrigin` as `Access-Control-Allow-Origin` header value.\
-    /// (Both `"*"` and a speciffic origin are available)
+    /// (`"*"`, a speciffic origin and a wildcard-subdomain pattern are available)
     #[allow(non_snake_case)]
     pub fn new(AllowOrigin: &'static str) -> Self {
         Self {
@@ -73,6 +150,24 @@ impl CORS {
             AllowHeaders:     None,
             ExposeHeaders:    None,
             MaxAge:           None,
+            AllowPrivateNetwork: false,
+        }
+    }
+
+    /// Create `CORS` fang allowing any of the origins or wildcard-subdomain patterns
+    /// like `"https://*.preview.example.com"`.
+    pub fn origins(origins: impl IntoIterator<Item = &'static str>) -> Self {
+        Self {
+            AllowOrigin: AccessControlAllowOrigin::from_patterns(origins),
+            ..Self::new("*")
+        }
+    }
+
+    /// Create `CORS` fang allowing the origins satisfying `predicate`.
+    pub fn origin_fn(predicate: impl Fn(&str) -> bool + Send + Sync + 'static) -> Self {
+        Self {
+            AllowOrigin: AccessControlAllowOrigin::Dynamic(Arc::new(predicate)),
+            ..Self::new("*")
         }
     }
 
@@ -109,6 +204,12 @@ impl CORS {
         self.MaxAge = Some(delta_seconds);
         self
     }
+    /// Respond `Access-Control-Allow-Private-Network: true` to preflights with
+    /// `Access-Control-Request-Private-Network: true` for Private Network Access.
+    pub fn AllowPrivateNetwork(mut self) -> Self {
+        self.AllowPrivateNetwork = true;
+        self
+    }
 }
 
 impl<Inner: FangProc> Fang<Inner> for CORS {
@@ -127,12 +228,21 @@ impl<Inner: FangProc> FangProc for CORSProc<Inner> {
     async fn bite<'b>(&'b self, req: &'b mut Request) -> Response {
         let mut res = self.inner.bite(req).await;
 
+        let Some(allow_origin) = self.cors.AllowOrigin.for_origin(req.headers.Origin()) else {
+            /* not to leave the default `Not Implemented` for a preflight from a rejected origin */
+            if req.method.isOPTIONS() && res.status == Status::NotImplemented {
+                res = crate::typed::reject(Status::Forbidden, "");
+            }
+            res.headers.set().Vary(append("Origin"));
+            return res
+        };
+
         let mut h = res.headers.set();
 
-        h = h.AccessControlAllowOrigin(self.cors.AllowOrigin.as_str());
-        if self.cors.AllowOrigin.is_any() {
-            h = h.Vary("Origin");
+        if !matches!(self.cors.AllowOrigin, AccessControlAllowOrigin::Only(_)) {
+            h = h.Vary(append("Origin"));
         }
+        h = h.AccessControlAllowOrigin(allow_origin);
         if self.cors.AllowCredentials {
             h = h.AccessControlAllowCredentials("true");
         }
@@ -153,6 +263,10 @@ impl<Inner: FangProc> FangProc for CORSProc<Inner> {
                 h = h.AccessControlAllowHeaders(allow_headers.to_string())
                     .Vary(append("Access-Control-Request-Headers"));
             }
+            if self.cors.AllowPrivateNetwork
+            && req.headers.get("Access-Control-Request-Private-Network") == Some("true") {
+                h = h.x("Access-Control-Allow-Private-Network", "true");
+            }
 
             /* override default `Not Implemented` response for valid preflight */
             if res.status == Status::NotImplemented {
@@ -357,4 +471,94 @@ mod test {
             }
         });
     }
+
+    #[cfg(all(feature="__rt_native__", feature="DEBUG"))]
+    #[test] fn multi_origin_cors() {
+        use crate::prelude::*;
+        use crate::testing::*;
+        use super::CORS;
+
+        crate::__rt__::testing::block_on(async {
+            let t = Ohkami::new((
+                CORS::origins([
+                    "https://app.example.com",
+                    "https://staging.example.com",
+                    "https://*.preview.example.com",
+                ]).AllowCredentials(),
+                "/".GET(|| async {"Hello!"}).PUT(|| async {"Hello!"})
+            )).test();
+
+            for (origin, allowed) in [
+                ("https://app.example.com",           true),
+                ("https://staging.example.com",       true),
+                ("https://pr-42.preview.example.com", true),
+                ("https://a.b.preview.example.com",   true),
+                ("https://preview.example.com",       false),
+                ("https://.preview.example.com",      false),
+                ("http://pr-42.preview.example.com",  false),
+                ("https://evil.com/.preview.example.com", false),
+                ("https://app.example.com.evil.com",  false),
+                ("null",                              false),
+            ] {
+                let res = t.oneshot(TestRequest::GET("/").header("Origin", origin)).await;
+                assert_eq!(res.status().code(), 200);
+                assert_eq!(res.header("Vary"), Some("Origin"));
+                if allowed {
+                    assert_eq!(res.header("Access-Control-Allow-Origin"), Some(origin));
+                    assert_eq!(res.header("Access-Control-Allow-Credentials"), Some("true"));
+                } else {
+                    assert_eq!(res.header("Access-Control-Allow-Origin"), None, "{origin}");
+                    assert_eq!(res.header("Access-Control-Allow-Credentials"), None);
+                }
+            }
+
+            let res = t.oneshot(TestRequest::GET("/")).await;
+            assert_eq!(res.header("Access-Control-Allow-Origin"), None);
+            assert_eq!(res.header("Vary"), Some("Origin"));
+
+            let t = Ohkami::new((
+                CORS::origin_fn(|origin| origin.ends_with(".localhost:3000"))
+                    .AllowPrivateNetwork(),
+                "/".GET(|| async {"Hello!"})
+            )).test(); {
+                let res = t.oneshot(TestRequest::OPTIONS("/")
+                    .header("Origin", "http://app.localhost:3000")
+                    .header("Access-Control-Request-Method", "GET")
+                    .header("Access-Control-Request-Private-Network", "true")
+                ).await;
+                assert_eq!(res.status().code(), 200);
+                assert_eq!(res.header("Access-Control-Allow-Origin"), Some("http://app.localhost:3000"));
+                assert_eq!(res.headers().find(|(n, _)| *n == "Access-Control-Allow-Private-Network").map(|(_, v)| v), Some("true"));
+                assert_eq!(res.header("Vary"), Some("Origin"));
+            } {
+                let res = t.oneshot(TestRequest::OPTIONS("/")
+                    .header("Origin", "http://app.localhost:3000")
+                    .header("Access-Control-Request-Method", "GET")
+                ).await;
+                assert_eq!(res.headers().find(|(n, _)| *n == "Access-Control-Allow-Private-Network"), None);
+            } {
+                let res = t.oneshot(TestRequest::OPTIONS("/")
+                    .header("Origin", "http://evil.example")
+                    .header("Access-Control-Request-Method", "GET")
+                    .header("Access-Control-Request-Private-Network", "true")
+                ).await;
+                assert_eq!(res.status().code(), 403);
+                assert_eq!(res.header("Access-Control-Allow-Origin"), None);
+                assert_eq!(res.header("Access-Control-Allow-Methods"), None);
+                assert_eq!(res.header("Vary"), Some("Origin"));
+                assert_eq!(res.headers().find(|(n, _)| *n == "Access-Control-Allow-Private-Network"), None);
+            }
+
+            /* wildcard-subdomain pattern by `CORS::new` */
+            let t = Ohkami::new((
+                CORS::new("https://*.example.com"),
+                "/".GET(|| async {"Hello!"})
+            )).test(); {
+                let res = t.oneshot(TestRequest::GET("/").header("Origin", "https://api.example.com")).await;
+                assert_eq!(res.header("Access-Control-Allow-Origin"), Some("https://api.example.com"));
+                let res = t.oneshot(TestRequest::GET("/").header("Origin", "https://example.com")).await;
+                assert_eq!(res.header("Access-Control-Allow-Origin"), None);
+            }
+        });
+    }
 }
@@ -0,0 +1,386 @@
+use crate::{Fang, FangProc, FromRequest, Request, Response, Method, Status};
+use crate::typed::reject;
+use crate::header::SetCookieBuilder;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+
+/// # Builtin fang for CSRF protection
+///
+/// <br>
+///
+/// Protect unsafe requests ( `POST`, `PUT`, `PATCH`, `DELETE` ) from
+/// cross-site request forgery by the double-submit Cookie pattern:
+///
+/// - A random token is issued as a Cookie, and available as [`CSRFToken`]
+///   in handlers to render into forms or `<meta>`s.
+/// - Unsafe requests must send the same token by the `X-CSRF-Token` header
+///   or the `csrf_token` field of a `application/x-www-form-urlencoded` form.
+///
+/// In addition, unsafe requests with `Origin` other than the request's own
+/// origin or the trusted origins are rejected, and so are ones without `Origin`
+/// but with `Sec-Fetch-Site: cross-site`. The request's own scheme is `http`
+/// unless a proxy reports otherwise by `X-Forwarded-Proto` or `Forwarded`.
+///
+/// Rejected requests get `403 Forbidden`, as a `Problem` when
+/// [`Problem::use_for_rejections`](crate::typed::Problem::use_for_rejections)
+/// is enabled.
+///
+/// Available with `csrf` feature.
+///
+/// <br>
+///
+/// ---
+/// *example.rs*
+/// ```no_run
+/// use ohkami::prelude::*;
+/// use ohkami::fang::{CSRF, CSRFToken};
+/// use ohkami::format::{HTML, URLEncoded};
+///
+/// async fn form(token: CSRFToken) -> HTML<String> {
+///     HTML(format!(r#"<form method="post" action="/posts">
+///         <input type="hidden" name="csrf_token" value="{token}">
+///         <input name="title">
+///         <button>post</button>
+///     </form>"#))
+/// }
+///
+/// #[derive(Deserialize)]
+/// struct NewPost<'req> {
+///     title: &'req str,
+/// }
+///
+/// async fn post(URLEncoded(new): URLEncoded<NewPost<'_>>) -> String {
+///     format!("posted `{}`", new.title)
+/// }
+///
+/// #[tokio::main]
+/// async fn main() {
+///     Ohkami::new((
+///         CSRF::new()
+///             .trusted_origins(["https://admin.example.com"]),
+///         "/posts/new".GET(form),
+///         "/posts".POST(post),
+///     )).howl("localhost:3000").await
+/// }
+/// ```
+/// ---
+#[derive(Clone)]
+pub struct CSRF(
+    Arc<Config>
+);
+
+struct Config {
+    cookie_name:     &'static str,
+    header_name:     &'static str,
+    field_name:      &'static str,
+    directives:      Box<dyn Fn(SetCookieBuilder)->SetCookieBuilder + Send + Sync>,
+    trusted_origins: Vec<&'static str>,
+}
+
+impl CSRF {
+    pub fn new() -> Self {
+        Self(Arc::new(Config {
+            cookie_name:     "csrf_token",
+            header_name:     "X-CSRF-Token",
+            field_name:      "csrf_token",
+            directives:      Box::new(|d| d.Path("/").HttpOnly().SameSiteLax()),
+            trusted_origins: Vec::new(),
+        }))
+    }
+
+    /// Name of the token Cookie ( default: `csrf_token` )
+    pub fn cookie_name(mut self, name: &'static str) -> Self {
+        self.config_mut().cookie_name = name;
+        self
+    }
+
+    /// Directives of the token Cookie
+    /// ( default: `Path=/; HttpOnly; SameSite=Lax` )
+    pub fn cookie(mut self, directives: impl Fn(SetCookieBuilder)->SetCookieBuilder + Send + Sync + 'static) -> Self {
+        self.config_mut().directives = Box::new(directives);
+        self
+    }
+
+    /// Request header to send the token ( default: `X-CSRF-Token` )
+    pub fn header_name(mut self, name: &'static str) -> Self {
+        self.config_mut().header_name = name;
+        self
+    }
+
+    /// Form field to send the token ( default: `csrf_token` )
+    pub fn field_name(mut self, name: &'static str) -> Self {
+        self.config_mut().field_name = name;
+        self
+    }
+
+    /// Origins allowed in addition to the request's `Host`,
+    /// like `https://admin.example.com`
+    pub fn trusted_origins(mut self, origins: impl IntoIterator<Item = &'static str>) -> Self {
+        self.config_mut().trusted_origins.extend(origins);
+        self
+    }
+
+    fn config_mut(&mut self) -> &mut Config {
+        Arc::get_mut(&mut self.0).expect("`CSRF` is configured before cloned")
+    }
+}
+
+impl Default for CSRF {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+
+/// # CSRF token of the request
+///
+/// Available in handlers under [`CSRF`] fang, to render into forms or
+/// `<meta>`s. `Display`s as the token itself.
+#[derive(Clone)]
+pub struct CSRFToken(
+    String
+);
+
+impl CSRFToken {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for CSRFToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl<'req> FromRequest<'req> for CSRFToken {
+    type Error = std::convert::Infallible;
+
+    fn from_request(req: &'req Request) -> Option<Result<Self, Self::Error>> {
+        match req.context.get::<CSRFToken>() {
+            Some(token) => Some(Ok(token.clone())),
+            None => {
+                #[cfg(debug_assertions)] {
+                    crate::warning!("`CSRFToken` requires `CSRF` fang")
+                }
+                None
+            }
+        }
+    }
+}
+
+fn new_token() -> String {
+    let mut bytes = [0; 32];
+    getrandom::getrandom(&mut bytes).expect("failed to generate a CSRF token");
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn is_valid_token(token: &str) -> bool {
+    token.len() == 64 && token.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// compare in constant time for the same length
+fn equals(a: &str, b: &str) -> bool {
+    a.len() == b.len() && a.bytes().zip(b.bytes()).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+impl Config {
+    fn is_safe_method(method: Method) -> bool {
+        matches!(method, Method::GET | Method::HEAD | Method::OPTIONS)
+    }
+
+    fn is_allowed_origin(&self, req: &Request) -> bool {
+        match req.headers.Origin() {
+            Some(origin) => {
+                let same_origin = origin.split_once("://").is_some_and(|(scheme, authority)|
+                    scheme.eq_ignore_ascii_case(scheme_of(req)) && Some(authority) == req.headers.Host()
+                );
+                same_origin || self.trusted_origins.contains(&origin)
+            }
+            /* `Sec-Fetch-Site` is only a fallback for requests without `Origin` */
+            None => req.headers.SecFetchSite() != Some("cross-site"),
+        }
+    }
+
+    fn submitted_token<'req>(&self, req: &'req Request) -> Option<Cow<'req, str>> {
+        if let Some(token) = req.headers.get(self.header_name) {
+            return Some(Cow::Borrowed(token))
+        }
+        if req.headers.ContentType()?.starts_with("application/x-www-form-urlencoded") {
+            let mut form = ohkami_lib::serde_urlencoded::from_bytes::<HashMap<Cow<str>, Cow<str>>>(req.payload()?).ok()?;
+            return form.remove(self.field_name)
+        }
+        None
+    }
+}
+
+
+impl<Inner: FangProc> Fang<Inner> for CSRF {
+    type Proc = CSRFProc<Inner>;
+    fn chain(&self, inner: Inner) -> Self::Proc {
+        CSRFProc { csrf: self.clone(), inner }
+    }
+}
+
+pub struct CSRFProc<Inner: FangProc> {
+    csrf:  CSRF,
+    inner: Inner,
+}
+impl<Inner: FangProc> FangProc for CSRFProc<Inner> {
+    async fn bite<'b>(&'b self, req: &'b mut Request) -> Response {
+        let config = &*self.csrf.0;
+
+        let cookie_token = req.headers.Cookies()
+            .find(|(name, _)| *name == config.cookie_name)
+            .map(|(_, token)| token)
+            .filter(|token| is_valid_token(token))
+            .map(String::from);
+
+        if !Config::is_safe_method(req.method) {
+            if !config.is_allowed_origin(req) {
+                crate::DEBUG!("[CSRF] rejected origin: Origin = {:?}, Sec-Fetch-Site = {:?}",
+                    req.headers.Origin(), req.headers.SecFetchSite());
+                return reject(Status::Forbidden, "cross-origin request")
+            }
+            let verified = match (&cookie_token, config.submitted_token(req)) {
+                (Some(expected), Some(submitted)) => equals(expected, &submitted),
+                _ => false
+            };
+            if !verified {
+                crate::DEBUG!("[CSRF] missing or mismatched token");
+                return reject(Status::Forbidden, "missing or invalid CSRF token")
+            }
+        }
+
+        let (token, is_new) = match cookie_token {
+            Some(token) => (token, false),
+            None        => (new_token(), true),
+        };
+        req.context.set(CSRFToken(token.clone()));
+
+        let mut res = self.inner.bite(req).await;
+
+        if is_new {
+            match (config.directives)(SetCookieBuilder::new(config.cookie_name, token)).build() {
+                Ok(setcookie) => res.headers.push_setcookie(setcookie),
+                Err(e) => crate::warning!("[CSRF] failed to set the token Cookie: {e}"),
+            }
+        }
+
+        res
+    }
+}
+
+
+/// Scheme of the request, `http` unless a proxy reports otherwise
+fn scheme_of(req: &Request) -> &str {
+    if let Some(proto) = req.headers.get("X-Forwarded-Proto") {
+        return proto.split(',').next().unwrap_or_default().trim()
+    }
+    if let Some(forwarded) = req.headers.Forwarded() {
+        let first = forwarded.split(',').next().unwrap_or_default();
+        if let Some(proto) = first.split(';').find_map(|pair| {
+            let (key, value) = pair.trim().split_once('=')?;
+            key.eq_ignore_ascii_case("proto").then(|| value.trim_matches('"'))
+        }) {
+            return proto
+        }
+    }
+    "http"
+}
+
+#[cfg(all(test, debug_assertions, feature="__rt_native__", feature="DEBUG"))]
+#[test] fn test_csrf() {
+    use crate::prelude::*;
+    use crate::testing::*;
+
+    async fn form(token: CSRFToken) -> String {
+        format!("{token}")
+    }
+    async fn post() -> &'static str {
+        "posted"
+    }
+
+    crate::__rt__::testing::block_on(async {
+        let t = Ohkami::new((
+            CSRF::new().trusted_origins(["https://admin.example.com"]),
+            "/form".GET(form),
+            "/post".POST(post),
+        )).test();
+
+        /* a token is issued on a safe request */
+        let res = t.oneshot(TestRequest::GET("/form")).await;
+        let token = res.text().unwrap().to_string();
+        assert_eq!(token.len(), 64);
+        assert_eq!(res.headers().find(|(n, _)| *n == "Set-Cookie").unwrap().1,
+            format!("csrf_token={token}; Path=/; HttpOnly; SameSite=Lax"));
+
+        /* and reused while the Cookie is sent */
+        let res = t.oneshot(TestRequest::GET("/form")
+            .header("Cookie", format!("csrf_token={token}"))
+        ).await;
+        assert_eq!(res.text(), Some(&*token));
+        assert_eq!(res.headers().filter(|(n, _)| *n == "Set-Cookie").count(), 0);
+
+        let res = t.oneshot(TestRequest::POST("/post")
+            .header("Cookie", format!("csrf_token={token}"))
+            .header("X-CSRF-Token", token.clone())
+        ).await;
+        assert_eq!(res.text(), Some("posted"));
+
+        let res = t.oneshot(TestRequest::POST("/post")
+            .header("Cookie", format!("csrf_token={token}"))
+            .content("application/x-www-form-urlencoded", format!("title=hello&csrf_token={token}").into_bytes())
+        ).await;
+        assert_eq!(res.text(), Some("posted"));
+
+        /* missing or mismatched token */
+        let res = t.oneshot(TestRequest::POST("/post")
+            .header("Cookie", format!("csrf_token={token}"))
+        ).await;
+        assert_eq!(res.status(), Status::Forbidden);
+        assert_eq!(res.text(), Some("missing or invalid CSRF token"));
+        let res = t.oneshot(TestRequest::POST("/post")
+            .header("X-CSRF-Token", token.clone())
+        ).await;
+        assert_eq!(res.status(), Status::Forbidden);
+        let res = t.oneshot(TestRequest::POST("/post")
+            .header("Cookie", format!("csrf_token={token}"))
+            .header("X-CSRF-Token", "0".repeat(64))
+        ).await;
+        assert_eq!(res.status(), Status::Forbidden);
+
+        /* origin checks */
+        for (origin, sec_fetch_site, proxy, expected) in [
+            (Some("http://localhost:3000"),     None,                None,                                Status::OK),
+            (Some("https://admin.example.com"), Some("same-site"),   None,                                Status::OK),
+            (Some("https://admin.example.com"), Some("cross-site"),  None,                                Status::OK),
+            (None,                              Some("same-origin"), None,                                Status::OK),
+            (Some("https://localhost:3000"),    None,                Some(("X-Forwarded-Proto", "https")), Status::OK),
+            (Some("https://localhost:3000"),    None,                Some(("Forwarded", "proto=https")),   Status::OK),
+            (Some("https://localhost:3000"),    None,                None,                                Status::Forbidden),
+            (Some("http://localhost:3000"),     None,                Some(("X-Forwarded-Proto", "https")), Status::Forbidden),
+            (Some("https://evil.example"),      Some("same-origin"), None,                                Status::Forbidden),
+            (Some("null"),                      None,                None,                                Status::Forbidden),
+            (None,                              Some("cross-site"),  None,                                Status::Forbidden),
+        ] {
+            let mut req = TestRequest::POST("/post")
+                .header("Host", "localhost:3000")
+                .header("Cookie", format!("csrf_token={token}"))
+                .header("X-CSRF-Token", token.clone());
+            if let Some(origin) = origin {
+                req = req.header("Origin", origin);
+            }
+            if let Some(sec_fetch_site) = sec_fetch_site {
+                req = req.header("Sec-Fetch-Site", sec_fetch_site);
+            }
+            if let Some((name, value)) = proxy {
+                req = req.header(name, value);
+            }
+            let res = t.oneshot(req).await;
+            assert_eq!(res.status(), expected, "Origin: {origin:?}, Sec-Fetch-Site: {sec_fetch_site:?}, {proxy:?}");
+        }
+    });
+}
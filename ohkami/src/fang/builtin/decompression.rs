@@ -0,0 +1,203 @@
+#![cfg(any(feature="gzip", feature="br", feature="zstd"))]
+
+use crate::header::encoding::Encoding;
+use crate::typed::reject;
+use crate::{Fang, FangProc, Request, Response, Status};
+
+
+/// # Builtin fang for request body decompression
+/// 
+/// <br>
+/// 
+/// Decode the request payload by its `Content-Encoding` before handlers
+/// and `FromBody` extractors like `JSON` see it. Available encodings depend
+/// on the enabled features:
+/// 
+/// - `br` by `br` feature
+/// - `zstd` by `zstd` feature
+/// - `gzip`, `deflate` by `gzip` feature
+/// 
+/// After decoding, `Content-Encoding` is removed from the request and
+/// `Content-Length` is updated to the decoded size.
+/// 
+/// Responds
+/// 
+/// - `415 Unsupported Media Type` ( with `Accept-Encoding` of the available ones )
+///   for unknown or disabled encodings
+/// - `413 Payload Too Large` when the decoded payload exceeds the `limit`
+///   ( default: 16 MiB ), to defend against decompression bombs
+/// - `400 Bad Request` for corrupted payloads
+/// 
+/// <br>
+/// 
+/// ---
+/// *example.rs*
+/// ```no_run
+/// use ohkami::prelude::*;
+/// use ohkami::fang::Decompression;
+/// use ohkami::format::JSON;
+/// # use ohkami::serde::Deserialize;
+/// 
+/// # #[derive(Deserialize)]
+/// # struct Event {}
+/// async fn webhook(JSON(event): JSON<Event>) -> Status {
+///     Status::NoContent
+/// }
+/// 
+/// #[tokio::main]
+/// async fn main() {
+///     Ohkami::new((Decompression::new().limit(1 << 20),
+///         "/webhook".POST(webhook),
+///     )).howl("localhost:3000").await
+/// }
+/// ```
+/// ---
+#[derive(Clone)]
+pub struct Decompression {
+    limit: usize,
+}
+
+impl Decompression {
+    pub fn new() -> Self {
+        Self { limit: 1 << 24 }
+    }
+
+    /// Maximum size in bytes of a decoded payload ( default: 16 MiB )
+    pub fn limit(mut self, bytes: usize) -> Self {
+        self.limit = bytes;
+        self
+    }
+}
+impl Default for Decompression {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Decompression {
+    /// decode the payload of `req`, or return the response rejecting it
+    fn decompress(&self, req: &mut Request) -> Option<Response> {
+        let content_encoding = req.headers.ContentEncoding()?;
+
+        /* codings are listed in the order they were applied */
+        let mut encodings = Vec::new();
+        for coding in content_encoding.split(',').map(str::trim).filter(|c| !c.is_empty()) {
+            if coding.eq_ignore_ascii_case("identity") {
+                continue
+            }
+            match Encoding::from_str(coding).filter(|e| Encoding::AVAILABLE.contains(e)) {
+                Some(e) => encodings.push(e),
+                None => return Some(reject(Status::UnsupportedMediaType, "").with_headers(|h| h
+                    .x("Accept-Encoding", Encoding::AVAILABLE.iter().map(Encoding::as_str).collect::<Vec<_>>().join(", "))
+                ))
+            }
+        }
+
+        if let Some(payload) = req.payload.take() {
+            let mut decoded = payload;
+            for e in encodings.into_iter().rev() {
+                decoded = match e.decode(&decoded, self.limit) {
+                    Ok(Some(decoded)) => decoded.into(),
+                    Ok(None) => return Some(reject(Status::PayloadTooLarge, "")),
+                    Err(_) => return Some(reject(Status::BadRequest,
+                        format!("payload is not valid `{}`-encoded", e.as_str())
+                    )),
+                };
+            }
+            req.headers.set().ContentLength(decoded.len().to_string());
+            req.payload = Some(decoded);
+        }
+        req.headers.set().ContentEncoding(None);
+
+        None
+    }
+}
+
+impl<Inner: FangProc> Fang<Inner> for Decompression {
+    type Proc = DecompressionProc<Inner>;
+    fn chain(&self, inner: Inner) -> Self::Proc {
+        DecompressionProc { inner, decompression: self.clone() }
+    }
+}
+
+pub struct DecompressionProc<Inner: FangProc> {
+    decompression: Decompression,
+    inner:         Inner,
+}
+impl<Inner: FangProc> FangProc for DecompressionProc<Inner> {
+    async fn bite<'b>(&'b self, req: &'b mut Request) -> Response {
+        match self.decompression.decompress(req) {
+            None => self.inner.bite(req).await,
+            Some(rejection) => rejection,
+        }
+    }
+}
+
+
+#[cfg(all(test, debug_assertions, feature="__rt_native__", feature="DEBUG", feature="gzip"))]
+#[test] fn test_decompression() {
+    use crate::prelude::*;
+    use crate::testing::*;
+    async fn greet(req: &Request) -> String {
+        #[derive(serde::Deserialize)]
+        struct Greeting { name: String }
+
+        let g: Greeting = serde_json::from_slice(req.payload().unwrap()).unwrap();
+        assert_eq!(req.headers.ContentEncoding(), None);
+        assert_eq!(req.headers.ContentLength(), Some(&*req.payload().unwrap().len().to_string()));
+        format!("Hello, {}!", g.name)
+    }
+
+    let t = Ohkami::new((Decompression::new().limit(64),
+        "/greet".POST(greet),
+    )).test();
+
+    crate::__rt__::testing::block_on(async {
+        {
+            let req = TestRequest::POST("/greet")
+                .json_lit(r#"{"name":"ohkami"}"#);
+            let res = t.oneshot(req).await;
+            assert_eq!(res.text(), Some("Hello, ohkami!"));
+        }
+        {
+            let req = TestRequest::POST("/greet")
+                .header("Content-Encoding", "gzip")
+                .content("application/json", Encoding::Gzip.encode(br#"{"name":"ohkami"}"#).unwrap());
+            let res = t.oneshot(req).await;
+            assert_eq!(res.status(), Status::OK);
+            assert_eq!(res.text(), Some("Hello, ohkami!"));
+        }
+        {
+            let req = TestRequest::POST("/greet")
+                .header("Content-Encoding", "deflate, gzip")
+                .content("application/json", Encoding::Gzip.encode(
+                    &Encoding::Deflate.encode(br#"{"name":"ohkami"}"#).unwrap()
+                ).unwrap());
+            let res = t.oneshot(req).await;
+            assert_eq!(res.text(), Some("Hello, ohkami!"));
+        }
+        {
+            let req = TestRequest::POST("/greet")
+                .header("Content-Encoding", "compress")
+                .json_lit(r#"{"name":"ohkami"}"#);
+            let res = t.oneshot(req).await;
+            assert_eq!(res.status(), Status::UnsupportedMediaType);
+            assert!(res.header("Accept-Encoding").unwrap().contains("gzip"));
+        }
+        {
+            let req = TestRequest::POST("/greet")
+                .header("Content-Encoding", "gzip")
+                .json_lit(r#"{"name":"ohkami"}"#);
+            let res = t.oneshot(req).await;
+            assert_eq!(res.status(), Status::BadRequest);
+        }
+        {
+            let bomb = format!(r#"{{"name":"{}"}}"#, "o".repeat(1 << 16));
+            let req = TestRequest::POST("/greet")
+                .header("Content-Encoding", "gzip")
+                .content("application/json", Encoding::Gzip.encode(bomb.as_bytes()).unwrap());
+            let res = t.oneshot(req).await;
+            assert_eq!(res.status(), Status::PayloadTooLarge);
+        }
+    });
+}
@@ -0,0 +1,293 @@
+use crate::{Fang, FangProc, FromRequest, Method, Request, Response, Status};
+use crate::header::etag;
+use crate::response::Content;
+
+
+/// # Builtin fang for `ETag` and conditional requests
+///
+/// <br>
+///
+/// For `2xx` responses, set `ETag` by hashing the payload unless the handler
+/// has already set one ( e.g. by [`ETag::version`] ), and then for `GET` / `HEAD`
+///
+/// - answer `304 Not Modified` when `If-None-Match` matches,
+/// - answer `412 Precondition Failed` when `If-Match` doesn't match.
+///
+/// The current entity-tag for unsafe methods ( `POST`, `PUT`, `PATCH`, `DELETE` )
+/// is known only by the handler, and [`Preconditions`] checks `If-Match` /
+/// `If-None-Match` there before applying any changes.
+///
+/// <br>
+///
+/// ---
+/// *example.rs*
+/// ```no_run
+/// use ohkami::prelude::*;
+/// use ohkami::fang::{ETag, Preconditions};
+///
+/// async fn get_article() -> String {
+///     String::from("...")
+/// }
+///
+/// async fn update_article(
+///     preconditions: Preconditions<'_>,
+/// ) -> Result<Response, Response> {
+///     let current_version = 42; // fetched from DB
+///     preconditions.check(&ETag::version(current_version))?;
+///
+///     /* update the article */
+///
+///     let mut res = Response::NoContent();
+///     res.headers.set().ETag(ETag::version(current_version + 1));
+///     Ok(res)
+/// }
+///
+/// #[tokio::main]
+/// async fn main() {
+///     Ohkami::new((ETag::new(),
+///         "/article"
+///             .GET(get_article)
+///             .PUT(update_article),
+///     )).howl("localhost:3000").await
+/// }
+/// ```
+/// ---
+#[derive(Clone)]
+pub struct ETag {
+    weak: bool,
+}
+
+impl ETag {
+    /// Strong entity-tags, for byte-for-byte identical payloads.
+    pub fn new() -> Self {
+        Self { weak: false }
+    }
+
+    /// Weak entity-tags ( `W/"..."` ), for semantically equivalent payloads.
+    pub fn weak() -> Self {
+        Self { weak: true }
+    }
+
+    /// Strong entity-tag of a version of the resource, without hashing payloads:
+    /// `ETag::version(42)` is `"42"` .
+    pub fn version(version: impl std::fmt::Display) -> String {
+        format!("\"{version}\"")
+    }
+}
+impl Default for ETag {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ETag {
+    /// stable across builds and toolchains, unlike `std`'s `DefaultHasher`
+    fn tag(&self, payload: &[u8]) -> String {
+        use ::sha2::{Sha256, Digest};
+        let hash = Sha256::digest(payload);
+        let hash = u64::from_be_bytes(hash[..8].try_into().unwrap());
+        format!("{}\"{hash:016x}-{:x}\"", if self.weak {"W/"} else {""}, payload.len())
+    }
+}
+
+impl<Inner: FangProc> Fang<Inner> for ETag {
+    type Proc = ETagProc<Inner>;
+    fn chain(&self, inner: Inner) -> Self::Proc {
+        ETagProc { inner, etag: self.clone() }
+    }
+}
+
+pub struct ETagProc<Inner: FangProc> {
+    etag:  ETag,
+    inner: Inner,
+}
+impl<Inner: FangProc> FangProc for ETagProc<Inner> {
+    async fn bite<'b>(&'b self, req: &'b mut Request) -> Response {
+        let mut res = self.inner.bite(req).await;
+
+        if !matches!(res.status.code(), 200..=299) {
+            return res
+        }
+
+        if res.headers.ETag().is_none() {
+            if let Content::Payload(payload) = &res.content {
+                let tag = self.etag.tag(payload);
+                res.headers.set().ETag(tag);
+            }
+        }
+
+        if matches!(req.method, Method::GET | Method::HEAD) {
+            if let Some(etag) = res.headers.ETag() {
+                match Preconditions::of(req).evaluate(etag) {
+                    Some(Status::NotModified) => return not_modified(res),
+                    Some(status) => return crate::typed::reject(status, ""),
+                    None => ()
+                }
+            }
+        }
+
+        res
+    }
+}
+
+/// `304 Not Modified` keeping the headers it should have
+/// ( <https://httpwg.org/specs/rfc9110.html#status.304> )
+fn not_modified(res: Response) -> Response {
+    let mut not_modified = Response::NotModified();
+    for (name, value) in [
+        ("ETag",             res.headers.ETag()),
+        ("Cache-Control",    res.headers.CacheControl()),
+        ("Content-Location", res.headers.ContentLocation()),
+        ("Date",             res.headers.Date()),
+        ("Expires",          res.headers.Expires()),
+        ("Vary",             res.headers.Vary()),
+    ] {
+        if let Some(value) = value {
+            not_modified.headers.set().x(name, value.to_string());
+        }
+    }
+    not_modified
+}
+
+/// # `If-Match` / `If-None-Match` of a request
+///
+/// Evaluate them against the current entity-tag of the resource in a handler,
+/// typically before an unsafe method changes it. See [`ETag`] for an example.
+pub struct Preconditions<'req> {
+    method:        Method,
+    if_match:      Option<&'req str>,
+    if_none_match: Option<&'req str>,
+}
+
+impl<'req> Preconditions<'req> {
+    fn of(req: &'req Request) -> Self {
+        Self {
+            method:        req.method,
+            if_match:      req.headers.IfMatch(),
+            if_none_match: req.headers.IfNoneMatch(),
+        }
+    }
+
+    /// `Err` of `412 Precondition Failed` ( or `304 Not Modified` for `GET` / `HEAD` )
+    /// when the preconditions fail for `current_etag`, like `"42"` or `W/"42"` .
+    pub fn check(&self, current_etag: &str) -> Result<(), Response> {
+        match self.evaluate(current_etag) {
+            None => Ok(()),
+            Some(Status::NotModified) => Err({
+                let mut res = Response::NotModified();
+                res.headers.set().ETag(current_etag.to_string());
+                res
+            }),
+            Some(status) => Err(crate::typed::reject(status, "")),
+        }
+    }
+
+    /// ( <https://httpwg.org/specs/rfc9110.html#precedence> )
+    fn evaluate(&self, current_etag: &str) -> Option<Status> {
+        if let Some(if_match) = self.if_match {
+            if !etag::matches_strong(if_match, current_etag) {
+                return Some(Status::PreconditionFailed)
+            }
+        }
+        if let Some(if_none_match) = self.if_none_match {
+            if etag::matches_weak(if_none_match, current_etag) {
+                return Some(match self.method {
+                    Method::GET | Method::HEAD => Status::NotModified,
+                    _ => Status::PreconditionFailed,
+                })
+            }
+        }
+        None
+    }
+}
+
+impl<'req> FromRequest<'req> for Preconditions<'req> {
+    type Error = std::convert::Infallible;
+
+    fn from_request(req: &'req Request) -> Option<Result<Self, Self::Error>> {
+        Some(Ok(Self::of(req)))
+    }
+}
+
+
+#[cfg(all(test, debug_assertions, feature="__rt_native__", feature="DEBUG"))]
+#[test] fn test_etag() {
+    use crate::prelude::*;
+    use crate::testing::*;
+
+    async fn update(preconditions: Preconditions<'_>) -> Result<Response, Response> {
+        preconditions.check(&ETag::version(3))?;
+        let mut res = Response::NoContent();
+        res.headers.set().ETag(ETag::version(4));
+        Ok(res)
+    }
+
+    crate::__rt__::testing::block_on(async {
+        let t = Ohkami::new((ETag::new(),
+            "/article"
+                .GET(|| async {"Hello, ETag!"})
+                .PUT(update),
+            "/versioned"
+                .GET(|| async {
+                    let mut res = Response::OK().with_text("v3");
+                    res.headers.set().ETag(ETag::version(3));
+                    res
+                }),
+            "/missing"
+                .GET(|| async {Response::NotFound().with_text("not found")}),
+        )).test();
+
+        let res = t.oneshot(TestRequest::GET("/article")).await;
+        assert_eq!(res.status(), Status::OK);
+        let etag = res.header("ETag").unwrap().to_string();
+        assert!(etag.starts_with('"') && etag.ends_with("-c\""));
+
+        let res = t.oneshot(TestRequest::GET("/article")
+            .header("If-None-Match", etag.clone())
+        ).await;
+        assert_eq!(res.status(), Status::NotModified);
+        assert_eq!(res.header("ETag"), Some(&*etag));
+        assert_eq!(res.text(), None);
+
+        let res = t.oneshot(TestRequest::GET("/article")
+            .header("If-None-Match", r#""other""#)
+        ).await;
+        assert_eq!(res.status(), Status::OK);
+        assert_eq!(res.text(), Some("Hello, ETag!"));
+
+        let res = t.oneshot(TestRequest::GET("/article")
+            .header("If-Match", r#""other""#)
+        ).await;
+        assert_eq!(res.status(), Status::PreconditionFailed);
+
+        let res = t.oneshot(TestRequest::GET("/versioned")
+            .header("If-None-Match", r#"W/"3""#)
+        ).await;
+        assert_eq!(res.status(), Status::NotModified);
+        assert_eq!(res.header("ETag"), Some(r#""3""#));
+
+        let res = t.oneshot(TestRequest::GET("/missing")).await;
+        assert_eq!(res.status(), Status::NotFound);
+        assert_eq!(res.header("ETag"), None);
+
+        let res = t.oneshot(TestRequest::PUT("/article")
+            .header("If-Match", r#""2""#)
+        ).await;
+        assert_eq!(res.status(), Status::PreconditionFailed);
+
+        let res = t.oneshot(TestRequest::PUT("/article")
+            .header("If-Match", r#""3""#)
+        ).await;
+        assert_eq!(res.status(), Status::NoContent);
+        assert_eq!(res.header("ETag"), Some(r#""4""#));
+
+        let res = t.oneshot(TestRequest::PUT("/article")
+            .header("If-None-Match", "*")
+        ).await;
+        assert_eq!(res.status(), Status::PreconditionFailed);
+    });
+
+    /* stable: the head of SHA-256 and the length */
+    assert_eq!(ETag::new().tag(b"abc"), r#""ba7816bf8f01cfea-3""#);
+    assert_eq!(ETag::weak().tag(b"abc"), r#"W/"ba7816bf8f01cfea-3""#);
+}
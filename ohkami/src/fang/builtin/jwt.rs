@@ -1,6 +1,7 @@
 #![allow(non_snake_case, non_camel_case_types)]
 
-use crate::{Fang, FangProc, IntoResponse, Request, Response};
+use crate::{Fang, FangProc, IntoResponse, Request, Response, Status};
+use crate::typed::reject;
 use crate::fang::{SendSyncOnNative, SendOnNative};
 use std::{borrow::Cow, marker::PhantomData};
 use serde::{Serialize, Deserialize};
@@ -314,49 +315,49 @@ impl<Payload: for<'de> Deserialize<'de>> JWT<Payload> {
         const UNAUTHORIZED_MESSAGE: &str = "missing or malformed jwt";
 
         let mut parts = (self.get_token)(req)
-            .ok_or_else(|| Response::Unauthorized().with_text(UNAUTHORIZED_MESSAGE))?
+            .ok_or_else(|| reject(Status::Unauthorized, UNAUTHORIZED_MESSAGE))?
             .split('.');
 
         type Header  = ::serde_json::Value;
         type Payload = ::serde_json::Value;
         fn part_value(part: &str) -> Result<::serde_json::Value, Response> {
             let part = crate::util::base64_url_decode(part)
-                .map_err(|_| Response::BadRequest().with_text("invalid base64"))?;
+                .map_err(|_| reject(Status::BadRequest, "invalid base64"))?;
             ::serde_json::from_slice(&part)
-                .map_err(|_| Response::BadRequest().with_text("invalid json"))
+                .map_err(|_| reject(Status::BadRequest, "invalid json"))
         }
 
         let header_part = parts.next()
-            .ok_or_else(Response::Unauthorized)?;
+            .ok_or_else(|| reject(Status::Unauthorized, ""))?;
         let header: Header = part_value(header_part)?;
         if header.get("typ").is_some_and(|typ| !typ.as_str().unwrap_or_default().eq_ignore_ascii_case("JWT")) {
-            return Err(Response::BadRequest())
+            return Err(reject(Status::BadRequest, ""))
         }
         if header.get("cty").is_some_and(|cty| !cty.as_str().unwrap_or_default().eq_ignore_ascii_case("JWT")) {
-            return Err(Response::BadRequest())
+            return Err(reject(Status::BadRequest, ""))
         }
-        if header.get("alg").ok_or_else(Response::Unauthorized)? != self.alg_str() {
-            return Err(Response::BadRequest())
+        if header.get("alg").ok_or_else(|| reject(Status::Unauthorized, ""))? != self.alg_str() {
+            return Err(reject(Status::BadRequest, ""))
         }
 
         let payload_part = parts.next()
-            .ok_or_else(Response::Unauthorized)?;
+            .ok_or_else(|| reject(Status::Unauthorized, ""))?;
         let payload: Payload = part_value(payload_part)?;
         let now = crate::util::unix_timestamp();
         if payload.get("nbf").is_some_and(|nbf| nbf.as_u64().unwrap_or(0) > now) {
-            return Err(Response::Unauthorized().with_text(UNAUTHORIZED_MESSAGE))
+            return Err(reject(Status::Unauthorized, UNAUTHORIZED_MESSAGE))
         }
         if payload.get("exp").is_some_and(|exp| exp.as_u64().unwrap_or(u64::MAX) <= now) {
-            return Err(Response::Unauthorized().with_text(UNAUTHORIZED_MESSAGE))
+            return Err(reject(Status::Unauthorized, UNAUTHORIZED_MESSAGE))
         }
         if payload.get("iat").is_some_and(|iat| iat.as_u64().unwrap_or(0) > now) {
-            return Err(Response::Unauthorized().with_text(UNAUTHORIZED_MESSAGE))
+            return Err(reject(Status::Unauthorized, UNAUTHORIZED_MESSAGE))
         }
 
         let signature_part = parts.next()
-            .ok_or_else(Response::Unauthorized)?;
+            .ok_or_else(|| reject(Status::Unauthorized, ""))?;
         let requested_signature = crate::util::base64_url_decode(signature_part)
-            .map_err(|_| Response::Unauthorized())?;
+            .map_err(|_| reject(Status::Unauthorized, ""))?;
 
         let is_correct_signature = {
             use ::sha2::{Sha256, Sha384, Sha512};
@@ -388,10 +389,10 @@ impl<Payload: for<'de> Deserialize<'de>> JWT<Payload> {
         };
         
         if !is_correct_signature {
-            return Err(Response::Unauthorized().with_text(UNAUTHORIZED_MESSAGE))
+            return Err(reject(Status::Unauthorized, UNAUTHORIZED_MESSAGE))
         }
 
-        let payload = ::serde_json::from_value(payload).map_err(|_| Response::InternalServerError())?;
+        let payload = ::serde_json::from_value(payload).map_err(|_| reject(Status::InternalServerError, ""))?;
         Ok(payload)
     }
 }
@@ -503,7 +504,7 @@ impl<Payload: for<'de> Deserialize<'de>> JWT<Payload> {
         impl IntoResponse for APIError {
             fn into_response(self) -> Response {
                 match self {
-                    Self::UserNotFound => Response::InternalServerError().with_text("User was not found"),
+                    Self::UserNotFound => crate::typed::reject(crate::Status::InternalServerError, "User was not found"),
                 }
             }
 
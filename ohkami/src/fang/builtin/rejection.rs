@@ -0,0 +1,150 @@
+use crate::{Request, Response, Rejection};
+use crate::fang::{FangAction, SendSyncOnNative};
+use crate::request::RejectionHook;
+use std::sync::Arc;
+
+
+/// # Builtin fang for extraction errors
+///
+/// <br>
+///
+/// Build the response for a request that built-in extractors ( `JSON<T>`,
+/// `Query<T>`, path params, ... ) failed to extract, from the [`Rejection`]
+/// telling which extractor, which field and what error.
+///
+/// <br>
+///
+/// ---
+/// *example.rs*
+/// ```no_run
+/// use ohkami::prelude::*;
+/// use ohkami::fang::OnRejection;
+/// use ohkami::Rejection;
+///
+/// #[derive(Serialize)]
+/// struct ErrorEnvelope {
+///     source:  &'static str,
+///     field:   Option<String>,
+///     message: String,
+/// }
+///
+/// fn envelope(r: Rejection) -> Response {
+///     let body = JSON(ErrorEnvelope {
+///         source:  r.extractor(),
+///         field:   r.field().map(String::from),
+///         message: r.message().into(),
+///     });
+///     let mut res = body.into_response();
+///     res.status = r.status();
+///     res
+/// }
+///
+/// #[tokio::main]
+/// async fn main() {
+///     Ohkami::new((OnRejection::new(envelope),
+///         "/users/:id".GET(get_user),
+///     )).howl("0.0.0.0:3000").await
+/// }
+///
+/// async fn get_user(id: u64) -> String {
+///     format!("user #{id}")
+/// }
+/// ```
+/// ---
+#[derive(Clone)]
+pub struct OnRejection(RejectionHook);
+
+impl OnRejection {
+    pub fn new(hook: impl Fn(Rejection) -> Response + SendSyncOnNative + 'static) -> Self {
+        Self(RejectionHook(Arc::new(hook)))
+    }
+}
+
+impl FangAction for OnRejection {
+    #[inline]
+    async fn fore<'a>(&'a self, req: &'a mut Request) -> Result<(), Response> {
+        req.context.set(self.0.clone());
+        Ok(())
+    }
+}
+
+
+#[cfg(all(test, debug_assertions, feature="__rt_native__", feature="DEBUG"))]
+#[test] fn test_on_rejection() {
+    use crate::prelude::*;
+    use crate::testing::*;
+
+    #[derive(::serde::Deserialize)]
+    struct User<'req> {
+        name: &'req str,
+    }
+    #[cfg(feature="openapi")]
+    impl crate::openapi::Schema for User<'_> {
+        fn schema() -> impl Into<crate::openapi::schema::SchemaRef> {
+            crate::openapi::object()
+                .property("name", crate::openapi::string())
+        }
+    }
+
+    fn envelope(r: Rejection) -> Response {
+        Response::UnprocessableEntity().with_text(format!(
+            "{}|{}|{}",
+            r.extractor(),
+            r.field().unwrap_or("-"),
+            r.status().code(),
+        ))
+    }
+
+    async fn search_user(Query(u): Query<User<'_>>) -> String {
+        u.name.into()
+    }
+    async fn create_user(JSON(u): JSON<User<'_>>) -> String {
+        u.name.into()
+    }
+
+    crate::__rt__::testing::block_on(async {
+        let t = Ohkami::new((OnRejection::new(envelope),
+            "/users/:id"
+                .GET(|id: u64| async move {format!("{id}")}),
+            "/users"
+                .GET(search_user)
+                .POST(create_user),
+        )).test();
+
+        let res = t.oneshot(TestRequest::GET("/users/42")).await;
+        assert_eq!(res.status(), Status::OK);
+        assert_eq!(res.text(), Some("42"));
+
+        let res = t.oneshot(TestRequest::GET("/users/abc")).await;
+        assert_eq!(res.status(), Status::UnprocessableEntity);
+        assert_eq!(res.text(), Some("u64|-|400"));
+
+        let res = t.oneshot(TestRequest::GET("/users/%FF")).await;
+        assert_eq!(res.status(), Status::UnprocessableEntity);
+        assert_eq!(res.text(), Some("u64|-|400"));
+
+        let res = t.oneshot(TestRequest::GET("/users").query("nam", "ohkami")).await;
+        assert_eq!(res.status(), Status::UnprocessableEntity);
+        assert_eq!(res.text(), Some("Query|name|400"));
+
+        let res = t.oneshot(TestRequest::POST("/users")
+            .json_lit(r#"{"nmae":"ohkami"}"#)
+        ).await;
+        assert_eq!(res.status(), Status::UnprocessableEntity);
+        assert_eq!(res.text(), Some("JSON|name|400"));
+
+        let res = t.oneshot(TestRequest::POST("/users")).await;
+        assert_eq!(res.status(), Status::UnprocessableEntity);
+        assert_eq!(res.text(), Some("JSON|-|400"));
+
+        /* without the hook, the same as before */
+        let t = Ohkami::new((
+            "/users/:id"
+                .GET(|id: u64| async move {format!("{id}")}),
+        )).test();
+
+        let res = t.oneshot(TestRequest::GET("/users/abc")).await;
+        assert_eq!(res.status(), Status::BadRequest);
+        assert_eq!(res.text(), Some("Unexpected path param"));
+    });
+}
@@ -0,0 +1,461 @@
+use crate::{FromRequest, Request, Response, Rejection, Status};
+use crate::fang::{FangAction, CookieJar};
+use crate::header::SetCookieBuilder;
+use crate::request::extractor_name;
+use serde::{Serialize, de::DeserializeOwned};
+use std::sync::Arc;
+
+#[cfg(feature="openapi")]
+use crate::openapi;
+
+
+/// # Builtin fang for the key of signed / private Cookies
+///
+/// <br>
+///
+/// Make the key available for [`SignedCookie`] and [`PrivateCookie`] ( with
+/// `private_cookie` feature ). Keys for signing and encryption are derived
+/// from the secret, which should be random and at least 32 bytes long.
+///
+/// For key rotation, give the former secrets by `previous`: Cookies are
+/// always signed / encrypted by the current one, and verified / decrypted
+/// by any of them.
+///
+/// <br>
+///
+/// ---
+/// *example.rs*
+/// ```no_run
+/// use ohkami::prelude::*;
+/// use ohkami::fang::{CookieKey, NamedCookie, SignedCookie};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Cart {
+///     items: Vec<u64>,
+/// }
+/// impl NamedCookie for Cart {
+///     const NAME: &'static str = "cart";
+/// }
+///
+/// async fn show_cart(
+///     SignedCookie(cart): SignedCookie<Cart>,
+/// ) -> String {
+///     format!("{} items", cart.items.len())
+/// }
+///
+/// async fn add_item(
+///     item: u64,
+///     key: CookieKey,
+///     cart: Option<SignedCookie<Cart>>,
+/// ) -> Response {
+///     let mut cart = cart.map(|SignedCookie(c)| c).unwrap_or(Cart { items: vec![] });
+///     cart.items.push(item);
+///
+///     let mut res = Response::Created();
+///     SignedCookie(cart).set_to(&mut res, &key, |d| d.Path("/").HttpOnly())
+///         .expect("`Cart` is serialized into JSON");
+///     res
+/// }
+///
+/// #[tokio::main]
+/// async fn main() {
+///     Ohkami::new((
+///         CookieKey::new(std::env::var("COOKIE_SECRET").unwrap())
+///             .previous(std::env::var("OLD_COOKIE_SECRET").unwrap()),
+///         "/cart"
+///             .GET(show_cart),
+///         "/cart/:item"
+///             .POST(add_item),
+///     )).howl("localhost:3000").await
+/// }
+/// ```
+/// ---
+#[derive(Clone)]
+pub struct CookieKey(
+    /// the current one first
+    Arc<Vec<DerivedKeys>>
+);
+
+#[derive(Clone)]
+struct DerivedKeys {
+    signing:    [u8; 32],
+    #[cfg_attr(not(feature="private_cookie"), allow(unused))]
+    encryption: [u8; 32],
+}
+
+impl DerivedKeys {
+    fn from_secret(secret: &[u8]) -> Self {
+        use ::hmac::{Hmac, Mac};
+        use ::sha2::Sha256;
+
+        let derive = |purpose: &[u8]| -> [u8; 32] {
+            let mut h = Hmac::<Sha256>::new_from_slice(secret).unwrap();
+            h.update(purpose);
+            h.finalize().into_bytes().into()
+        };
+        Self {
+            signing:    derive(b"ohkami signed cookie"),
+            encryption: derive(b"ohkami private cookie"),
+        }
+    }
+}
+
+impl CookieKey {
+    pub fn new(secret: impl AsRef<[u8]>) -> Self {
+        Self(Arc::new(vec![DerivedKeys::from_secret(secret.as_ref())]))
+    }
+
+    /// Add a former secret still accepted for verification / decryption.
+    pub fn previous(self, secret: impl AsRef<[u8]>) -> Self {
+        let mut keys = Arc::unwrap_or_clone(self.0);
+        keys.push(DerivedKeys::from_secret(secret.as_ref()));
+        Self(Arc::new(keys))
+    }
+
+    fn current(&self) -> &DerivedKeys {
+        &self.0[0]
+    }
+
+    fn sign(keys: &DerivedKeys, name: &str, payload: &str) -> ::hmac::Hmac<::sha2::Sha256> {
+        use ::hmac::{Hmac, Mac};
+
+        let mut h = Hmac::<::sha2::Sha256>::new_from_slice(&keys.signing).unwrap();
+        h.update(name.as_bytes());
+        h.update(b"=");
+        h.update(payload.as_bytes());
+        h
+    }
+}
+
+impl FangAction for CookieKey {
+    #[inline]
+    async fn fore<'a>(&'a self, req: &'a mut Request) -> Result<(), Response> {
+        req.context.set(self.clone());
+        Ok(())
+    }
+}
+
+impl<'req> FromRequest<'req> for CookieKey {
+    type Error = std::convert::Infallible;
+
+    fn from_request(req: &'req Request) -> Option<Result<Self, Self::Error>> {
+        match req.context.get::<CookieKey>() {
+            Some(key) => Some(Ok(key.clone())),
+            None => {
+                #[cfg(debug_assertions)] {
+                    crate::warning!("`CookieKey` fang is required for signed or private Cookies")
+                }
+                None
+            }
+        }
+    }
+}
+
+/// A type stored in a Cookie by [`SignedCookie`] or [`PrivateCookie`]
+pub trait NamedCookie {
+    /// Cookie name
+    const NAME: &'static str;
+}
+
+/// percent-decoded value of Cookie `T::NAME`
+fn cookie_of<'req, T: NamedCookie>(req: &'req Request) -> Option<std::borrow::Cow<'req, str>> {
+    let (_, value) = req.headers.Cookies().find(|(name, _)| *name == T::NAME)?;
+    Some(ohkami_lib::percent_decode_utf8(value.as_bytes()).unwrap_or(std::borrow::Cow::Borrowed(value)))
+}
+
+fn deserialize<C, T: DeserializeOwned>(json: &[u8]) -> Result<T, Rejection> {
+    serde_json::from_slice(json).map_err(|e| Rejection::new(Status::BadRequest, extractor_name::<C>(), e))
+}
+
+fn setcookie(
+    name:       &'static str,
+    value:      String,
+    directives: impl FnOnce(SetCookieBuilder)->SetCookieBuilder,
+) -> Result<String, serde_json::Error> {
+    directives(SetCookieBuilder::new(name, value)).build()
+        .map_err(serde::ser::Error::custom)
+}
+
+/// # Signed Cookie
+///
+/// `T` in a Cookie named `T::NAME`, serialized into JSON and signed by
+/// HMAC-SHA256 with [`CookieKey`], readable but not modifiable by the client.
+///
+/// Extraction fails with `401 Unauthorized` when the signature doesn't match,
+/// and with `400 Bad Request` when the Cookie is missing or malformed.
+/// See [`CookieKey`] for an example.
+pub struct SignedCookie<T>(pub T);
+
+impl<T: NamedCookie + Serialize> SignedCookie<T> {
+    fn value(&self, key: &CookieKey) -> Result<String, serde_json::Error> {
+        let payload = crate::util::base64_url_encode(serde_json::to_vec(&self.0)?);
+        let signature = {
+            use ::hmac::Mac;
+            crate::util::base64_url_encode(CookieKey::sign(key.current(), T::NAME, &payload).finalize().into_bytes())
+        };
+        Ok(format!("{payload}.{signature}"))
+    }
+
+    /// Add `Set-Cookie` of this to `res`.
+    pub fn set_to(&self,
+        res:        &mut Response,
+        key:        &CookieKey,
+        directives: impl FnOnce(SetCookieBuilder)->SetCookieBuilder,
+    ) -> Result<(), impl std::error::Error> {
+        res.headers.push_setcookie(setcookie(T::NAME, self.value(key)?, directives)?);
+        Ok::<_, serde_json::Error>(())
+    }
+
+    /// Add this to `jar`.
+    pub fn add_to(&self,
+        jar:        &CookieJar,
+        key:        &CookieKey,
+        directives: impl FnOnce(SetCookieBuilder)->SetCookieBuilder,
+    ) -> Result<(), impl std::error::Error> {
+        jar.try_add(T::NAME.into(), self.value(key)?, directives).map_err(serde::ser::Error::custom)?;
+        Ok::<_, serde_json::Error>(())
+    }
+}
+
+impl<'req, T: NamedCookie + DeserializeOwned> FromRequest<'req> for SignedCookie<T> {
+    type Error = Rejection;
+
+    fn from_request(req: &'req Request) -> Option<Result<Self, Self::Error>> {
+        let Some(key) = req.context.get::<CookieKey>() else {
+            #[cfg(debug_assertions)] {
+                crate::warning!("`CookieKey` fang is required for `{}`", extractor_name::<Self>())
+            }
+            return None
+        };
+        let value = cookie_of::<T>(req)?;
+
+        Some((|| {
+            let (payload, signature) = value.split_once('.')
+                .ok_or_else(|| Rejection::new(Status::BadRequest, extractor_name::<Self>(), "malformed signed Cookie"))?;
+            let signature = crate::util::base64_url_decode(signature)
+                .map_err(|_| Rejection::new(Status::BadRequest, extractor_name::<Self>(), "malformed signed Cookie"))?;
+
+            let verified = key.0.iter().any(|keys| {
+                use ::hmac::Mac;
+                CookieKey::sign(keys, T::NAME, payload).verify_slice(&signature).is_ok()
+            });
+            if !verified {
+                return Err(Rejection::new(Status::Unauthorized, extractor_name::<Self>(), "invalid signature"))
+            }
+
+            let json = crate::util::base64_url_decode(payload)
+                .map_err(|_| Rejection::new(Status::BadRequest, extractor_name::<Self>(), "malformed signed Cookie"))?;
+            deserialize::<Self, T>(&json).map(SignedCookie)
+        })())
+    }
+
+    #[cfg(feature="openapi")]
+    fn openapi_inbound() -> openapi::Inbound {
+        openapi::Inbound::Params(vec![openapi::Parameter::in_cookie(T::NAME, openapi::string())])
+    }
+}
+
+#[cfg(feature="private_cookie")]
+pub use private::PrivateCookie;
+
+#[cfg(feature="private_cookie")]
+mod private {
+    use super::*;
+    use ::chacha20poly1305::{XChaCha20Poly1305, XNonce, KeyInit, AeadCore, aead::{Aead, Payload, OsRng}};
+
+    /// # Private Cookie
+    ///
+    /// `T` in a Cookie named `T::NAME`, serialized into JSON and encrypted by
+    /// XChaCha20-Poly1305 with [`CookieKey`], neither readable nor modifiable
+    /// by the client.
+    ///
+    /// Extraction fails with `401 Unauthorized` when the Cookie can't be
+    /// decrypted ( tampered or by an unknown key ), and with `400 Bad Request`
+    /// when the Cookie is missing or malformed.
+    pub struct PrivateCookie<T>(pub T);
+
+    const NONCE_SIZE: usize = 24;
+
+    impl<T: NamedCookie + Serialize> PrivateCookie<T> {
+        fn value(&self, key: &CookieKey) -> Result<String, serde_json::Error> {
+            let json = serde_json::to_vec(&self.0)?;
+
+            let cipher = XChaCha20Poly1305::new(&key.current().encryption.into());
+            let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+            let encrypted = cipher.encrypt(&nonce, Payload { msg: &json, aad: T::NAME.as_bytes() })
+                .expect("encrypting a Cookie never fails");
+
+            let mut sealed = nonce.to_vec();
+            sealed.extend(encrypted);
+            Ok(crate::util::base64_url_encode(sealed))
+        }
+
+        /// Add `Set-Cookie` of this to `res`.
+        pub fn set_to(&self,
+            res:        &mut Response,
+            key:        &CookieKey,
+            directives: impl FnOnce(SetCookieBuilder)->SetCookieBuilder,
+        ) -> Result<(), impl std::error::Error> {
+            res.headers.push_setcookie(setcookie(T::NAME, self.value(key)?, directives)?);
+            Ok::<_, serde_json::Error>(())
+        }
+
+        /// Add this to `jar`.
+        pub fn add_to(&self,
+            jar:        &CookieJar,
+            key:        &CookieKey,
+            directives: impl FnOnce(SetCookieBuilder)->SetCookieBuilder,
+        ) -> Result<(), impl std::error::Error> {
+            jar.try_add(T::NAME.into(), self.value(key)?, directives).map_err(serde::ser::Error::custom)?;
+            Ok::<_, serde_json::Error>(())
+        }
+    }
+
+    impl<'req, T: NamedCookie + DeserializeOwned> FromRequest<'req> for PrivateCookie<T> {
+        type Error = Rejection;
+
+        fn from_request(req: &'req Request) -> Option<Result<Self, Self::Error>> {
+            let Some(key) = req.context.get::<CookieKey>() else {
+                #[cfg(debug_assertions)] {
+                    crate::warning!("`CookieKey` fang is required for `{}`", extractor_name::<Self>())
+                }
+                return None
+            };
+            let value = cookie_of::<T>(req)?;
+
+            Some((|| {
+                let sealed = crate::util::base64_url_decode(&*value)
+                    .ok().filter(|sealed| sealed.len() > NONCE_SIZE)
+                    .ok_or_else(|| Rejection::new(Status::BadRequest, extractor_name::<Self>(), "malformed private Cookie"))?;
+                let (nonce, encrypted) = sealed.split_at(NONCE_SIZE);
+
+                let json = key.0.iter().find_map(|keys| {
+                    XChaCha20Poly1305::new(&keys.encryption.into())
+                        .decrypt(XNonce::from_slice(nonce), Payload { msg: encrypted, aad: T::NAME.as_bytes() })
+                        .ok()
+                }).ok_or_else(|| Rejection::new(Status::Unauthorized, extractor_name::<Self>(), "failed to decrypt"))?;
+
+                deserialize::<Self, T>(&json).map(PrivateCookie)
+            })())
+        }
+
+        #[cfg(feature="openapi")]
+        fn openapi_inbound() -> openapi::Inbound {
+            openapi::Inbound::Params(vec![openapi::Parameter::in_cookie(T::NAME, openapi::string())])
+        }
+    }
+}
+
+
+#[cfg(all(test, debug_assertions, feature="__rt_native__", feature="DEBUG"))]
+#[test] fn test_secure_cookie() {
+    use crate::prelude::*;
+    use crate::testing::*;
+
+    #[derive(::serde::Serialize, ::serde::Deserialize)]
+    struct Cart {
+        items: Vec<u64>,
+    }
+    impl NamedCookie for Cart {
+        const NAME: &'static str = "cart";
+    }
+
+    async fn show(SignedCookie(cart): SignedCookie<Cart>) -> String {
+        format!("{:?}", cart.items)
+    }
+    async fn issue(key: CookieKey) -> Response {
+        let mut res = Response::OK();
+        SignedCookie(Cart { items: vec![1, 2] }).set_to(&mut res, &key, |d| d.Path("/")).unwrap();
+        res
+    }
+
+    fn setcookie(res: &TestResponse) -> &str {
+        res.headers().find(|(name, _)| *name == "Set-Cookie").unwrap().1
+    }
+    fn cookie_value(res: &TestResponse) -> String {
+        let encoded = setcookie(res).strip_prefix("cart=").unwrap().split(';').next().unwrap();
+        ohkami_lib::percent_decode_utf8(encoded.as_bytes()).unwrap().into_owned()
+    }
+
+    crate::__rt__::testing::block_on(async {
+        let old = Ohkami::new((CookieKey::new("old secret"),
+            "/issue".GET(issue),
+        )).test();
+        let t = Ohkami::new((CookieKey::new("new secret").previous("old secret"),
+            "/issue".GET(issue),
+            "/show".GET(show),
+        )).test();
+        let other = Ohkami::new((CookieKey::new("other secret"),
+            "/show".GET(show),
+        )).test();
+
+        let res = t.oneshot(TestRequest::GET("/issue")).await;
+        let signed = cookie_value(&res);
+        assert!(setcookie(&res).ends_with("; Path=/"));
+
+        let res = t.oneshot(TestRequest::GET("/show").header("Cookie", format!("cart={signed}"))).await;
+        assert_eq!(res.text(), Some("[1, 2]"));
+
+        /* signed by the previous key */
+        let res = old.oneshot(TestRequest::GET("/issue")).await;
+        let signed_by_old = cookie_value(&res);
+        assert_ne!(signed_by_old, signed);
+        let res = t.oneshot(TestRequest::GET("/show").header("Cookie", format!("cart={signed_by_old}"))).await;
+        assert_eq!(res.text(), Some("[1, 2]"));
+
+        /* by an unknown key */
+        let res = other.oneshot(TestRequest::GET("/show").header("Cookie", format!("cart={signed}"))).await;
+        assert_eq!(res.status(), Status::Unauthorized);
+
+        /* tampered */
+        let (_, signature) = signed.split_once('.').unwrap();
+        let tampered = format!("{}.{signature}", crate::util::base64_url_encode(r#"{"items":[1,2,3]}"#));
+        let res = t.oneshot(TestRequest::GET("/show").header("Cookie", format!("cart={tampered}"))).await;
+        assert_eq!(res.status(), Status::Unauthorized);
+
+        let res = t.oneshot(TestRequest::GET("/show").header("Cookie", "cart=malformed")).await;
+        assert_eq!(res.status(), Status::BadRequest);
+
+        let res = t.oneshot(TestRequest::GET("/show")).await;
+        assert_eq!(res.status(), Status::BadRequest);
+    });
+
+    #[cfg(feature="private_cookie")]
+    crate::__rt__::testing::block_on(async {
+        async fn show(PrivateCookie(cart): PrivateCookie<Cart>) -> String {
+            format!("{:?}", cart.items)
+        }
+        async fn issue(key: CookieKey, jar: CookieJar) -> &'static str {
+            PrivateCookie(Cart { items: vec![3] }).add_to(&jar, &key, |d| d.HttpOnly()).unwrap();
+            "issued"
+        }
+
+        let old = Ohkami::new((CookieKey::new("old secret"), CookieJar::fang(),
+            "/issue".GET(issue),
+        )).test();
+        let t = Ohkami::new((CookieKey::new("new secret").previous("old secret"), CookieJar::fang(),
+            "/issue".GET(issue),
+            "/show".GET(show),
+        )).test();
+
+        let res = t.oneshot(TestRequest::GET("/issue")).await;
+        let encrypted = cookie_value(&res);
+        assert!(!encrypted.contains("items"));
+        let res = t.oneshot(TestRequest::GET("/show").header("Cookie", format!("cart={encrypted}"))).await;
+        assert_eq!(res.text(), Some("[3]"));
+
+        let res = old.oneshot(TestRequest::GET("/issue")).await;
+        let encrypted_by_old = cookie_value(&res);
+        let res = t.oneshot(TestRequest::GET("/show").header("Cookie", format!("cart={encrypted_by_old}"))).await;
+        assert_eq!(res.text(), Some("[3]"));
+
+        let mut tampered = crate::util::base64_url_decode(&encrypted).unwrap();
+        *tampered.last_mut().unwrap() ^= 1;
+        let tampered = crate::util::base64_url_encode(tampered);
+        let res = t.oneshot(TestRequest::GET("/show").header("Cookie", format!("cart={tampered}"))).await;
+        assert_eq!(res.status(), Status::Unauthorized);
+
+        let res = t.oneshot(TestRequest::GET("/show").header("Cookie", "cart=short")).await;
+        assert_eq!(res.status(), Status::BadRequest);
+    });
+}
@@ -0,0 +1,291 @@
+#![cfg(feature="__rt_native__")]
+
+use crate::{Fang, FangProc, FromRequest, Request, Response, Trailers};
+use crate::header::append;
+use crate::response::Content;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+
+/// # Builtin fang for `Server-Timing`
+///
+/// <br>
+///
+/// Emit the durations recorded by handlers via [`Timings`], and `total` of
+/// the request handling, as `Server-Timing` header.
+///
+/// For a chunked stream response, it's sent as a trailer instead, including
+/// the durations recorded while streaming and `total` until the stream ends.
+///
+/// <br>
+///
+/// ---
+/// *example.rs*
+/// ```no_run
+/// use ohkami::prelude::*;
+/// use ohkami::fang::{ServerTiming, Timings};
+///
+/// async fn list_users(timings: Timings) -> String {
+///     let users = timings.measure("db", async {
+///         /* query the database */
+///         vec!["ohkami", "ohkami2"]
+///     }).await;
+///
+///     let _render = timings.start("render");
+///     users.join(", ")
+/// }
+///
+/// #[tokio::main]
+/// async fn main() {
+///     Ohkami::new((ServerTiming::new(),
+///         "/users".GET(list_users),
+///     )).howl("localhost:3000").await
+/// }
+/// ```
+/// ---
+#[derive(Clone)]
+pub struct ServerTiming {
+    total: bool,
+}
+
+impl ServerTiming {
+    pub fn new() -> Self {
+        Self { total: true }
+    }
+
+    /// Don't add `total` metric.
+    pub fn without_total(mut self) -> Self {
+        self.total = false;
+        self
+    }
+}
+impl Default for ServerTiming {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// # Request-scoped `Server-Timing` metrics
+///
+/// Extracted in handlers under [`ServerTiming`] fang to record named durations.
+/// This is a shared handle, so it can be moved into a response stream.
+#[derive(Clone)]
+pub struct Timings(
+    Arc<Mutex<Vec<Metric>>>
+);
+
+struct Metric {
+    name: &'static str,
+    desc: Option<String>,
+    dur:  Duration,
+}
+
+impl Timings {
+    fn new() -> Self {
+        Self(Arc::new(Mutex::new(Vec::new())))
+    }
+
+    /// Record `name` as taking `dur`.
+    /// `name` must be a token like `db` or `cache-miss`.
+    pub fn record(&self, name: &'static str, dur: Duration) {
+        self.0.lock().unwrap().push(Metric { name, desc: None, dur });
+    }
+
+    /// Record `name` with a human-readable description as taking `dur`.
+    pub fn record_with_desc(&self, name: &'static str, desc: impl Into<String>, dur: Duration) {
+        self.0.lock().unwrap().push(Metric { name, desc: Some(desc.into()), dur });
+    }
+
+    /// Record `name` as the duration of awaiting `future`.
+    pub async fn measure<F: Future>(&self, name: &'static str, future: F) -> F::Output {
+        let start = Instant::now();
+        let output = future.await;
+        self.record(name, start.elapsed());
+        output
+    }
+
+    /// Start to measure `name`, recorded when the returned timer is dropped.
+    pub fn start(&self, name: &'static str) -> Timer {
+        Timer { timings: self.clone(), name, start: Instant::now() }
+    }
+
+    fn render(&self) -> String {
+        let mut value = String::new();
+        for (i, Metric { name, desc, dur }) in self.0.lock().unwrap().iter().enumerate() {
+            if i > 0 {value.push_str(", ")}
+            value.push_str(name);
+            if let Some(desc) = desc {
+                value.push_str(";desc=\"");
+                value.push_str(&desc.replace('\\', "\\\\").replace('"', "\\\""));
+                value.push('"');
+            }
+            value.push_str(&format!(";dur={:.1}", dur.as_secs_f64() * 1000.));
+        }
+        value
+    }
+}
+
+/// Measuring timer of [`Timings::start`]
+pub struct Timer {
+    timings: Timings,
+    name:    &'static str,
+    start:   Instant,
+}
+impl Timer {
+    /// Stop and record the duration.
+    pub fn stop(self) {/* recorded by drop */}
+}
+impl Drop for Timer {
+    fn drop(&mut self) {
+        self.timings.record(self.name, self.start.elapsed());
+    }
+}
+
+impl<'req> FromRequest<'req> for Timings {
+    type Error = std::convert::Infallible;
+
+    fn from_request(req: &'req Request) -> Option<Result<Self, Self::Error>> {
+        match req.context.get::<Timings>() {
+            Some(timings) => Some(Ok(timings.clone())),
+            None => {
+                #[cfg(debug_assertions)] {
+                    crate::warning!("`Timings` requires `ServerTiming` fang")
+                }
+                None
+            }
+        }
+    }
+}
+
+impl<Inner: FangProc> Fang<Inner> for ServerTiming {
+    type Proc = ServerTimingProc<Inner>;
+    fn chain(&self, inner: Inner) -> Self::Proc {
+        ServerTimingProc { inner, total: self.total }
+    }
+}
+
+pub struct ServerTimingProc<Inner: FangProc> {
+    total: bool,
+    inner: Inner,
+}
+impl<Inner: FangProc> FangProc for ServerTimingProc<Inner> {
+    async fn bite<'b>(&'b self, req: &'b mut Request) -> Response {
+        let start = Instant::now();
+        let timings = Timings::new();
+        req.context.set(timings.clone());
+
+        let mut res = self.inner.bite(req).await;
+
+        let total = self.total;
+        let finish = move |timings: &Timings| {
+            if total {timings.record("total", start.elapsed())}
+            timings.render()
+        };
+
+        let chunked = res.headers.TransferEncoding().is_some_and(|te| te.contains("chunked"));
+        match res.content.take() {
+            Content::BodyStream(stream) if chunked => {
+                let trailers = res.trailers.get_or_insert_with(Trailers::new).clone();
+                res.headers.set().Trailer(append("Server-Timing"));
+                res.content = Content::BodyStream(Box::pin(OnEnd::new(stream, move || {
+                    trailers.set("Server-Timing", finish(&timings));
+                })));
+            }
+            #[cfg(feature="sse")]
+            Content::Stream(stream) => {
+                let trailers = res.trailers.get_or_insert_with(Trailers::new).clone();
+                res.headers.set().Trailer(append("Server-Timing"));
+                res.content = Content::Stream(Box::pin(OnEnd::new(stream, move || {
+                    trailers.set("Server-Timing", finish(&timings));
+                })));
+            }
+            content => {
+                res.content = content;
+                let value = finish(&timings);
+                if !value.is_empty() {
+                    res.headers.set().x("Server-Timing", append(value));
+                }
+            }
+        }
+
+        res
+    }
+}
+
+/// Yield all items of `S` and then call `F`
+struct OnEnd<S, F: FnOnce()> {
+    stream: S,
+    on_end: Option<F>,
+}
+impl<S, F: FnOnce()> OnEnd<S, F> {
+    fn new(stream: S, on_end: F) -> Self {
+        Self { stream, on_end: Some(on_end) }
+    }
+}
+impl<S: crate::util::Stream + Unpin, F: FnOnce() + Unpin> crate::util::Stream for OnEnd<S, F> {
+    type Item = S::Item;
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>
+    ) -> std::task::Poll<Option<Self::Item>> {
+        let poll = std::pin::Pin::new(&mut self.stream).poll_next(cx);
+        if let std::task::Poll::Ready(None) = poll {
+            if let Some(on_end) = self.on_end.take() {
+                on_end()
+            }
+        }
+        poll
+    }
+}
+
+
+#[cfg(all(test, debug_assertions, feature="__rt_native__", feature="DEBUG"))]
+#[test] fn test_server_timing() {
+    use crate::prelude::*;
+    use crate::testing::*;
+    use crate::util::stream;
+
+    async fn users(timings: Timings) -> &'static str {
+        timings.measure("db", async {"ohkami"}).await;
+        timings.record_with_desc("cache", "Cache \"miss\"", Duration::from_millis(2));
+        timings.start("render").stop();
+        "ohkami"
+    }
+
+    async fn export(timings: Timings) -> Response {
+        Response::OK().with_body_stream(stream::queue(move |mut q| async move {
+            let _chunk = timings.start("chunk");
+            q.push(Ok::<_, std::io::Error>(&b"a,b\n"[..]));
+        }))
+    }
+
+    crate::__rt__::testing::block_on(async {
+        let t = Ohkami::new((ServerTiming::new(),
+            "/users".GET(users),
+            "/export".GET(export),
+        )).test();
+
+        let res = t.oneshot(TestRequest::GET("/users")).await.collect_body().await;
+        assert_eq!(res.text(), Some("ohkami"));
+        let server_timing = res.header("Server-Timing").unwrap();
+        let names = server_timing.split(", ")
+            .map(|metric| metric.split(';').next().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["db", "cache", "render", "total"]);
+        assert!(server_timing.contains(r#"cache;desc="Cache \"miss\"";dur=2.0"#));
+
+        let res = t.oneshot(TestRequest::GET("/export")).await.collect_body().await;
+        assert_eq!(res.header("Server-Timing"), None);
+        assert_eq!(res.header("Trailer"), Some("Server-Timing"));
+        let server_timing = res.trailer("Server-Timing").unwrap();
+        assert!(server_timing.starts_with("chunk;dur="));
+        assert!(server_timing.contains(", total;dur="));
+
+        let t = Ohkami::new((ServerTiming::new().without_total(),
+            "/".GET(|| async {"Hello"}),
+        )).test();
+        let res = t.oneshot(TestRequest::GET("/")).await;
+        assert_eq!(res.header("Server-Timing"), None);
+    });
+}
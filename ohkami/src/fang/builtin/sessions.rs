@@ -0,0 +1,534 @@
+use crate::{Fang, FangProc, FromRequest, Request, Response};
+use crate::fang::bound::{SendOnNativeFuture, SendSyncOnNative};
+use crate::header::SetCookieBuilder;
+use serde::{Serialize, de::DeserializeOwned};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+
+/// # Builtin fang for server-side sessions
+///
+/// <br>
+///
+/// Issue an opaque session id Cookie, load the session data from the
+/// store into [`Session`] before the handler, and persist changes
+/// afterwards.
+///
+/// - A session id is issued only when the data is first stored, and
+///   an unknown id sent by the client is never adopted.
+/// - Sessions expire when idle for `idle_timeout` ( default: 30 minutes ).
+/// - Call [`Session::renew`] on a privilege change like login to rotate the id.
+///
+/// [`MemorySessionStore`] is available for a single process. Implement
+/// [`SessionStore`] for Redis, SQL or any other backends.
+///
+/// Available with `sessions` feature.
+///
+/// <br>
+///
+/// ---
+/// *example.rs*
+/// ```no_run
+/// use ohkami::prelude::*;
+/// use ohkami::fang::{Sessions, Session, MemorySessionStore};
+///
+/// async fn login(session: Session) -> &'static str {
+///     /* ... authenticate ... */
+///     session.renew();
+///     session.insert("user_id", 42).unwrap();
+///     "logged in"
+/// }
+///
+/// async fn me(session: Session) -> Result<String, Response> {
+///     let user_id = session.get::<u64>("user_id")
+///         .ok_or_else(Response::Unauthorized)?;
+///     Ok(format!("user #{user_id}"))
+/// }
+///
+/// async fn logout(session: Session) -> &'static str {
+///     session.destroy();
+///     "logged out"
+/// }
+///
+/// #[tokio::main]
+/// async fn main() {
+///     Ohkami::new((
+///         Sessions::new(MemorySessionStore::new())
+///             .cookie(|d| d.Path("/").HttpOnly().Secure().SameSiteLax()),
+///         "/login".POST(login),
+///         "/me".GET(me),
+///         "/logout".POST(logout),
+///     )).howl("localhost:3000").await
+/// }
+/// ```
+/// ---
+pub struct Sessions<Store: SessionStore> {
+    store:  Arc<Store>,
+    config: Arc<Config>,
+}
+
+struct Config {
+    cookie_name:  &'static str,
+    directives:   Box<dyn Fn(SetCookieBuilder)->SetCookieBuilder + Send + Sync>,
+    idle_timeout: Duration,
+}
+
+impl<Store: SessionStore> Clone for Sessions<Store> {
+    fn clone(&self) -> Self {
+        Self { store: Arc::clone(&self.store), config: Arc::clone(&self.config) }
+    }
+}
+
+impl<Store: SessionStore> Sessions<Store> {
+    pub fn new(store: Store) -> Self {
+        Self {
+            store:  Arc::new(store),
+            config: Arc::new(Config {
+                cookie_name:  "session_id",
+                directives:   Box::new(|d| d.Path("/").HttpOnly().SameSiteLax()),
+                idle_timeout: Duration::from_secs(30 * 60),
+            }),
+        }
+    }
+
+    /// Name of the session id Cookie ( default: `session_id` )
+    pub fn cookie_name(mut self, name: &'static str) -> Self {
+        self.config_mut().cookie_name = name;
+        self
+    }
+
+    /// Directives of the session id Cookie
+    /// ( default: `Path=/; HttpOnly; SameSite=Lax` )
+    pub fn cookie(mut self, directives: impl Fn(SetCookieBuilder)->SetCookieBuilder + Send + Sync + 'static) -> Self {
+        self.config_mut().directives = Box::new(directives);
+        self
+    }
+
+    /// Duration after which a session without any request expires
+    /// ( default: 30 minutes )
+    pub fn idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.config_mut().idle_timeout = idle_timeout;
+        self
+    }
+
+    fn config_mut(&mut self) -> &mut Config {
+        Arc::get_mut(&mut self.config).expect("`Sessions` is configured before cloned")
+    }
+}
+
+
+/// # Backend of [`Sessions`]
+///
+/// Store the session data, serialized as JSON, by session id with the
+/// idle timeout `ttl`. An expired session must not be loaded.
+pub trait SessionStore: SendSyncOnNative + 'static {
+    type Error: std::fmt::Display;
+
+    fn load(&self, id: &str) -> impl SendOnNativeFuture<Result<Option<String>, Self::Error>>;
+
+    fn save(&self, id: &str, data: &str, ttl: Duration) -> impl SendOnNativeFuture<Result<(), Self::Error>>;
+
+    /// Extend the expiration of the unchanged session by `ttl`.
+    fn touch(&self, id: &str, ttl: Duration) -> impl SendOnNativeFuture<Result<(), Self::Error>>;
+
+    fn delete(&self, id: &str) -> impl SendOnNativeFuture<Result<(), Self::Error>>;
+}
+
+/// In-memory [`SessionStore`] for a single process
+#[derive(Default)]
+pub struct MemorySessionStore(
+    /// id => (data, expiration as unix timestamp)
+    Mutex<HashMap<String, (String, u64)>>
+);
+
+impl MemorySessionStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl SessionStore for MemorySessionStore {
+    type Error = std::convert::Infallible;
+
+    async fn load(&self, id: &str) -> Result<Option<String>, Self::Error> {
+        let mut sessions = self.0.lock().unwrap();
+        match sessions.get(id) {
+            Some((_, expires)) if *expires <= crate::util::unix_timestamp() => {
+                sessions.remove(id);
+                Ok(None)
+            }
+            Some((data, _)) => Ok(Some(data.clone())),
+            None => Ok(None),
+        }
+    }
+
+    async fn save(&self, id: &str, data: &str, ttl: Duration) -> Result<(), Self::Error> {
+        let now = crate::util::unix_timestamp();
+        let mut sessions = self.0.lock().unwrap();
+        sessions.retain(|_, (_, expires)| *expires > now);
+        sessions.insert(id.to_string(), (data.to_string(), now + ttl.as_secs()));
+        Ok(())
+    }
+
+    async fn touch(&self, id: &str, ttl: Duration) -> Result<(), Self::Error> {
+        if let Some((_, expires)) = self.0.lock().unwrap().get_mut(id) {
+            *expires = crate::util::unix_timestamp() + ttl.as_secs();
+        }
+        Ok(())
+    }
+
+    async fn delete(&self, id: &str) -> Result<(), Self::Error> {
+        self.0.lock().unwrap().remove(id);
+        Ok(())
+    }
+}
+
+
+/// # Session of the request
+///
+/// Available in handlers under [`Sessions`] fang. Values are stored
+/// as JSON.
+#[derive(Clone)]
+pub struct Session(
+    Arc<Mutex<State>>
+);
+
+struct State {
+    /// `None` when no session is established yet
+    id:        Option<String>,
+    data:      serde_json::Map<String, serde_json::Value>,
+    modified:  bool,
+    renew:     bool,
+    destroyed: bool,
+}
+
+impl Session {
+    fn new(id: Option<String>, data: serde_json::Map<String, serde_json::Value>) -> Self {
+        Self(Arc::new(Mutex::new(State {
+            id, data, modified: false, renew: false, destroyed: false
+        })))
+    }
+
+    /// The session id, `None` for a session not established yet.
+    /// The id may change at the end of the request by [`Session::renew`].
+    pub fn id(&self) -> Option<String> {
+        self.0.lock().unwrap().id.clone()
+    }
+
+    /// Deserialize the value of `key`. `None` when missing or
+    /// failed to deserialize.
+    pub fn get<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let state = self.0.lock().unwrap();
+        T::deserialize(state.data.get(key)?).ok()
+    }
+
+    pub fn insert(&self, key: impl Into<String>, value: impl Serialize) -> Result<(), impl std::error::Error> {
+        let value = serde_json::to_value(value)?;
+        let mut state = self.0.lock().unwrap();
+        state.data.insert(key.into(), value);
+        state.modified = true;
+        Ok::<_, serde_json::Error>(())
+    }
+
+    pub fn remove(&self, key: &str) {
+        let mut state = self.0.lock().unwrap();
+        if state.data.remove(key).is_some() {
+            state.modified = true;
+        }
+    }
+
+    pub fn clear(&self) {
+        let mut state = self.0.lock().unwrap();
+        state.data.clear();
+        state.modified = true;
+    }
+
+    /// Rotate the session id at the end of the request, keeping the data.
+    /// Call this on a privilege change like login to prevent session fixation.
+    pub fn renew(&self) {
+        self.0.lock().unwrap().renew = true;
+    }
+
+    /// Delete the session from the store and the client.
+    pub fn destroy(&self) {
+        let mut state = self.0.lock().unwrap();
+        state.data.clear();
+        state.destroyed = true;
+    }
+}
+
+impl<'req> FromRequest<'req> for Session {
+    type Error = std::convert::Infallible;
+
+    fn from_request(req: &'req Request) -> Option<Result<Self, Self::Error>> {
+        match req.context.get::<Session>() {
+            Some(session) => Some(Ok(session.clone())),
+            None => {
+                #[cfg(debug_assertions)] {
+                    crate::warning!("`Session` requires `Sessions` fang")
+                }
+                None
+            }
+        }
+    }
+}
+
+fn new_session_id() -> String {
+    let mut bytes = [0; 32];
+    getrandom::getrandom(&mut bytes).expect("failed to generate a session id");
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+
+impl<Store: SessionStore, Inner: FangProc> Fang<Inner> for Sessions<Store> {
+    type Proc = SessionsProc<Store, Inner>;
+    fn chain(&self, inner: Inner) -> Self::Proc {
+        SessionsProc { sessions: self.clone(), inner }
+    }
+}
+
+pub struct SessionsProc<Store: SessionStore, Inner: FangProc> {
+    sessions: Sessions<Store>,
+    inner:    Inner,
+}
+impl<Store: SessionStore, Inner: FangProc> FangProc for SessionsProc<Store, Inner> {
+    async fn bite<'b>(&'b self, req: &'b mut Request) -> Response {
+        let Sessions { store, config } = &self.sessions;
+
+        macro_rules! store {
+            ($op:ident($($arg:expr),*)) => {
+                match store.$op($($arg),*).await {
+                    Ok(ok) => ok,
+                    Err(e) => {
+                        crate::warning!("[Sessions] failed to {} a session: {e}", stringify!($op));
+                        return crate::typed::reject(crate::Status::InternalServerError, "")
+                    }
+                }
+            };
+        }
+
+        let cookie_id = req.headers.Cookies()
+            .find(|(name, _)| *name == config.cookie_name)
+            .map(|(_, id)| id.to_string());
+
+        let loaded = match &cookie_id {
+            None     => None,
+            Some(id) => store!(load(id)).and_then(|data| serde_json::from_str(&data).ok()),
+        };
+        let session = match loaded {
+            Some(data) => Session::new(cookie_id.clone(), data),
+            None       => Session::new(None, Default::default()),
+        };
+        req.context.set(session.clone());
+
+        let mut res = self.inner.bite(req).await;
+
+        let State { id, data, modified, renew, destroyed } = {
+            let mut state = session.0.lock().unwrap();
+            let fresh = State {
+                id: None, data: Default::default(), modified: false, renew: false, destroyed: false
+            };
+            std::mem::replace(&mut *state, fresh)
+        };
+
+        if destroyed {
+            if let Some(id) = &id {
+                store!(delete(id));
+            }
+            if cookie_id.is_some() {
+                match (config.directives)(SetCookieBuilder::new(config.cookie_name, ""))
+                    .MaxAge(0).ExpiresAt(0).build()
+                {
+                    Ok(setcookie) => res.headers.push_setcookie(setcookie),
+                    Err(e) => crate::warning!("[Sessions] failed to remove the session Cookie: {e}"),
+                }
+            }
+            return res
+        }
+
+        match id {
+            Some(id) if !renew => if modified {
+                store!(save(&id, &serde_json::Value::Object(data).to_string(), config.idle_timeout));
+            } else {
+                store!(touch(&id, config.idle_timeout));
+            },
+            old_id => {
+                if old_id.is_none() && data.is_empty() {
+                    return res
+                }
+                if let Some(old_id) = &old_id {
+                    store!(delete(old_id));
+                }
+                let new_id = new_session_id();
+                store!(save(&new_id, &serde_json::Value::Object(data).to_string(), config.idle_timeout));
+                match (config.directives)(SetCookieBuilder::new(config.cookie_name, new_id)).build() {
+                    Ok(setcookie) => res.headers.push_setcookie(setcookie),
+                    Err(e) => crate::warning!("[Sessions] failed to set the session Cookie: {e}"),
+                }
+            }
+        }
+
+        res
+    }
+}
+
+
+#[cfg(all(test, debug_assertions, feature="__rt_native__", feature="DEBUG"))]
+#[test] fn test_sessions() {
+    use crate::prelude::*;
+    use crate::testing::*;
+
+    /// test double recording the operations
+    #[derive(Default)]
+    struct RecordingStore {
+        inner: MemorySessionStore,
+        log:   Arc<Mutex<Vec<String>>>,
+    }
+    impl SessionStore for RecordingStore {
+        type Error = &'static str;
+
+        async fn load(&self, id: &str) -> Result<Option<String>, Self::Error> {
+            if id == "broken" {return Err("store is down")}
+            self.log.lock().unwrap().push(format!("load {id}"));
+            self.inner.load(id).await.map_err(|e| match e {})
+        }
+        async fn save(&self, id: &str, data: &str, ttl: Duration) -> Result<(), Self::Error> {
+            self.log.lock().unwrap().push(format!("save {id} {data} {}", ttl.as_secs()));
+            self.inner.save(id, data, ttl).await.map_err(|e| match e {})
+        }
+        async fn touch(&self, id: &str, ttl: Duration) -> Result<(), Self::Error> {
+            self.log.lock().unwrap().push(format!("touch {id} {}", ttl.as_secs()));
+            self.inner.touch(id, ttl).await.map_err(|e| match e {})
+        }
+        async fn delete(&self, id: &str) -> Result<(), Self::Error> {
+            self.log.lock().unwrap().push(format!("delete {id}"));
+            self.inner.delete(id).await.map_err(|e| match e {})
+        }
+    }
+
+    async fn count(session: Session) -> String {
+        let count = session.get::<u64>("count").unwrap_or(0) + 1;
+        session.insert("count", count).unwrap();
+        format!("{count}")
+    }
+    async fn show(session: Session) -> String {
+        format!("{:?}", session.get::<u64>("count"))
+    }
+    async fn login(session: Session) -> &'static str {
+        session.renew();
+        "ok"
+    }
+    async fn logout(session: Session) -> &'static str {
+        session.destroy();
+        "ok"
+    }
+
+    fn session_id(res: &TestResponse) -> Option<String> {
+        let setcookie = res.headers().find(|(n, _)| *n == "Set-Cookie")?.1;
+        Some(setcookie.strip_prefix("sid=")?.split(';').next()?.to_string())
+    }
+
+    crate::__rt__::testing::block_on(async {
+        let store = RecordingStore::default();
+        let log = Arc::clone(&store.log);
+        let take_log = || std::mem::take(&mut *log.lock().unwrap());
+
+        let t = Ohkami::new((
+            Sessions::new(store)
+                .cookie_name("sid")
+                .idle_timeout(Duration::from_secs(60)),
+            "/count".POST(count),
+            "/show".GET(show),
+            "/login".POST(login),
+            "/logout".POST(logout),
+        )).test();
+
+        /* no session is established without data */
+        let res = t.oneshot(TestRequest::GET("/show")).await;
+        assert_eq!(res.text(), Some("None"));
+        assert_eq!(session_id(&res), None);
+        assert!(take_log().is_empty());
+
+        let res = t.oneshot(TestRequest::POST("/count")).await;
+        assert_eq!(res.text(), Some("1"));
+        let id = session_id(&res).unwrap();
+        assert_eq!(id.len(), 64);
+        assert_eq!(res.headers().find(|(n, _)| *n == "Set-Cookie").unwrap().1,
+            format!("sid={id}; Path=/; HttpOnly; SameSite=Lax"));
+        assert_eq!(take_log(), [format!(r#"save {id} {{"count":1}} 60"#)]);
+
+        let res = t.oneshot(TestRequest::POST("/count")
+            .header("Cookie", format!("sid={id}"))
+        ).await;
+        assert_eq!(res.text(), Some("2"));
+        assert_eq!(session_id(&res), None);
+        assert_eq!(take_log(), [
+            format!("load {id}"),
+            format!(r#"save {id} {{"count":2}} 60"#),
+        ]);
+
+        /* unchanged session is only touched */
+        let res = t.oneshot(TestRequest::GET("/show")
+            .header("Cookie", format!("sid={id}"))
+        ).await;
+        assert_eq!(res.text(), Some("Some(2)"));
+        assert_eq!(take_log(), [format!("load {id}"), format!("touch {id} 60")]);
+
+        /* renew rotates the id keeping the data */
+        let res = t.oneshot(TestRequest::POST("/login")
+            .header("Cookie", format!("sid={id}"))
+        ).await;
+        let new_id = session_id(&res).unwrap();
+        assert_ne!(new_id, id);
+        assert_eq!(take_log(), [
+            format!("load {id}"),
+            format!("delete {id}"),
+            format!(r#"save {new_id} {{"count":2}} 60"#),
+        ]);
+        let res = t.oneshot(TestRequest::GET("/show")
+            .header("Cookie", format!("sid={id}"))
+        ).await;
+        assert_eq!(res.text(), Some("None"));
+        let res = t.oneshot(TestRequest::GET("/show")
+            .header("Cookie", format!("sid={new_id}"))
+        ).await;
+        assert_eq!(res.text(), Some("Some(2)"));
+        take_log();
+
+        /* unknown id is never adopted */
+        let res = t.oneshot(TestRequest::POST("/count")
+            .header("Cookie", "sid=attacker-chosen")
+        ).await;
+        assert_eq!(res.text(), Some("1"));
+        assert!(session_id(&res).is_some_and(|id| id != "attacker-chosen"));
+        take_log();
+
+        let res = t.oneshot(TestRequest::POST("/logout")
+            .header("Cookie", format!("sid={new_id}"))
+        ).await;
+        assert_eq!(res.headers().find(|(n, _)| *n == "Set-Cookie").unwrap().1,
+            "sid=; Expires=Thu, 01 Jan 1970 00:00:00 GMT; Max-Age=0; Path=/; HttpOnly; SameSite=Lax");
+        assert_eq!(take_log(), [format!("load {new_id}"), format!("delete {new_id}")]);
+
+        let res = t.oneshot(TestRequest::GET("/show")
+            .header("Cookie", "sid=broken")
+        ).await;
+        assert_eq!(res.status(), Status::InternalServerError);
+    });
+
+    /* idle sessions expire */
+    crate::__rt__::testing::block_on(async {
+        let t = Ohkami::new((
+            Sessions::new(MemorySessionStore::new()).idle_timeout(Duration::ZERO),
+            "/count".POST(count),
+        )).test();
+
+        let res = t.oneshot(TestRequest::POST("/count")).await;
+        let setcookie = res.headers().find(|(n, _)| *n == "Set-Cookie").unwrap().1;
+        let cookie = setcookie.split(';').next().unwrap().to_string();
+
+        let res = t.oneshot(TestRequest::POST("/count").header("Cookie", cookie)).await;
+        assert_eq!(res.text(), Some("1"));
+    });
+}
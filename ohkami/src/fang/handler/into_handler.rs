@@ -1,6 +1,7 @@
 use std::{future::Future, pin::Pin};
 use super::{Handler, SendOnNative, SendSyncOnNative, SendOnNativeFuture};
-use crate::{Response, FromRequest, FromParam, Request, IntoResponse};
+use crate::{Response, FromRequest, FromParam, Request, IntoResponse, Rejection};
+use crate::request::{decode_param, extractor_name, handle_rejection};
 
 #[cfg(feature="openapi")]
 use crate::openapi;
@@ -9,6 +10,16 @@ use crate::openapi;
 pub trait IntoHandler<T> {
     fn n_params(&self) -> usize;
     fn into_handler(self) -> Handler;
+
+    #[doc(hidden)]
+    fn __handler_name__(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
+    #[doc(hidden)]
+    /// type names of local fangs, from the most outer one
+    fn __fang_names__(&self) -> Vec<&'static str> {
+        vec![]
+    }
 }
 
 
@@ -25,8 +36,19 @@ fn from_request<'fr, 'req, R: FromRequest<'fr>>(
     <R as FromRequest>::from_request(unsafe {
         std::mem::transmute::<&'req _, &'fr _>(req)
     })
-        .ok_or_else(|| Response::BadRequest().with_text("missing something expected in request"))?
-        .map_err(IntoResponse::into_response)
+        .ok_or_else(|| handle_rejection(req, Rejection::new(
+            crate::Status::BadRequest, extractor_name::<R>(), "missing something expected in request"
+        )))?
+        .map_err(|e| handle_rejection(req, e))
+}
+
+#[inline(always)]
+fn from_param<'p, P: FromParam<'p>>(
+    req:       &Request,
+    raw_param: &'p [u8],
+) -> Result<P, Response> {
+    P::from_param(decode_param::<P>(raw_param).map_err(|e| handle_rejection(req, e))?)
+        .map_err(|e| handle_rejection(req, e))
 }
 
 #[cfg(feature="openapi")]
@@ -96,7 +118,7 @@ const _: (/* FromParam */) = {
 
         fn into_handler(self) -> Handler {
             Handler::new(move |req| {
-                match P1::from_raw_param(unsafe {req.path.assume_one_param()}) {
+                match from_param::<P1>(req, unsafe {req.path.assume_one_param()}) {
                     Ok(p1) => {
                         let res = self(p1);
                         Box::pin(async move {res.await.into_response()})
@@ -121,7 +143,7 @@ const _: (/* FromParam */) = {
         fn into_handler(self) -> Handler {
             Handler::new(move |req| {
                 // SAFETY: `crate::Route` has already checked the number of params
-                match P1::from_raw_param(unsafe {req.path.assume_one_param()}) {
+                match from_param::<P1>(req, unsafe {req.path.assume_one_param()}) {
                     Ok(p1) => {
                         let res = self((p1,));
                         Box::pin(async move {res.await.into_response()})
@@ -145,7 +167,7 @@ const _: (/* FromParam */) = {
         fn into_handler(self) -> Handler {
             Handler::new(move |req| {
                 let (p1, p2) = unsafe {req.path.assume_two_params()};
-                match (P1::from_raw_param(p1), P2::from_raw_param(p2)) {
+                match (from_param::<P1>(req, p1), from_param::<P2>(req, p2)) {
                     (Ok(p1), Ok(p2)) => {
                         let res = self((p1, p2));
                         Box::pin(async move {res.await.into_response()})
@@ -280,7 +302,7 @@ const _: (/* one FromParam without tuple and FromRequest items */) = {
                 // SAFETY: `crate::Route` has already checked the number of params
                 let p1 = unsafe {req.path.assume_one_param()};
 
-                match (P1::from_raw_param(p1), from_request(req)) {
+                match (from_param::<P1>(req, p1), from_request(req)) {
                     (Ok(p1), Ok(item1)) => {
                         let res = self(p1, item1);
                         Box::pin(async move {res.await.into_response()})
@@ -308,7 +330,7 @@ const _: (/* one FromParam without tuple and FromRequest items */) = {
                 // SAFETY: `crate::Route` has already checked the number of params
                 let p1 = unsafe {req.path.assume_one_param()};
 
-                match (P1::from_raw_param(p1), from_request::<Item1>(req), from_request::<Item2>(req)) {
+                match (from_param::<P1>(req, p1), from_request::<Item1>(req), from_request::<Item2>(req)) {
                     (Ok(p1), Ok(item1), Ok(item2)) => {
                         let res = self(p1, item1, item2);
                         Box::pin(async move {res.await.into_response()})
@@ -338,7 +360,7 @@ const _: (/* one FromParam without tuple and FromRequest items */) = {
                 // SAFETY: `crate::Route` has already checked the number of params
                 let p1 = unsafe {req.path.assume_one_param()};
 
-                match (P1::from_raw_param(p1), from_request::<Item1>(req), from_request::<Item2>(req), from_request::<Item3>(req)) {
+                match (from_param::<P1>(req, p1), from_request::<Item1>(req), from_request::<Item2>(req), from_request::<Item3>(req)) {
                     (Ok(p1), Ok(item1), Ok(item2), Ok(item3)) => {
                         let res = self(p1, item1, item2, item3);
                         Box::pin(async move {res.await.into_response()})
@@ -370,7 +392,7 @@ const _: (/* one FromParam without tuple and FromRequest items */) = {
                 // SAFETY: `crate::Route` has already checked the number of params
                 let p1 = unsafe {req.path.assume_one_param()};
 
-                match (P1::from_raw_param(p1), from_request::<Item1>(req), from_request::<Item2>(req), from_request::<Item3>(req), from_request::<Item4>(req)) {
+                match (from_param::<P1>(req, p1), from_request::<Item1>(req), from_request::<Item2>(req), from_request::<Item3>(req), from_request::<Item4>(req)) {
                     (Ok(p1), Ok(item1), Ok(item2), Ok(item3), Ok(item4)) => {
                         let res = self(p1, item1, item2, item3, item4);
                         Box::pin(async move {res.await.into_response()})
@@ -406,7 +428,7 @@ const _: (/* one FromParam and FromRequest items */) = {
                 // SAFETY: `crate::Route` has already checked the number of params
                 let p1 = unsafe {req.path.assume_one_param()};
 
-                match (P1::from_raw_param(p1), from_request::<Item1>(req)) {
+                match (from_param::<P1>(req, p1), from_request::<Item1>(req)) {
                     (Ok(p1), Ok(item1)) => {
                         let res = self((p1,), item1);
                         Box::pin(async move {res.await.into_response()})
@@ -434,7 +456,7 @@ const _: (/* one FromParam and FromRequest items */) = {
                 // SAFETY: `crate::Route` has already checked the number of params
                 let p1 = unsafe {req.path.assume_one_param()};
 
-                match (P1::from_raw_param(p1), from_request::<Item1>(req), from_request::<Item2>(req)) {
+                match (from_param::<P1>(req, p1), from_request::<Item1>(req), from_request::<Item2>(req)) {
                     (Ok(p1), Ok(item1), Ok(item2)) => {
                         let res = self((p1,), item1, item2);
                         Box::pin(async move {res.await.into_response()})
@@ -464,7 +486,7 @@ const _: (/* one FromParam and FromRequest items */) = {
                 // SAFETY: `crate::Route` has already checked the number of params
                 let p1 = unsafe {req.path.assume_one_param()};
                 
-                match (P1::from_raw_param(p1), from_request::<Item1>(req), from_request::<Item2>(req), from_request::<Item3>(req)) {
+                match (from_param::<P1>(req, p1), from_request::<Item1>(req), from_request::<Item2>(req), from_request::<Item3>(req)) {
                     (Ok(p1), Ok(item1), Ok(item2), Ok(item3)) => {
                         let res = self((p1,), item1, item2, item3);
                         Box::pin(async move {res.await.into_response()})
@@ -496,7 +518,7 @@ const _: (/* one FromParam and FromRequest items */) = {
                 // SAFETY: `crate::Route` has already checked the number of params
                 let p1 = unsafe {req.path.assume_one_param()};
                 
-                match (P1::from_raw_param(p1), from_request::<Item1>(req), from_request::<Item2>(req), from_request::<Item3>(req), from_request::<Item4>(req)) {
+                match (from_param::<P1>(req, p1), from_request::<Item1>(req), from_request::<Item2>(req), from_request::<Item3>(req), from_request::<Item4>(req)) {
                     (Ok(p1), Ok(item1), Ok(item2), Ok(item3), Ok(item4)) => {
                         let res = self((p1,), item1, item2, item3, item4);
                         Box::pin(async move {res.await.into_response()})
@@ -532,7 +554,7 @@ const _: (/* two PathParams and FromRequest items */) = {
                 // SAFETY: `crate::Route` has already checked the number of params
                 let (p1, p2) = unsafe {req.path.assume_two_params()};
 
-                match (FromParam::from_raw_param(p1), FromParam::from_raw_param(p2), from_request::<Item1>(req)) {
+                match (from_param(req, p1), from_param(req, p2), from_request::<Item1>(req)) {
                     (Ok(p1), Ok(p2), Ok(item1)) => {
                         let res = self((p1, p2), item1); 
                         Box::pin(async move {res.await.into_response()})
@@ -562,7 +584,7 @@ const _: (/* two PathParams and FromRequest items */) = {
                 // SAFETY: `crate::Route` has already checked the number of params
                 let (p1, p2) = unsafe {req.path.assume_two_params()};
 
-                match (FromParam::from_raw_param(p1), FromParam::from_raw_param(p2), from_request::<Item1>(req), from_request::<Item2>(req)) {
+                match (from_param(req, p1), from_param(req, p2), from_request::<Item1>(req), from_request::<Item2>(req)) {
                     (Ok(p1), Ok(p2), Ok(item1), Ok(item2)) => {
                         let res = self((p1, p2), item1, item2);
                         Box::pin(async move {res.await.into_response()})
@@ -594,7 +616,7 @@ const _: (/* two PathParams and FromRequest items */) = {
                 // SAFETY: `crate::Route` has already checked the number of params
                 let (p1, p2) = unsafe {req.path.assume_two_params()};
 
-                match (FromParam::from_raw_param(p1), FromParam::from_raw_param(p2), from_request::<Item1>(req), from_request::<Item2>(req), from_request::<Item3>(req)) {
+                match (from_param(req, p1), from_param(req, p2), from_request::<Item1>(req), from_request::<Item2>(req), from_request::<Item3>(req)) {
                     (Ok(p1), Ok(p2), Ok(item1), Ok(item2), Ok(item3)) => {
                         let res = self((p1, p2), item1, item2, item3);
                         Box::pin(async move {res.await.into_response()})
@@ -628,7 +650,7 @@ const _: (/* two PathParams and FromRequest items */) = {
                 // SAFETY: `crate::Route` has already checked the number of params
                 let (p1, p2) = unsafe {req.path.assume_two_params()};
 
-                match (FromParam::from_raw_param(p1), FromParam::from_raw_param(p2), from_request::<Item1>(req), from_request::<Item2>(req), from_request::<Item3>(req), from_request::<Item4>(req)) {
+                match (from_param(req, p1), from_param(req, p2), from_request::<Item1>(req), from_request::<Item2>(req), from_request::<Item3>(req), from_request::<Item4>(req)) {
                     (Ok(p1), Ok(p2), Ok(item1), Ok(item2), Ok(item3), Ok(item4)) => {
                         let res = self((p1, p2), item1, item2, item3, item4);
                         Box::pin(async move {res.await.into_response()})
@@ -69,7 +69,7 @@ impl Handler {
 
         static NOT_FOUND: LazyLock<Handler> = LazyLock::new(|| {
             async fn not_found() -> Response {
-                Response::NotFound()
+                crate::typed::reject(crate::Status::NotFound, "")
             }
             not_found.into_handler()
         });
@@ -114,7 +114,7 @@ impl Handler {
                         (if available_methods.contains(&method) {
                             crate::Response::NotImplemented()
                         } else {
-                            crate::Response::BadRequest()
+                            crate::typed::reject(crate::Status::BadRequest, "")
                         }).with_headers(|h| h
                             .AccessControlAllowMethods(available_methods_str)
                         )
@@ -131,7 +131,7 @@ impl Handler {
                                 )
                             ```
                         */
-                        crate::Response::NotFound()
+                        crate::typed::reject(crate::Status::NotFound, "")
                     }
                 }
             })
@@ -1,5 +1,6 @@
 use super::{Handler, IntoHandler};
 use super::super::{Fang, BoxedFPC, middleware::Fangs};
+use std::any::type_name;
 
 
 impl<H: IntoHandler<T>, T, F1> IntoHandler<(F1, H, T)> for (F1, H)
@@ -7,6 +8,12 @@ where
     F1: Fang<BoxedFPC>
 {
     fn n_params(&self) -> usize {self.1.n_params()}
+    fn __handler_name__(&self) -> &'static str {self.1.__handler_name__()}
+    fn __fang_names__(&self) -> Vec<&'static str> {
+        let mut names = vec![type_name::<F1>()];
+        names.extend(self.1.__fang_names__());
+        names
+    }
 
     fn into_handler(self) -> Handler {
         let (f, h) = self;
@@ -25,6 +32,12 @@ where
     F2: Fang<BoxedFPC>,
 {
     fn n_params(&self) -> usize {self.2.n_params()}
+    fn __handler_name__(&self) -> &'static str {self.2.__handler_name__()}
+    fn __fang_names__(&self) -> Vec<&'static str> {
+        let mut names = vec![type_name::<F1>(), type_name::<F2>()];
+        names.extend(self.2.__fang_names__());
+        names
+    }
 
     fn into_handler(self) -> Handler {
         let (f1, f2, h) = self;
@@ -45,6 +58,12 @@ where
     F3: Fang<BoxedFPC>,
 {
     fn n_params(&self) -> usize {self.3.n_params()}
+    fn __handler_name__(&self) -> &'static str {self.3.__handler_name__()}
+    fn __fang_names__(&self) -> Vec<&'static str> {
+        let mut names = vec![type_name::<F1>(), type_name::<F2>(), type_name::<F3>()];
+        names.extend(self.3.__fang_names__());
+        names
+    }
 
     fn into_handler(self) -> Handler {
         let (f1, f2, f3, h) = self;
@@ -66,6 +85,12 @@ where
     F4: Fang<BoxedFPC>,
 {
     fn n_params(&self) -> usize {self.4.n_params()}
+    fn __handler_name__(&self) -> &'static str {self.4.__handler_name__()}
+    fn __fang_names__(&self) -> Vec<&'static str> {
+        let mut names = vec![type_name::<F1>(), type_name::<F2>(), type_name::<F3>(), type_name::<F4>()];
+        names.extend(self.4.__fang_names__());
+        names
+    }
 
     fn into_handler(self) -> Handler {
         let (f1, f2, f3, f4, h) = self;
@@ -1,5 +1,6 @@
 pub mod util;
 use super::{Fang, BoxedFPC};
+use std::any::type_name;
 
 
 #[allow(private_interfaces)]
@@ -9,6 +10,11 @@ pub trait Fangs {
 
     #[cfg(feature="openapi")]
     fn openapi_map_operation(&self, operation: crate::openapi::Operation) -> crate::openapi::Operation;
+
+    /// type names of the fangs, from the most outer one
+    fn type_names(&self) -> Vec<&'static str> {
+        vec![type_name::<Self>()]
+    }
 }
 
 #[allow(private_interfaces)]
@@ -29,6 +35,10 @@ const _: () = {
             inner
         }
 
+        fn type_names(&self) -> Vec<&'static str> {
+            vec![]
+        }
+
         #[cfg(feature="openapi")]
         fn openapi_map_operation(&self, operation: crate::openapi::Operation) -> crate::openapi::Operation {
             operation
@@ -39,6 +49,10 @@ const _: () = {
         F1: Fang<BoxedFPC>,
     > Fangs for (F1,)
     {
+        fn type_names(&self) -> Vec<&'static str> {
+            vec![type_name::<F1>()]
+        }
+
         fn build(&self, inner: BoxedFPC) -> BoxedFPC {
             let (f1,) = self;
             BoxedFPC::from_proc(
@@ -57,6 +71,10 @@ const _: () = {
         F1: Fang<F2::Proc>,
         F2: Fang<BoxedFPC>,
     > Fangs for (F1, F2) {
+        fn type_names(&self) -> Vec<&'static str> {
+            vec![type_name::<F1>(), type_name::<F2>()]
+        }
+
         fn build(&self, inner: BoxedFPC) -> BoxedFPC {
             let (f1, f2) = self;
             BoxedFPC::from_proc(
@@ -80,6 +98,10 @@ const _: () = {
         F2: Fang<F3::Proc>,
         F3: Fang<BoxedFPC>,
     > Fangs for (F1, F2, F3) {
+        fn type_names(&self) -> Vec<&'static str> {
+            vec![type_name::<F1>(), type_name::<F2>(), type_name::<F3>()]
+        }
+
         fn build(&self, inner: BoxedFPC) -> BoxedFPC {
             let (f1, f2, f3) = self;
             BoxedFPC::from_proc(
@@ -108,6 +130,10 @@ const _: () = {
         F3: Fang<F4::Proc>,
         F4: Fang<BoxedFPC>,
     > Fangs for (F1, F2, F3, F4) {
+        fn type_names(&self) -> Vec<&'static str> {
+            vec![type_name::<F1>(), type_name::<F2>(), type_name::<F3>(), type_name::<F4>()]
+        }
+
         fn build(&self, inner: BoxedFPC) -> BoxedFPC {
             let (f1, f2, f3, f4) = self;
             BoxedFPC::from_proc(
@@ -141,6 +167,10 @@ const _: () = {
         F4: Fang<F5::Proc>,
         F5: Fang<BoxedFPC>,
     > Fangs for (F1, F2, F3, F4, F5) {
+        fn type_names(&self) -> Vec<&'static str> {
+            vec![type_name::<F1>(), type_name::<F2>(), type_name::<F3>(), type_name::<F4>(), type_name::<F5>()]
+        }
+
         fn build(&self, inner: BoxedFPC) -> BoxedFPC {
             let (f1, f2, f3, f4, f5) = self;
             BoxedFPC::from_proc(
@@ -179,6 +209,10 @@ const _: () = {
         F5: Fang<F6::Proc>,
         F6: Fang<BoxedFPC>,
     > Fangs for (F1, F2, F3, F4, F5, F6) {
+        fn type_names(&self) -> Vec<&'static str> {
+            vec![type_name::<F1>(), type_name::<F2>(), type_name::<F3>(), type_name::<F4>(), type_name::<F5>(), type_name::<F6>()]
+        }
+
         fn build(&self, inner: BoxedFPC) -> BoxedFPC {
             let (f1, f2, f3, f4, f5, f6) = self;
             BoxedFPC::from_proc(
@@ -222,6 +256,10 @@ const _: () = {
         F6: Fang<F7::Proc>,
         F7: Fang<BoxedFPC>,
     > Fangs for (F1, F2, F3, F4, F5, F6, F7) {
+        fn type_names(&self) -> Vec<&'static str> {
+            vec![type_name::<F1>(), type_name::<F2>(), type_name::<F3>(), type_name::<F4>(), type_name::<F5>(), type_name::<F6>(), type_name::<F7>()]
+        }
+
         fn build(&self, inner: BoxedFPC) -> BoxedFPC {
             let (f1, f2, f3, f4, f5, f6, f7) = self;
             BoxedFPC::from_proc(
@@ -270,6 +308,10 @@ const _: () = {
         F7: Fang<F8::Proc>,
         F8: Fang<BoxedFPC>,
     > Fangs for (F1, F2, F3, F4, F5, F6, F7, F8) {
+        fn type_names(&self) -> Vec<&'static str> {
+            vec![type_name::<F1>(), type_name::<F2>(), type_name::<F3>(), type_name::<F4>(), type_name::<F5>(), type_name::<F6>(), type_name::<F7>(), type_name::<F8>()]
+        }
+
         fn build(&self, inner: BoxedFPC) -> BoxedFPC {
             let (f1, f2, f3, f4, f5, f6, f7, f8) = self;
             BoxedFPC::from_proc(
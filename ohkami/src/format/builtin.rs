@@ -16,11 +16,12 @@ pub use html::HTML;
 mod query;
 pub use query::Query;
 
-
-#[cold] #[inline(never)]
-fn reject(msg: impl std::fmt::Display) -> crate::Response {
-    crate::Response::BadRequest().with_text(msg.to_string())
-}
+mod negotiated;
+pub use negotiated::{Negotiated, Representation, Representations, AsJSON, AsURLEncoded, AsCSV};
+#[cfg(feature="msgpack")]
+pub use negotiated::AsMessagePack;
+#[cfg(feature="cbor")]
+pub use negotiated::AsCBOR;
 
 #[cfg(feature="openapi")]
 pub mod bound {
@@ -0,0 +1,268 @@
+use crate::{IntoResponse, Response, Status};
+use crate::typed::header::{Accept, MediaRanges};
+use crate::header::append;
+use super::bound::{self, Outgoing};
+use ohkami_lib::{serde_urlencoded, serde_csv};
+use serde::Serialize;
+use std::marker::PhantomData;
+
+#[cfg(feature="openapi")]
+use crate::openapi;
+
+/// # Content-negotiated response
+///
+/// Serialize `T` into the representation most preferred by the request's
+/// `Accept` among `R`, a tuple of [`Representation`]s. On a tie, the earlier
+/// one in `R` is selected, and the first one is used when `Accept` is missing.
+///
+/// Responds `406 Not Acceptable` when none of `R` is acceptable,
+/// and always sets `Vary: Accept`.
+///
+/// When `openapi` feature is activated, schema bound additionally
+/// requires `openapi::Schema`.
+///
+/// Built-in representations are [`AsJSON`], [`AsURLEncoded`], [`AsCSV`],
+/// and `AsMessagePack` / `AsCBOR` with `msgpack` / `cbor` feature.
+/// Implement [`Representation`] for other formats.
+///
+/// ### example
+///
+/// ```
+/// use ohkami::format::{Negotiated, AsJSON, AsCSV};
+/// use ohkami::typed::header::{Accept, MediaRanges};
+/// use ohkami::serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct User {
+///     name: String,
+///     age: Option<u8>,
+/// }
+///
+/// async fn list_users(
+///     accept: Option<Accept<MediaRanges<'_>>>,
+/// ) -> Negotiated<Vec<User>, (AsJSON, AsCSV)> {
+///     let users = vec![User { name: "ohkami".into(), age: None }];
+///     Negotiated::new(accept, users)
+/// }
+/// ```
+pub struct Negotiated<T: bound::Schema, R: Representations = (AsJSON,)> {
+    value:    T,
+    selected: Option<usize>,
+    __r__:    PhantomData<fn() -> R>,
+}
+
+impl<T: bound::Schema, R: Representations> Negotiated<T, R> {
+    pub fn new(accept: Option<Accept<MediaRanges<'_>>>, value: T) -> Self {
+        let selected = match accept {
+            None => (!R::CONTENT_TYPES.is_empty()).then_some(0),
+            Some(Accept(accept)) => accept
+                .preferred(R::CONTENT_TYPES.iter().copied())
+                .and_then(|content_type| R::CONTENT_TYPES.iter().position(|c| *c == content_type)),
+        };
+        Self { value, selected, __r__: PhantomData }
+    }
+}
+
+impl<T: Outgoing, R: Representations> IntoResponse for Negotiated<T, R> {
+    fn into_response(self) -> Response {
+        let mut res = match self.selected {
+            None => crate::typed::reject(Status::NotAcceptable, format!(
+                "acceptable media types: {}", R::CONTENT_TYPES.join(", ")
+            )),
+            Some(index) => match R::serialize(index, &self.value) {
+                Ok(body) => Response::OK().with_payload(R::CONTENT_TYPES[index], body),
+                Err(_err) => {
+                    #[cfg(debug_assertions)] {
+                        eprintln!("Failed to serialize `{}` as `{}` in `Negotiated`: {_err}",
+                            std::any::type_name::<T>(),
+                            R::CONTENT_TYPES[index]
+                        )
+                    }
+                    crate::typed::reject(Status::InternalServerError, "")
+                }
+            }
+        };
+        res.headers.set().Vary(append("Accept"));
+        res
+    }
+
+    #[cfg(feature="openapi")]
+    fn openapi_responses() -> openapi::Responses {
+        let mut res = openapi::Response::when("OK");
+        for content_type in R::CONTENT_TYPES {
+            let mime_type = match content_type.split_once(';') {
+                None => content_type,
+                Some((mime_type, _)) => mime_type
+            };
+            res = res.content(mime_type, T::schema());
+        }
+        openapi::Responses::new([
+            (200, res),
+            (406, openapi::Response::when("Not Acceptable")),
+        ])
+    }
+}
+
+/// A format [`Negotiated`] can serialize into
+pub trait Representation {
+    /// e.g. `application/json` `text/csv; charset=UTF-8`
+    const CONTENT_TYPE: &'static str;
+
+    fn serialize<T: Serialize>(value: &T) -> Result<Vec<u8>, impl std::fmt::Display>;
+}
+
+/// `application/json` by `serde_json`
+pub struct AsJSON;
+impl Representation for AsJSON {
+    const CONTENT_TYPE: &'static str = "application/json";
+
+    #[inline]
+    fn serialize<T: Serialize>(value: &T) -> Result<Vec<u8>, impl std::fmt::Display> {
+        serde_json::to_vec(value)
+    }
+}
+
+/// `application/x-www-form-urlencoded`
+pub struct AsURLEncoded;
+impl Representation for AsURLEncoded {
+    const CONTENT_TYPE: &'static str = "application/x-www-form-urlencoded";
+
+    #[inline]
+    fn serialize<T: Serialize>(value: &T) -> Result<Vec<u8>, impl std::fmt::Display> {
+        serde_urlencoded::to_string(value).map(String::into_bytes)
+    }
+}
+
+/// `text/csv; charset=UTF-8`, with a header line for struct or map records
+///
+/// A sequence is serialized into records, and other values into one record.
+/// Fields of a record must be scalars.
+pub struct AsCSV;
+impl Representation for AsCSV {
+    const CONTENT_TYPE: &'static str = "text/csv; charset=UTF-8";
+
+    #[inline]
+    fn serialize<T: Serialize>(value: &T) -> Result<Vec<u8>, impl std::fmt::Display> {
+        serde_csv::to_string(value).map(String::into_bytes)
+    }
+}
+
+/// `application/vnd.msgpack`, with structs as maps keyed by the field names
+///
+/// Available with `msgpack` feature.
+#[cfg(feature="msgpack")]
+pub struct AsMessagePack;
+#[cfg(feature="msgpack")]
+impl Representation for AsMessagePack {
+    const CONTENT_TYPE: &'static str = "application/vnd.msgpack";
+
+    #[inline]
+    fn serialize<T: Serialize>(value: &T) -> Result<Vec<u8>, impl std::fmt::Display> {
+        ohkami_lib::serde_msgpack::to_vec(value)
+    }
+}
+
+/// `application/cbor`, with structs as maps keyed by the field names
+///
+/// Available with `cbor` feature.
+#[cfg(feature="cbor")]
+pub struct AsCBOR;
+#[cfg(feature="cbor")]
+impl Representation for AsCBOR {
+    const CONTENT_TYPE: &'static str = "application/cbor";
+
+    #[inline]
+    fn serialize<T: Serialize>(value: &T) -> Result<Vec<u8>, impl std::fmt::Display> {
+        ohkami_lib::serde_cbor::to_vec(value)
+    }
+}
+
+/// Tuple of [`Representation`]s, in the server's preference order
+pub trait Representations {
+    const CONTENT_TYPES: &'static [&'static str];
+
+    fn serialize<T: Serialize>(index: usize, value: &T) -> Result<Vec<u8>, String>;
+}
+macro_rules! representations {
+    ($( ( $($R:ident : $index:literal),+ ) )*) => {$(
+        impl<$($R: Representation),+> Representations for ($($R,)+) {
+            const CONTENT_TYPES: &'static [&'static str] = &[$($R::CONTENT_TYPE),+];
+
+            fn serialize<T: Serialize>(index: usize, value: &T) -> Result<Vec<u8>, String> {
+                match index {
+                    $($index => $R::serialize(value).map_err(|e| e.to_string()),)+
+                    _ => Err(format!("no representation at {index}"))
+                }
+            }
+        }
+    )*};
+} representations! {
+    (R1:0)
+    (R1:0, R2:1)
+    (R1:0, R2:1, R3:2)
+    (R1:0, R2:1, R3:2, R4:3)
+    (R1:0, R2:1, R3:2, R4:3, R5:4)
+}
+
+
+#[cfg(test)]
+#[test] fn test_negotiated() {
+    #[derive(Serialize)]
+    struct User {
+        name: &'static str,
+        age:  u8,
+    }
+    #[cfg(feature="openapi")]
+    impl openapi::Schema for User {
+        fn schema() -> impl Into<openapi::schema::SchemaRef> {
+            openapi::object()
+                .property("name", openapi::string())
+                .property("age", openapi::integer())
+        }
+    }
+
+    fn negotiate(accept: Option<&str>) -> Response {
+        Negotiated::<_, (AsJSON, AsURLEncoded, AsCSV)>::new(
+            accept.map(|a| Accept(MediaRanges::parse(a))),
+            User { name: "ohkami", age: 4 }
+        ).into_response()
+    }
+
+    let res = negotiate(None);
+    assert_eq!(res.status, Status::OK);
+    assert_eq!(res.headers.ContentType(), Some("application/json"));
+    assert_eq!(res.payload(), Some(&br#"{"name":"ohkami","age":4}"#[..]));
+    assert_eq!(res.headers.Vary(), Some("Accept"));
+
+    let res = negotiate(Some("application/x-www-form-urlencoded, application/json;q=0.5"));
+    assert_eq!(res.headers.ContentType(), Some("application/x-www-form-urlencoded"));
+    assert_eq!(res.payload(), Some(&b"name=ohkami&age=4"[..]));
+
+    let res = negotiate(Some("application/*"));
+    assert_eq!(res.headers.ContentType(), Some("application/json"));
+
+    let res = negotiate(Some("text/csv, application/json;q=0.9"));
+    assert_eq!(res.headers.ContentType(), Some("text/csv; charset=UTF-8"));
+    assert_eq!(res.payload(), Some(&b"name,age\r\nohkami,4\r\n"[..]));
+
+    let res = negotiate(Some("application/xml"));
+    assert_eq!(res.status, Status::NotAcceptable);
+    assert_eq!(res.headers.Vary(), Some("Accept"));
+
+    #[cfg(all(feature="msgpack", feature="cbor"))] {
+        fn negotiate_binary(accept: &str) -> Response {
+            Negotiated::<_, (AsMessagePack, AsCBOR)>::new(
+                Some(Accept(MediaRanges::parse(accept))),
+                User { name: "ohkami", age: 4 }
+            ).into_response()
+        }
+
+        let res = negotiate_binary("application/vnd.msgpack");
+        assert_eq!(res.headers.ContentType(), Some("application/vnd.msgpack"));
+        assert_eq!(res.payload(), Some(&b"\x82\xa4name\xa6ohkami\xa3age\x04"[..]));
+
+        let res = negotiate_binary("application/cbor");
+        assert_eq!(res.headers.ContentType(), Some("application/cbor"));
+        assert_eq!(res.payload(), Some(&b"\xa2\x64name\x66ohkami\x63age\x04"[..]));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{Response, FromRequest};
+use crate::{FromRequest, Rejection, Status};
 use super::bound::{self, Incoming};
 
 #[cfg(feature="openapi")]
@@ -47,11 +47,11 @@ use crate::openapi;
 pub struct Query<T: bound::Schema>(pub T);
 
 impl<'req, T: Incoming<'req>> FromRequest<'req> for Query<T> {
-    type Error = Response;
+    type Error = Rejection;
 
     fn from_request(req: &'req crate::Request) -> Option<Result<Self, Self::Error>> {
         req.query.parse()
-            .map_err(super::reject)
+            .map_err(|e| Rejection::new(Status::BadRequest, "Query", e))
             .map(Query).into()
     }
 
@@ -8,6 +8,7 @@
 //! - `URLEncoded` - payload of application/x-www-form-urlencoded
 //! - `Text` - payload of text/plain
 //! - `HTML` - payload of text/html
+//! - `Negotiated` - payload in a representation selected by `Accept`
 
 mod builtin;
 pub use builtin::*;
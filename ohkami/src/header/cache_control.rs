@@ -0,0 +1,224 @@
+use std::borrow::Cow;
+
+
+/// # Structured `Cache-Control`
+///
+/// Build a `Cache-Control` value from directives, or parse one to inspect them.
+///
+/// ```
+/// use ohkami::header::CacheControl;
+///
+/// let cc = CacheControl::new()
+///     .public()
+///     .max_age(60)
+///     .stale_while_revalidate(30);
+/// assert_eq!(cc.to_string(), "public, max-age=60, stale-while-revalidate=30");
+///
+/// let cc = CacheControl::parse("private, max-age=0, no-store");
+/// assert!(cc.is_no_store());
+/// assert_eq!(cc.get_max_age(), Some(0));
+/// ```
+///
+/// It can be set to a response by `res.headers.set().CacheControl(cc)`,
+/// or as `typed::header::CacheControl(cc)` in `WithHeaders`.
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct CacheControl {
+    max_age:                Option<u64>,
+    s_maxage:               Option<u64>,
+    stale_while_revalidate: Option<u64>,
+    stale_if_error:         Option<u64>,
+    public:                 bool,
+    private:                bool,
+    no_cache:               bool,
+    no_store:               bool,
+    no_transform:           bool,
+    must_revalidate:        bool,
+    proxy_revalidate:       bool,
+    immutable:              bool,
+}
+
+impl CacheControl {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `max-age=<secs>`
+    pub fn max_age(mut self, secs: u64) -> Self {
+        self.max_age = Some(secs);
+        self
+    }
+    /// `s-maxage=<secs>`, the freshness lifetime for shared caches
+    pub fn s_maxage(mut self, secs: u64) -> Self {
+        self.s_maxage = Some(secs);
+        self
+    }
+    /// `stale-while-revalidate=<secs>`
+    pub fn stale_while_revalidate(mut self, secs: u64) -> Self {
+        self.stale_while_revalidate = Some(secs);
+        self
+    }
+    /// `stale-if-error=<secs>`
+    pub fn stale_if_error(mut self, secs: u64) -> Self {
+        self.stale_if_error = Some(secs);
+        self
+    }
+    pub fn public(mut self) -> Self {
+        self.public = true;
+        self
+    }
+    pub fn private(mut self) -> Self {
+        self.private = true;
+        self
+    }
+    pub fn no_cache(mut self) -> Self {
+        self.no_cache = true;
+        self
+    }
+    pub fn no_store(mut self) -> Self {
+        self.no_store = true;
+        self
+    }
+    pub fn no_transform(mut self) -> Self {
+        self.no_transform = true;
+        self
+    }
+    pub fn must_revalidate(mut self) -> Self {
+        self.must_revalidate = true;
+        self
+    }
+    pub fn proxy_revalidate(mut self) -> Self {
+        self.proxy_revalidate = true;
+        self
+    }
+    pub fn immutable(mut self) -> Self {
+        self.immutable = true;
+        self
+    }
+}
+
+impl CacheControl {
+    /// Parse a `Cache-Control` value. Unknown or invalid directives are ignored.
+    pub fn parse(raw: &str) -> Self {
+        let mut cc = Self::new();
+        for directive in raw.split(',') {
+            let (name, arg) = match directive.split_once('=') {
+                Some((name, arg)) => (name.trim(), Some(arg.trim().trim_matches('"'))),
+                None => (directive.trim(), None)
+            };
+            let secs = || arg.and_then(|a| a.parse::<u64>().ok());
+            match &*name.to_ascii_lowercase() {
+                "max-age"                => cc.max_age = secs(),
+                "s-maxage"               => cc.s_maxage = secs(),
+                "stale-while-revalidate" => cc.stale_while_revalidate = secs(),
+                "stale-if-error"         => cc.stale_if_error = secs(),
+                "public"                 => cc.public = true,
+                "private"                => cc.private = true,
+                "no-cache"               => cc.no_cache = true,
+                "no-store"               => cc.no_store = true,
+                "no-transform"           => cc.no_transform = true,
+                "must-revalidate"        => cc.must_revalidate = true,
+                "proxy-revalidate"       => cc.proxy_revalidate = true,
+                "immutable"              => cc.immutable = true,
+                _ => ()
+            }
+        }
+        cc
+    }
+
+    pub fn get_max_age(&self) -> Option<u64> {
+        self.max_age
+    }
+    pub fn get_s_maxage(&self) -> Option<u64> {
+        self.s_maxage
+    }
+    pub fn get_stale_while_revalidate(&self) -> Option<u64> {
+        self.stale_while_revalidate
+    }
+    pub fn get_stale_if_error(&self) -> Option<u64> {
+        self.stale_if_error
+    }
+    pub fn is_public(&self) -> bool {
+        self.public
+    }
+    pub fn is_private(&self) -> bool {
+        self.private
+    }
+    pub fn is_no_cache(&self) -> bool {
+        self.no_cache
+    }
+    pub fn is_no_store(&self) -> bool {
+        self.no_store
+    }
+    pub fn is_no_transform(&self) -> bool {
+        self.no_transform
+    }
+    pub fn is_must_revalidate(&self) -> bool {
+        self.must_revalidate
+    }
+    pub fn is_immutable(&self) -> bool {
+        self.immutable
+    }
+}
+
+impl std::fmt::Display for CacheControl {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let directives = [
+            self.public.then_some(Cow::Borrowed("public")),
+            self.private.then_some(Cow::Borrowed("private")),
+            self.max_age.map(|secs| Cow::Owned(format!("max-age={secs}"))),
+            self.s_maxage.map(|secs| Cow::Owned(format!("s-maxage={secs}"))),
+            self.no_cache.then_some(Cow::Borrowed("no-cache")),
+            self.no_store.then_some(Cow::Borrowed("no-store")),
+            self.no_transform.then_some(Cow::Borrowed("no-transform")),
+            self.must_revalidate.then_some(Cow::Borrowed("must-revalidate")),
+            self.proxy_revalidate.then_some(Cow::Borrowed("proxy-revalidate")),
+            self.immutable.then_some(Cow::Borrowed("immutable")),
+            self.stale_while_revalidate.map(|secs| Cow::Owned(format!("stale-while-revalidate={secs}"))),
+            self.stale_if_error.map(|secs| Cow::Owned(format!("stale-if-error={secs}"))),
+        ];
+        for (i, directive) in directives.iter().flatten().enumerate() {
+            if i > 0 {f.write_str(", ")?}
+            f.write_str(directive)?;
+        }
+        Ok(())
+    }
+}
+
+const _: () = {
+    use crate::response::{SetHeaders, Header, HeaderAction};
+
+    impl<'a> HeaderAction<'a> for CacheControl {
+        #[inline]
+        fn perform(self, set: SetHeaders<'a>, key: Header) -> SetHeaders<'a> {
+            self.to_string().perform(set, key)
+        }
+    }
+
+    impl crate::typed::header::IntoHeader for CacheControl {
+        fn into_header(self) -> Cow<'static, str> {
+            Cow::Owned(self.to_string())
+        }
+    }
+};
+
+
+#[cfg(test)]
+#[test] fn test_cache_control() {
+    assert_eq!(CacheControl::new().to_string(), "");
+    assert_eq!(CacheControl::new().no_store().to_string(), "no-store");
+    assert_eq!(
+        CacheControl::new().immutable().max_age(31536000).public().to_string(),
+        "public, max-age=31536000, immutable"
+    );
+    assert_eq!(
+        CacheControl::new().private().no_cache().s_maxage(0).max_age(10).to_string(),
+        "private, max-age=10, s-maxage=0, no-cache"
+    );
+
+    let cc = CacheControl::parse(r#"Public, max-age="120", s-maxage=60, unknown, stale-while-revalidate=x"#);
+    assert!(cc.is_public());
+    assert_eq!(cc.get_max_age(), Some(120));
+    assert_eq!(cc.get_s_maxage(), Some(60));
+    assert_eq!(cc.get_stale_while_revalidate(), None);
+    assert_eq!(CacheControl::parse(&cc.to_string()), cc);
+}
@@ -0,0 +1,325 @@
+/// content-coding ( <https://httpwg.org/specs/rfc9110.html#content.codings> )
+/// that ohkami knows
+#[cfg(any(feature="__rt__", feature="gzip", feature="br", feature="zstd"))]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum Encoding {
+    Gzip,
+    Deflate,
+    Br,
+    Zstd,
+}
+
+#[cfg(any(feature="__rt__", feature="gzip", feature="br", feature="zstd"))]
+impl Encoding {
+    /// all the encodings in the server's preference order
+    #[cfg(feature="__rt__")]
+    pub(crate) const ALL: &'static [Self] = &[
+        Self::Br,
+        Self::Zstd,
+        Self::Gzip,
+        Self::Deflate,
+    ];
+
+    /// encodings ohkami can encode with under the enabled features
+    /// ( `gzip`, `br`, `zstd` ), in the server's preference order
+    #[allow(unused)]
+    pub(crate) const AVAILABLE: &'static [Self] = &[
+        #[cfg(feature="br")]   Self::Br,
+        #[cfg(feature="zstd")] Self::Zstd,
+        #[cfg(feature="gzip")] Self::Gzip,
+        #[cfg(feature="gzip")] Self::Deflate,
+    ];
+
+    pub(crate) const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Gzip    => "gzip",
+            Self::Deflate => "deflate",
+            Self::Br      => "br",
+            Self::Zstd    => "zstd",
+        }
+    }
+
+    pub(crate) fn from_str(coding: &str) -> Option<Self> {
+        let coding = coding.trim();
+        if coding.eq_ignore_ascii_case("gzip") || coding.eq_ignore_ascii_case("x-gzip") {
+            Some(Self::Gzip)
+        } else if coding.eq_ignore_ascii_case("deflate") {
+            Some(Self::Deflate)
+        } else if coding.eq_ignore_ascii_case("br") {
+            Some(Self::Br)
+        } else if coding.eq_ignore_ascii_case("zstd") {
+            Some(Self::Zstd)
+        } else {
+            None
+        }
+    }
+
+    /// file extension ( without leading `.` ) conventionally used
+    /// for precompressed files of this encoding
+    #[cfg(feature="__rt__")]
+    pub(crate) const fn file_extension(&self) -> Option<&'static str> {
+        match self {
+            Self::Gzip    => Some("gz"),
+            Self::Br      => Some("br"),
+            Self::Zstd    => Some("zst"),
+            Self::Deflate => None,
+        }
+    }
+
+    #[allow(unused)]
+    pub(crate) fn encode(&self, bytes: &[u8]) -> std::io::Result<Vec<u8>> {
+        #[allow(unused_imports)]
+        use std::io::Write;
+
+        match self {
+            #[cfg(feature="gzip")]
+            Self::Gzip => {
+                let mut e = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+                e.write_all(bytes)?;
+                e.finish()
+            }
+            #[cfg(feature="gzip")]
+            Self::Deflate => {
+                /* `deflate` content-coding is actually the "zlib" format */
+                let mut e = flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+                e.write_all(bytes)?;
+                e.finish()
+            }
+            #[cfg(feature="br")]
+            Self::Br => {
+                let mut out = Vec::new();
+                {
+                    let mut e = brotli::CompressorWriter::new(&mut out, 4096, 9, 22);
+                    e.write_all(bytes)?;
+                    e.flush()?;
+                }
+                Ok(out)
+            }
+            #[cfg(feature="zstd")]
+            Self::Zstd => {
+                zstd::encode_all(bytes, zstd::DEFAULT_COMPRESSION_LEVEL)
+            }
+            #[allow(unreachable_patterns)]
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                format!("`{}` encoding is not enabled", self.as_str())
+            ))
+        }
+    }
+
+    /// Decode `bytes` encoded with this encoding, or `None` when
+    /// the decoded size exceeds `limit`
+    #[allow(unused)]
+    pub(crate) fn decode(&self, bytes: &[u8], limit: usize) -> std::io::Result<Option<Vec<u8>>> {
+        #[allow(unused_imports)]
+        use std::io::Read;
+
+        #[allow(unused)]
+        fn read_limited(decoder: impl Read, limit: usize) -> std::io::Result<Option<Vec<u8>>> {
+            let mut decoded = Vec::new();
+            decoder.take(limit as u64 + 1).read_to_end(&mut decoded)?;
+            Ok((decoded.len() <= limit).then_some(decoded))
+        }
+
+        match self {
+            #[cfg(feature="gzip")]
+            Self::Gzip => read_limited(flate2::read::MultiGzDecoder::new(bytes), limit),
+            #[cfg(feature="gzip")]
+            Self::Deflate => {
+                /* `deflate` content-coding is the "zlib" format, but some clients send raw deflate */
+                read_limited(flate2::read::ZlibDecoder::new(bytes), limit)
+                    .or_else(|_| read_limited(flate2::read::DeflateDecoder::new(bytes), limit))
+            }
+            #[cfg(feature="br")]
+            Self::Br => read_limited(brotli::Decompressor::new(bytes, 4096), limit),
+            #[cfg(feature="zstd")]
+            Self::Zstd => read_limited(zstd::stream::read::Decoder::new(bytes)?, limit),
+            #[allow(unreachable_patterns)]
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                format!("`{}` encoding is not enabled", self.as_str())
+            ))
+        }
+    }
+}
+
+/// Encoder compressing a body chunk by chunk, flushing on every chunk
+/// so that the receiver can decode each one without waiting for the rest
+#[cfg(all(feature="__rt__", any(feature="gzip", feature="br", feature="zstd")))]
+pub(crate) enum StreamEncoder {
+    #[cfg(feature="gzip")]
+    Gzip(flate2::write::GzEncoder<Vec<u8>>),
+    #[cfg(feature="gzip")]
+    Deflate(flate2::write::ZlibEncoder<Vec<u8>>),
+    #[cfg(feature="br")]
+    Br(Box<brotli::CompressorWriter<Vec<u8>>>),
+    #[cfg(feature="zstd")]
+    Zstd(zstd::stream::write::Encoder<'static, Vec<u8>>),
+}
+#[cfg(all(feature="__rt__", any(feature="gzip", feature="br", feature="zstd")))]
+impl StreamEncoder {
+    pub(crate) fn new(encoding: Encoding) -> std::io::Result<Self> {
+        match encoding {
+            #[cfg(feature="gzip")]
+            Encoding::Gzip => Ok(Self::Gzip(flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default()))),
+            #[cfg(feature="gzip")]
+            Encoding::Deflate => Ok(Self::Deflate(flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default()))),
+            #[cfg(feature="br")]
+            Encoding::Br => Ok(Self::Br(Box::new(brotli::CompressorWriter::new(Vec::new(), 4096, 5, 22)))),
+            #[cfg(feature="zstd")]
+            Encoding::Zstd => zstd::stream::write::Encoder::new(Vec::new(), zstd::DEFAULT_COMPRESSION_LEVEL).map(Self::Zstd),
+            #[allow(unreachable_patterns)]
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                format!("`{}` encoding is not enabled", encoding.as_str())
+            ))
+        }
+    }
+
+    /// compress `chunk` and return the output so far
+    pub(crate) fn encode(&mut self, chunk: &[u8]) -> std::io::Result<Vec<u8>> {
+        use std::io::Write;
+
+        macro_rules! encode {
+            ($e:expr) => {{
+                $e.write_all(chunk)?;
+                $e.flush()?;
+                Ok(std::mem::take($e.get_mut()))
+            }};
+        }
+        match self {
+            #[cfg(feature="gzip")] Self::Gzip(e)    => encode!(e),
+            #[cfg(feature="gzip")] Self::Deflate(e) => encode!(e),
+            #[cfg(feature="br")]   Self::Br(e)      => encode!(e),
+            #[cfg(feature="zstd")] Self::Zstd(e)    => encode!(e),
+        }
+    }
+
+    /// finish the compression and return the rest of the output
+    pub(crate) fn finish(self) -> std::io::Result<Vec<u8>> {
+        match self {
+            #[cfg(feature="gzip")] Self::Gzip(e)    => e.finish(),
+            #[cfg(feature="gzip")] Self::Deflate(e) => e.finish(),
+            #[cfg(feature="br")]   Self::Br(e)      => Ok(e.into_inner()),
+            #[cfg(feature="zstd")] Self::Zstd(e)    => e.finish(),
+        }
+    }
+}
+
+/// Select the most preferred one of `candidates` ( in the server's preference order )
+/// by `Accept-Encoding` ( <https://httpwg.org/specs/rfc9110.html#field.accept-encoding> ).
+///
+/// `None` means `identity` should be used: no `Accept-Encoding`, none of `candidates`
+/// is acceptable, or `identity` is explicitly preferred.
+#[cfg(feature="__rt__")]
+pub(crate) fn negotiate(
+    accept_encoding: Option<&str>,
+    candidates:      impl IntoIterator<Item = Encoding>,
+) -> Option<Encoding> {
+    let accept_encoding = accept_encoding?;
+
+    let entries = || accept_encoding.split(',').filter_map(|entry| {
+        let mut parts = entry.split(';');
+        let coding = parts.next()?.trim();
+        if coding.is_empty() {
+            return None
+        }
+        let q = parts
+            .find_map(|p| p.trim().strip_prefix("q=").or_else(|| p.trim().strip_prefix("Q=")))
+            .map(parse_qvalue)
+            .unwrap_or(Some(1000))?;
+        Some((coding, q))
+    });
+
+    let q_of = |coding: &str| entries()
+        .find(|(c, _)| c.eq_ignore_ascii_case(coding))
+        .map(|(_, q)| q);
+    let q_of_star = entries()
+        .find(|(c, _)| *c == "*")
+        .map(|(_, q)| q);
+
+    let mut selected: Option<(Encoding, u16)> = None;
+    for candidate in candidates {
+        let q = match entries().find(|(c, _)| Encoding::from_str(c) == Some(candidate)) {
+            Some((_, q)) => q,
+            None         => q_of_star.unwrap_or(0),
+        };
+        if q == 0 || selected.is_some_and(|(_, max)| max >= q) {
+            continue
+        }
+        selected = Some((candidate, q));
+    }
+
+    let (encoding, q) = selected?;
+    match q_of("identity") {
+        Some(identity_q) if identity_q > q => None,
+        _ => Some(encoding)
+    }
+}
+
+/// `qvalue` in thousandths
+pub(crate) fn parse_qvalue(q: &str) -> Option<u16> {
+    let q: f32 = q.trim().parse().ok()?;
+    (0.0..=1.0).contains(&q).then_some((q * 1000.) as u16)
+}
+
+#[cfg(feature="__rt__")]
+pub(crate) use ohkami_lib::mime::is_compressible;
+
+#[cfg(test)]
+#[cfg(all(feature="__rt__", any(feature="gzip", feature="br", feature="zstd")))]
+#[test] fn test_stream_encoder() {
+    for &encoding in Encoding::AVAILABLE {
+        let mut e = StreamEncoder::new(encoding).unwrap();
+        let mut stream = Vec::new();
+        for chunk in ["Hello, ", "world", "!"] {
+            stream.extend(e.encode(chunk.as_bytes()).unwrap());
+        }
+        stream.extend(e.finish().unwrap());
+
+        let decoded = match encoding {
+            #[cfg(feature="gzip")]
+            Encoding::Gzip => std::io::read_to_string(flate2::read::GzDecoder::new(&*stream)).unwrap(),
+            #[cfg(feature="gzip")]
+            Encoding::Deflate => std::io::read_to_string(flate2::read::ZlibDecoder::new(&*stream)).unwrap(),
+            #[cfg(feature="br")]
+            Encoding::Br => std::io::read_to_string(brotli::Decompressor::new(&*stream, 4096)).unwrap(),
+            #[cfg(feature="zstd")]
+            Encoding::Zstd => String::from_utf8(zstd::decode_all(&*stream).unwrap()).unwrap(),
+            #[allow(unreachable_patterns)]
+            _ => unreachable!()
+        };
+        assert_eq!(decoded, "Hello, world!", "{encoding:?}");
+    }
+}
+
+#[cfg(test)]
+#[cfg(any(feature="gzip", feature="br", feature="zstd"))]
+#[test] fn test_decode() {
+    let original = "Hello, world!".repeat(100);
+    for &encoding in Encoding::AVAILABLE {
+        let encoded = encoding.encode(original.as_bytes()).unwrap();
+        assert_eq!(encoding.decode(&encoded, 1300).unwrap().as_deref(), Some(original.as_bytes()), "{encoding:?}");
+        assert_eq!(encoding.decode(&encoded, 1299).unwrap(), None, "{encoding:?}");
+        assert!(encoding.decode(b"not encoded", 1300).is_err(), "{encoding:?}");
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature="__rt__")]
+#[test] fn test_negotiate_encoding() {
+    use Encoding::*;
+
+    assert_eq!(negotiate(None, [Br, Gzip]), None);
+    assert_eq!(negotiate(Some("gzip, deflate, br"), [Br, Gzip]), Some(Br));
+    assert_eq!(negotiate(Some("gzip, deflate"), [Br, Gzip]), Some(Gzip));
+    assert_eq!(negotiate(Some("gzip;q=0.5, br;q=0.8"), [Gzip, Br]), Some(Br));
+    assert_eq!(negotiate(Some("gzip, br;q=0"), [Br, Gzip]), Some(Gzip));
+    assert_eq!(negotiate(Some("*"), [Zstd, Gzip]), Some(Zstd));
+    assert_eq!(negotiate(Some("*;q=0.1, zstd;q=0"), [Zstd, Gzip]), Some(Gzip));
+    assert_eq!(negotiate(Some("identity"), [Br, Gzip]), None);
+    assert_eq!(negotiate(Some("identity, gzip;q=0.5"), [Gzip]), None);
+    assert_eq!(negotiate(Some("x-gzip"), [Gzip]), Some(Gzip));
+    assert_eq!(negotiate(Some("gzip;q=2"), [Gzip]), None);
+    assert_eq!(negotiate(Some("br"), []), None);
+}
@@ -0,0 +1,67 @@
+/// Iterate entity-tags in a list like `If-None-Match` or `If-Match`,
+/// yielding `(is_weak, opaque_tag)` where `opaque_tag` includes the double quotes.
+/// 
+/// `*` is yielded as `(false, "*")`.
+fn iter_entity_tags(list: &str) -> impl Iterator<Item = (bool, &str)> {
+    let mut rest = list;
+    std::iter::from_fn(move || {
+        rest = rest.trim_start_matches(|c: char| c == ',' || c.is_ascii_whitespace());
+        if rest.is_empty() {
+            return None
+        }
+        if let Some(after) = rest.strip_prefix('*') {
+            rest = after;
+            return Some((false, "*"))
+        }
+        let (is_weak, tag) = match rest.strip_prefix("W/") {
+            Some(tag) => (true, tag),
+            None      => (false, rest),
+        };
+        if !tag.starts_with('"') {
+            /* invalid; skip to the next element */
+            rest = tag.split_once(',').map(|(_, r)| r).unwrap_or("");
+            return Some((is_weak, ""))
+        }
+        let end = tag[1..].find('"').map(|i| i + 2).unwrap_or(tag.len());
+        rest = &tag[end..];
+        Some((is_weak, &tag[..end]))
+    }).filter(|(_, tag)| !tag.is_empty())
+}
+
+/// `(is_weak, opaque_tag)` of an entity-tag like `"xyz"` or `W/"xyz"`
+fn split_weak(etag: &str) -> (bool, &str) {
+    match etag.strip_prefix("W/") {
+        Some(tag) => (true, tag),
+        None      => (false, etag),
+    }
+}
+
+/// whether an entity-tag list ( or `*` ) matches `etag` by **weak comparison**,
+/// used for `If-None-Match`
+pub(crate) fn matches_weak(list: &str, etag: &str) -> bool {
+    let (_, opaque) = split_weak(etag);
+    iter_entity_tags(list).any(|(_, tag)| tag == "*" || tag == opaque)
+}
+
+/// whether an entity-tag list ( or `*` ) matches `etag` by **strong comparison**,
+/// used for `If-Match` and `If-Range`
+pub(crate) fn matches_strong(list: &str, etag: &str) -> bool {
+    let (is_weak, opaque) = split_weak(etag);
+    iter_entity_tags(list).any(|(w, tag)| tag == "*" || (!w && !is_weak && tag == opaque))
+}
+
+#[cfg(test)]
+#[test] fn test_etag_matching() {
+    assert!(matches_weak(r#""abc""#, r#""abc""#));
+    assert!(matches_weak(r#"W/"abc""#, r#""abc""#));
+    assert!(matches_weak(r#""xyz", W/"abc""#, r#"W/"abc""#));
+    assert!(matches_weak("*", r#""abc""#));
+    assert!(!matches_weak(r#""xyz", "a,bc""#, r#""abc""#));
+    assert!(matches_weak(r#""xyz", "a,bc""#, r#""a,bc""#));
+    assert!(!matches_weak(r#"abc, "xyz""#, r#""abc""#));
+
+    assert!(matches_strong(r#""abc""#, r#""abc""#));
+    assert!(!matches_strong(r#"W/"abc""#, r#""abc""#));
+    assert!(!matches_strong(r#""abc""#, r#"W/"abc""#));
+    assert!(matches_strong(r#""xyz" , "abc""#, r#""abc""#));
+}
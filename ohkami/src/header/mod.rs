@@ -4,8 +4,16 @@ mod append;
 pub use append::append;
 pub(crate) use append::Append;
 
+mod cache_control;
+pub use cache_control::CacheControl;
+
 mod setcookie;
 pub(crate) use setcookie::*;
 
 mod map;
 pub(crate) use map::IndexMap;
+
+pub(crate) mod etag;
+#[cfg(feature="__rt__")]
+pub(crate) mod range;
+pub(crate) mod encoding;
@@ -0,0 +1,92 @@
+use std::ops::Range;
+
+/// `Range` header ( <https://httpwg.org/specs/rfc9110.html#field.range> )
+/// interpreted for a representation of `len` bytes
+#[derive(Debug, PartialEq)]
+pub(crate) enum ByteRanges {
+    /// ranges of `0 < len` bytes, in the requested order
+    Satisfiable(Vec<Range<u64>>),
+    /// should be answered by `416 Range Not Satisfiable`
+    Unsatisfiable,
+}
+
+impl ByteRanges {
+    /// at most this number of ranges are accepted in one request
+    /// ( otherwise the `Range` is ignored ) to prevent abuse
+    const LIMIT: usize = 16;
+
+    /// `None` when the `Range` should be ignored: unknown unit, invalid syntax or too many ranges
+    pub(crate) fn parse(range: &str, len: u64) -> Option<Self> {
+        let (unit, ranges) = range.trim().split_once('=')?;
+        if !unit.trim().eq_ignore_ascii_case("bytes") {
+            return None
+        }
+
+        let mut satisfiable = Vec::new();
+        for (i, spec) in ranges.split(',').map(str::trim).filter(|s| !s.is_empty()).enumerate() {
+            if i >= Self::LIMIT {
+                return None
+            }
+
+            let (first, last) = spec.split_once('-')?;
+            let (first, last) = (first.trim(), last.trim());
+            let range = if first.is_empty() {
+                /* suffix-range */
+                let suffix_len: u64 = last.parse().ok()?;
+                len.saturating_sub(suffix_len)..len
+            } else {
+                let first: u64 = first.parse().ok()?;
+                let last = match last {
+                    "" => len.saturating_sub(1),
+                    _  => last.parse::<u64>().ok()?.min(len.saturating_sub(1)),
+                };
+                if last < first && first < len {
+                    /* syntactically invalid */
+                    return None
+                }
+                first..(last + 1)
+            };
+
+            if range.start < len && range.start < range.end {
+                satisfiable.push(range);
+            }
+        }
+
+        Some(if satisfiable.is_empty() {
+            Self::Unsatisfiable
+        } else {
+            Self::Satisfiable(satisfiable)
+        })
+    }
+}
+
+/// `Content-Range` value for `range` of a representation of `len` bytes
+pub(crate) fn content_range(range: &Range<u64>, len: u64) -> String {
+    format!("bytes {}-{}/{len}", range.start, range.end - 1)
+}
+
+#[cfg(test)]
+#[allow(clippy::single_range_in_vec_init)]
+#[test] fn test_parse_byte_ranges() {
+    use ByteRanges::*;
+
+    assert_eq!(ByteRanges::parse("bytes=0-499", 10000), Some(Satisfiable(vec![0..500])));
+    assert_eq!(ByteRanges::parse("bytes=500-999", 10000), Some(Satisfiable(vec![500..1000])));
+    assert_eq!(ByteRanges::parse("bytes=-500", 10000), Some(Satisfiable(vec![9500..10000])));
+    assert_eq!(ByteRanges::parse("bytes=9500-", 10000), Some(Satisfiable(vec![9500..10000])));
+    assert_eq!(ByteRanges::parse("bytes=0-0,-1", 10000), Some(Satisfiable(vec![0..1, 9999..10000])));
+    assert_eq!(ByteRanges::parse("bytes= 0-999, 4500-5499, -1000", 10000), Some(Satisfiable(vec![0..1000, 4500..5500, 9000..10000])));
+    assert_eq!(ByteRanges::parse("bytes=0-99999", 10000), Some(Satisfiable(vec![0..10000])));
+    assert_eq!(ByteRanges::parse("bytes=-99999", 10000), Some(Satisfiable(vec![0..10000])));
+
+    assert_eq!(ByteRanges::parse("bytes=10000-", 10000), Some(Unsatisfiable));
+    assert_eq!(ByteRanges::parse("bytes=20000-30000", 10000), Some(Unsatisfiable));
+    assert_eq!(ByteRanges::parse("bytes=-0", 10000), Some(Unsatisfiable));
+    assert_eq!(ByteRanges::parse("bytes=0-", 0), Some(Unsatisfiable));
+
+    assert_eq!(ByteRanges::parse("items=0-5", 10000), None);
+    assert_eq!(ByteRanges::parse("bytes=5-0", 10000), None);
+    assert_eq!(ByteRanges::parse("bytes=a-b", 10000), None);
+    assert_eq!(ByteRanges::parse("bytes=0", 10000), None);
+    assert_eq!(ByteRanges::parse(&format!("bytes={}", ["0-0"; 17].join(",")), 10000), None);
+}
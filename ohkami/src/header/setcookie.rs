@@ -27,7 +27,7 @@ impl SameSitePolicy {
 
 #[derive(Debug, PartialEq)]
 pub struct SetCookie<'c> {
-    pub(crate) Cookie:   (&'c str, Cow<'c, str>),
+    pub(crate) Cookie:   (Cow<'c, str>, Cow<'c, str>),
     pub(crate) Expires:  Option<Cow<'c, str>>,
     pub(crate) MaxAge:   Option<u64>,
     pub(crate) Domain:   Option<Cow<'c, str>>,
@@ -83,7 +83,7 @@ impl<'c> SetCookie<'c> {
             }).map_err(|e| format!("Invalid Cookie value: {e}"))?;
 
             Self {
-                Cookie: (name, value),
+                Cookie: (Cow::Borrowed(name), value),
                 Expires:  None,
                 MaxAge:   None,
                 Domain:   None,
@@ -137,13 +137,21 @@ impl<'c> SetCookie<'c> {
 pub struct SetCookieBuilder(SetCookie<'static>);
 impl SetCookieBuilder {
     #[inline]
-    pub(crate) fn new(cookie_name: &'static str, cookie_value: impl Into<Cow<'static, str>>) -> Self {
+    pub(crate) fn new(cookie_name: impl Into<Cow<'static, str>>, cookie_value: impl Into<Cow<'static, str>>) -> Self {
         Self(SetCookie {
-            Cookie: (cookie_name, cookie_value.into()),
+            Cookie: (cookie_name.into(), cookie_value.into()),
             Expires: None, MaxAge: None, Domain: None, Path: None, Secure: None, HttpOnly: None, SameSite: None,
         })
     }
-    pub(crate) fn build(self) -> String {
+    /// Fails if the Cookie name is not a valid one, to prevent
+    /// injecting `;`, `=` or CR/LF into the `Set-Cookie` header.
+    pub(crate) fn build(self) -> Result<String, ohkami_lib::serde_cookie::Error> {
+        if !ohkami_lib::serde_cookie::is_valid_name(&self.0.Cookie.0) {
+            return Err(serde::ser::Error::custom(format!(
+                "invalid Cookie name: `{}`", self.0.Cookie.0.escape_debug()
+            )))
+        }
+
         let mut bytes = Vec::new();
 
         let (name, value) = self.0.Cookie; {
@@ -178,9 +186,9 @@ impl SetCookieBuilder {
             bytes.extend_from_slice(SameSite.as_str().as_bytes());
         }
 
-        unsafe {// SAFETY: All fields and punctuaters is UTF-8
+        Ok(unsafe {// SAFETY: All fields and punctuaters is UTF-8
             String::from_utf8_unchecked(bytes)
-        }
+        })
     }
 
     #[inline]
@@ -188,6 +196,14 @@ impl SetCookieBuilder {
         self.0.Expires = Some(Expires.into());
         self
     }
+    /// `Expires` of `duration` later than now
+    pub fn ExpiresIn(self, duration: std::time::Duration) -> Self {
+        self.ExpiresAt(crate::util::unix_timestamp() + duration.as_secs())
+    }
+    /// `Expires` at the unix timestamp
+    pub fn ExpiresAt(self, unix_timestamp: u64) -> Self {
+        self.Expires(ohkami_lib::time::imf_fixdate(unix_timestamp))
+    }
     #[inline]
     pub const fn MaxAge(mut self, MaxAge: u64) -> Self {
         self.0.MaxAge = Some(MaxAge);
@@ -229,3 +245,13 @@ impl SetCookieBuilder {
         self
     }
 }
+
+
+#[cfg(test)]
+#[test] fn test_setcookie_builder_name() {
+    assert_eq!(SetCookieBuilder::new("id", "a;b").Path("/").build().unwrap(), "id=a%3Bb; Path=/");
+
+    for name in ["a;b", "a=b", "a\r\nSet-Cookie: x", "a b", "狼"] {
+        assert!(SetCookieBuilder::new(name, "1").build().is_err(), "{name:?}");
+    }
+}
@@ -69,23 +69,68 @@ mod __rt__ {
     pub(crate) use nio::time::sleep;
     #[cfg(feature="rt_glommio")]
     pub(crate) fn sleep(duration: std::time::Duration) -> impl std::future::Future<Output = ()> + Send {
-        return SendFuture(glommio::timer::sleep(duration));
-
-        ///////////////////////////////////////////////////////////
+        SendFuture(glommio::timer::sleep(duration))
+    }
 
+    #[cfg(feature="rt_glommio")]
+    struct SendFuture<F>(F);
+    #[cfg(feature="rt_glommio")]
+    const _: () = {
         use std::{future::Future, pin::Pin, task::{Context, Poll}};
 
-        struct SendFuture<F>(F);
-
-        // SAFETY: sleep is executed on the same thread in glommio
+        // SAFETY: tasks are executed on the same thread in glommio
         unsafe impl<F> Send for SendFuture<F> {}
 
-        impl<F: Future<Output = ()>> Future for SendFuture<F> {
-            type Output = ();
+        impl<F: Future> Future for SendFuture<F> {
+            type Output = F::Output;
             fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
                 unsafe {self.map_unchecked_mut(|this| &mut this.0)}.poll(cx)
             }
         }
+    };
+
+    /// Run blocking `f` ( like `std::fs` operations ) off the executor
+    pub(crate) async fn unblock<T: Send + 'static>(f: impl FnOnce() -> T + Send + 'static) -> T {
+        #[cfg(feature="rt_tokio")]
+        return match tokio::task::spawn_blocking(f).await {
+            Ok(t)  => t,
+            Err(e) => std::panic::resume_unwind(e.into_panic()),
+        };
+
+        #[cfg(feature="rt_async-std")]
+        return async_std::task::spawn_blocking(f).await;
+
+        #[cfg(feature="rt_smol")]
+        return smol::unblock(f).await;
+
+        #[cfg(feature="rt_nio")]
+        return {
+            /* on a dedicated thread, waking the task when done */
+            use std::sync::{Arc, Mutex};
+            use std::task::{Poll, Waker};
+
+            let state = Arc::new(Mutex::new((None::<std::thread::Result<T>>, None::<Waker>)));
+            std::thread::spawn({
+                let state = Arc::clone(&state);
+                move || {
+                    let t = std::panic::catch_unwind(std::panic::AssertUnwindSafe(f));
+                    let mut state = state.lock().unwrap();
+                    state.0 = Some(t);
+                    if let Some(waker) = state.1.take() {waker.wake()}
+                }
+            });
+            std::future::poll_fn(|cx| {
+                let mut state = state.lock().unwrap();
+                match state.0.take() {
+                    Some(Ok(t))  => Poll::Ready(t),
+                    Some(Err(e)) => std::panic::resume_unwind(e),
+                    None => {state.1 = Some(cx.waker().clone()); Poll::Pending}
+                }
+            }).await
+        };
+
+        #[cfg(feature="rt_glommio")]
+        return SendFuture(glommio::executor().spawn_blocking(f)).await;
     }
 
     #[cfg(feature="rt_tokio")]
@@ -184,11 +229,13 @@ pub(crate) static CONFIG: config::Config = config::Config::new();
 pub mod testing;
 
 mod request;
-pub use request::{Request, Method, FromRequest, FromParam, FromBody};
+pub use request::{Request, Method, FromRequest, FromParam, FromBody, Rejection};
 pub use ::ohkami_macros::FromRequest;
 
 mod response;
-pub use response::{Response, Status, IntoResponse, IntoBody};
+pub use response::{Response, Status, IntoResponse, IntoBody, Trailers};
+#[cfg(feature="__rt_native__")]
+pub use response::NamedFile;
 
 #[cfg(feature="__rt_native__")]
 mod session;
@@ -201,7 +248,9 @@ mod router;
 #[cfg(feature="__rt__")]
 mod ohkami;
 #[cfg(feature="__rt__")]
-pub use ohkami::{Ohkami, Route};
+pub use ohkami::{Ohkami, Route, RouteInfo, Versioned, Version};
+#[cfg(feature="__rt__")]
+pub use ::ohkami_macros::embed_dir;
 
 pub mod fang;
 pub use fang::{handler, Fang, FangProc};
@@ -343,6 +392,9 @@ pub mod __internal__ {
 
     pub use crate::fang::Fangs;
 
+    #[cfg(feature="__rt__")]
+    pub use crate::ohkami::{EmbeddedDir, EmbeddedFile};
+
     /* for benchmarks */
     #[cfg(feature="DEBUG")]
     pub use crate::{
@@ -747,6 +747,22 @@ fn method_dependent_fang_applying() {
             let res = t.oneshot(req).await;
             assert_eq!(res.status(), Status::NotFound);
         }
+
+        /* with a param sibling; MUST NOT be taken by the prefix */
+
+        let t = Ohkami::new((
+            "/abc".GET(|| async {"This is abc"}),
+            "/:name".GET(|name: String| async move {format!("This is {name}")}),
+        )).test(); {
+            let req = TestRequest::GET("/abc");
+            let res = t.oneshot(req).await;
+            assert_eq!(res.text(), Some("This is abc"));
+        } {
+            let req = TestRequest::GET("/abcd");
+            let res = t.oneshot(req).await;
+            assert_eq!(res.status(), Status::OK);
+            assert_eq!(res.text(), Some("This is abcd"));
+        }
     });
 }
 
@@ -792,3 +808,162 @@ fn check_path_params_counted_accumulatedly() {
         ))),
     )).test(); /* panics here */
 }
+
+#[test] fn test_routes_introspection() {
+    #[derive(Clone)] struct Logger;
+    impl FangAction for Logger {}
+    #[derive(Clone)] struct Auth;
+    impl FangAction for Auth {}
+
+    async fn list_users() -> &'static str {"list_users"}
+    async fn create_user() -> &'static str {"create_user"}
+    async fn get_user(_id: usize) -> &'static str {"get_user"}
+    async fn health() -> &'static str {"health"}
+
+    let o = Ohkami::new((Logger,
+        "/health".GET(health),
+        "/api".By(Ohkami::new((Auth,
+            "/users"
+                .GET(list_users)
+                .POST((Auth, create_user)),
+            "/users/:id"
+                .GET(get_user),
+        ))),
+    ));
+
+    let routes = o.routes();
+    assert_eq!(
+        routes.iter()
+            .map(|r| (r.method, &*r.path, r.handler))
+            .collect::<Vec<_>>(),
+        [
+            (Method::GET,  "/api/users", std::any::type_name_of_val(&list_users)),
+            (Method::POST, "/api/users", std::any::type_name_of_val(&create_user)),
+            (Method::GET,  "/api/users/:id", std::any::type_name_of_val(&get_user)),
+            (Method::GET,  "/health", std::any::type_name_of_val(&health)),
+        ]
+    );
+
+    let [list_users, create_user, get_user, health] = &*routes else {panic!()};
+    assert_eq!(list_users.fangs, [
+        std::any::type_name::<Logger>(),
+        std::any::type_name::<Auth>(),
+    ]);
+    assert_eq!(create_user.fangs, [
+        std::any::type_name::<Logger>(),
+        std::any::type_name::<Auth>(),
+        std::any::type_name::<Auth>(),
+    ]);
+    assert!(get_user.has_fang::<Auth>());
+    assert!(!health.has_fang::<Auth>());
+    assert!(health.has_fang::<Logger>());
+
+    #[cfg(feature="openapi")] {
+        assert_eq!(list_users.operation_id, Some("list_users"));
+        assert_eq!(create_user.operation_id, Some("create_user"));
+    }
+
+    let table = super::routing::RouteTable(&routes).to_string();
+    assert_eq!(table, "\
+        METHOD  PATH            HANDLER      FANGS\n\
+        GET     /api/users      list_users   Logger, Auth\n\
+        POST    /api/users      create_user  Logger, Auth, Auth\n\
+        GET     /api/users/:id  get_user     Logger, Auth\n\
+        GET     /health         health       Logger\n\
+    ");
+}
+
+#[test] fn test_route_level_fangs() {
+    #[derive(Clone)] struct Logger;
+    impl FangAction for Logger {
+        async fn back<'a>(&'a self, res: &'a mut Response) {
+            res.headers.set().x("X-Trace", append("Logger"));
+        }
+    }
+    #[derive(Clone)] struct Auth;
+    impl FangAction for Auth {
+        async fn fore<'a>(&'a self, req: &'a mut Request) -> Result<(), Response> {
+            match req.headers.Authorization() {
+                Some("Bearer ohkami") => Ok(()),
+                _ => Err(Response::Unauthorized())
+            }
+        }
+        async fn back<'a>(&'a self, res: &'a mut Response) {
+            res.headers.set().x("X-Trace", append("Auth"));
+        }
+    }
+    #[derive(Clone)] struct Audit;
+    impl FangAction for Audit {
+        async fn back<'a>(&'a self, res: &'a mut Response) {
+            res.headers.set().x("X-Trace", append("Audit"));
+        }
+    }
+
+    use crate::header::append;
+
+    let o = Ohkami::new((Logger,
+        "/items"
+            .GET(|| async {"list"})
+            .POST((Audit, || async {"create"}))
+            .with_fangs(Auth),
+        "/public"
+            .GET(|| async {"public"}),
+    ));
+
+    let routes = o.routes();
+    assert_eq!(
+        routes.iter().map(|r| (r.method, &*r.path, r.fangs.clone())).collect::<Vec<_>>(),
+        [
+            (Method::GET,  "/items", vec![
+                std::any::type_name::<Logger>(),
+                std::any::type_name::<Auth>(),
+            ]),
+            (Method::POST, "/items", vec![
+                std::any::type_name::<Logger>(),
+                std::any::type_name::<Auth>(),
+                std::any::type_name::<Audit>(),
+            ]),
+            (Method::GET,  "/public", vec![
+                std::any::type_name::<Logger>(),
+            ]),
+        ]
+    );
+
+    let t = o.test();
+    crate::__rt__::testing::block_on(async {
+        {
+            let req = TestRequest::GET("/items");
+            let res = t.oneshot(req).await;
+            assert_eq!(res.status(), Status::Unauthorized);
+            assert_eq!(res.header("X-Trace"), Some("Logger"));
+        }
+        {
+            let req = TestRequest::GET("/items")
+                .header("Authorization", "Bearer ohkami");
+            let res = t.oneshot(req).await;
+            assert_eq!(res.status(), Status::OK);
+            assert_eq!(res.text(), Some("list"));
+            assert_eq!(res.header("X-Trace"), Some("Auth, Logger"));
+        }
+        {
+            let req = TestRequest::POST("/items")
+                .header("Authorization", "Bearer ohkami");
+            let res = t.oneshot(req).await;
+            assert_eq!(res.status(), Status::OK);
+            assert_eq!(res.text(), Some("create"));
+            assert_eq!(res.header("X-Trace"), Some("Audit, Auth, Logger"));
+        }
+        {
+            let req = TestRequest::GET("/public");
+            let res = t.oneshot(req).await;
+            assert_eq!(res.status(), Status::OK);
+            assert_eq!(res.header("X-Trace"), Some("Logger"));
+        }
+        {
+            /* auto-generated `OPTIONS` is not affected */
+            let req = TestRequest::OPTIONS("/items");
+            let res = t.oneshot(req).await;
+            assert_ne!(res.status(), Status::Unauthorized);
+        }
+    });
+}
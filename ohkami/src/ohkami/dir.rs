@@ -0,0 +1,1932 @@
+#![allow(non_snake_case)]
+
+use super::routing::{HandlerSet, RoutingItem};
+use crate::router::{base::Router, segments::RouteSegments};
+use crate::fang::handler::{Handler, IntoHandler};
+use crate::header::{etag, range::ByteRanges, encoding::{Encoding, negotiate}};
+use crate::{Request, Response, Status};
+use crate::typed::reject;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use std::path::PathBuf;
+
+/// Static files served by `Route::Dir`, from a directory on disk or
+/// embedded into the binary by `ohkami::embed_dir!`.
+///
+/// Precompressed siblings of a file ( `app.js.br`, `app.js.zst`, `app.js.gz`
+/// for `app.js` ) are not routed by themselves, but served instead of the
+/// original to clients accepting the encoding, with `Content-Encoding` and
+/// `Vary: Accept-Encoding`.
+pub struct Dir {
+    pub(crate) route: &'static str,
+    pub(crate) files: Vec<(
+        Vec<String>,
+        DirFile,
+    )>,
+
+    /// The directory on disk, `None` for embedded files
+    pub(crate) root: Option<PathBuf>,
+
+    /*=== config ===*/
+
+    /// File extensions (leading `.` trimmed) that should not be appeared in handling path
+    pub(crate) omit_extensions: Option<Box<[&'static str]>>,
+
+    /// `Cache-Control` of every response
+    pub(crate) cache_control: Option<Cow<'static, str>>,
+
+    /// Read files from disk on each request instead of preloading them
+    pub(crate) stream_from_disk: bool,
+
+    /// Compress compressible files at load time when they have no precompressed variant
+    pub(crate) compress: bool,
+
+    /// MIME types by file extension (leading `.` trimmed, lowercased), preferred to the builtin ones
+    pub(crate) mime_overrides: Vec<(String, &'static str)>,
+
+    /// File names served for their directory, in priority order
+    pub(crate) index_files: Vec<&'static str>,
+
+    /// File (relative to the directory) served for unmatched paths under the route
+    pub(crate) spa_fallback: Option<&'static str>,
+
+    /// Serve listings of directories that have no index file
+    pub(crate) listing: bool,
+
+    /// Look up the files on disk on each request instead of routing them at startup
+    pub(crate) dev: bool,
+
+    /// Notify served HTML pages of changes in the directory to reload them
+    #[cfg(all(feature="sse", feature="__rt_native__"))]
+    pub(crate) live_reload: bool,
+}
+impl Dir {
+    pub(super) fn new(route: &'static str, dir_path: PathBuf) -> std::io::Result<Self> {
+        let dir_path = dir_path.canonicalize()?;
+
+        if !dir_path.is_dir() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("{} is not directory", dir_path.display()))
+            )
+        }
+
+        let mut files = Vec::new(); {
+            fn fetch_entries(
+                dir: PathBuf
+            ) -> std::io::Result<Vec<PathBuf>> {
+                dir.read_dir()?
+                    .map(|de| de.map(|de| de.path()))
+                    .collect()
+            }
+
+            let mut entries = fetch_entries(dir_path.clone())?;
+            while let Some(entry) = entries.pop() {
+                if entry.is_file() {
+                    let entry = entry.canonicalize()?;
+
+                    let path_Segments = entry
+                        .components()
+                        .skip(dir_path.components().count())
+                        .map(|c| c.as_os_str().to_os_string()
+                            .into_string()
+                            .map_err(|os_string| std::io::Error::new(
+                                std::io::ErrorKind::InvalidData,
+                                format!("Can't read a path segment `{}`", os_string.as_encoded_bytes().escape_ascii())
+                            ))
+                        )
+                        .collect::<std::io::Result<Vec<_>>>()?;
+
+                    if path_Segments.last().unwrap().starts_with('.') {
+                        crate::warning!("\
+                            =========\n\
+                            [WARNING] `Route::Dir`: found `{}` in directory `{}`, \
+                            are you sure to serve this file？\n\
+                            =========\n",
+                            entry.display(),
+                            dir_path.display(),
+                        )
+                    }
+
+                    files.push((
+                        path_Segments,
+                        DirFile::Path(entry)
+                    ));
+
+                } else if entry.is_dir() {
+                    entries.append(&mut fetch_entries(entry)?)
+
+                } else {
+                    continue
+                }
+            }
+        }
+
+        Ok(Self { root: Some(dir_path), ..Self::with_files(route, files) })
+    }
+
+    pub(super) fn embedded(route: &'static str, dir: EmbeddedDir) -> Self {
+        let files = dir.files.iter()
+            .map(|file| (
+                file.path.split('/').map(String::from).collect(),
+                DirFile::Embedded(file)
+            ))
+            .collect();
+
+        Self::with_files(route, files)
+    }
+
+    fn with_files(route: &'static str, files: Vec<(Vec<String>, DirFile)>) -> Self {
+        Self {
+            route,
+            files,
+            root: None,
+
+            omit_extensions: None,
+            cache_control:   None,
+            stream_from_disk: false,
+            compress:         false,
+            mime_overrides:   Vec::new(),
+            index_files:      vec!["index.html"],
+            spa_fallback:     None,
+            listing:          false,
+            dev:              false,
+            #[cfg(all(feature="sse", feature="__rt_native__"))]
+            live_reload:      false,
+        }
+    }
+
+    pub fn omit_extensions<const N: usize>(mut self, target_extensions: [&'static str; N]) -> Self {
+        self.omit_extensions = Some(Box::new(
+            target_extensions.map(|ext| ext.trim_start_matches('.'))
+        ));
+        self
+    }
+
+    /// Set file names to be served for their directory, in priority order.
+    /// Defaults to `["index.html"]`.
+    ///
+    /// For example, with `"/docs".Dir("./docs").index_files(["index.html", "index.htm"])`,
+    /// `GET /docs/guide` ( or `/docs/guide/` ) is answered by `./docs/guide/index.html`,
+    /// or `./docs/guide/index.htm` if the former doesn't exist.
+    pub fn index_files<const N: usize>(mut self, file_names: [&'static str; N]) -> Self {
+        self.index_files = file_names.into();
+        self
+    }
+
+    /// Serve `file` ( relative to the directory ) for `GET` requests to
+    /// any paths under the route that don't match the other files,
+    /// typically `index.html` of a single-page application.
+    ///
+    /// ```no_run
+    /// use ohkami::prelude::*;
+    ///
+    /// # let _ =
+    /// Ohkami::new((
+    ///     "/api/hello".GET(|| async {"Hello!"}),
+    ///     // `GET /app/settings` is answered by `./dist/index.html`
+    ///     "/app".Dir("./dist").spa_fallback("index.html"),
+    /// ))
+    /// # ;
+    /// ```
+    pub fn spa_fallback(mut self, file: &'static str) -> Self {
+        self.spa_fallback = Some(file);
+        self
+    }
+
+    /// Serve a listing of entries for directories that have no index file,
+    /// as HTML, or as JSON when `Accept` prefers `application/json`.
+    ///
+    /// Entries whose name starts with `.` are not listed.
+    pub fn listing(mut self) -> Self {
+        self.listing = true;
+        self
+    }
+
+    /// Use `mime` as `Content-Type` of files with `extension`,
+    /// instead of the builtin one.
+    ///
+    /// MIME type of a file is determined by
+    ///
+    /// 1. overrides by this method
+    /// 2. builtin table by extension ( `ohkami::util::mime::get_by_extension` )
+    /// 3. sniffing the content ( like `image/png` by its signature )
+    /// 4. `application/octet-stream`
+    ///
+    /// in this order.
+    ///
+    /// ```no_run
+    /// use ohkami::prelude::*;
+    ///
+    /// # let _ =
+    /// Ohkami::new((
+    ///     "/".Dir("./public")
+    ///         .mime("foo", "application/x-foo")
+    ///         .mime(".ts", "text/typescript"),
+    /// ))
+    /// # ;
+    /// ```
+    pub fn mime(mut self, extension: &str, mime: &'static str) -> Self {
+        let extension = extension.trim_start_matches('.').to_ascii_lowercase();
+        self.mime_overrides.retain(|(ext, _)| *ext != extension);
+        self.mime_overrides.push((extension, mime));
+        self
+    }
+
+    /// Set `Cache-Control` of responses for the files.
+    ///
+    /// ```no_run
+    /// use ohkami::prelude::*;
+    ///
+    /// # let _ =
+    /// Ohkami::new((
+    ///     "/assets".Dir("./assets")
+    ///         .cache_control("public, max-age=31536000, immutable"),
+    /// ))
+    /// # ;
+    /// ```
+    pub fn cache_control(mut self, cache_control: impl Into<Cow<'static, str>>) -> Self {
+        self.cache_control = Some(cache_control.into());
+        self
+    }
+
+    /// Don't preload the files into memory at startup, but read
+    /// ( only the requested ranges of ) them from disk on each request,
+    /// in chunks off the executor like [`NamedFile`](crate::NamedFile).
+    ///
+    /// This is useful for large files like videos. Modifications of
+    /// the files after startup are reflected in the responses
+    /// ( including `ETag` and `Last-Modified` ), while files added
+    /// or removed after startup are not.
+    pub fn stream_from_disk(mut self) -> Self {
+        self.stream_from_disk = true;
+        self
+    }
+
+    /// Compress files of compressible MIME types ( like `text/*` or
+    /// `application/json` ) once at load time with every encoding
+    /// enabled by features ( `br`, `zstd`, `gzip` ), and serve them
+    /// according to `Accept-Encoding`.
+    ///
+    /// Files that have precompressed variants are not compressed, and
+    /// this has no effect with `.stream_from_disk()`.
+    #[cfg(any(feature="gzip", feature="br", feature="zstd"))]
+    pub fn compress(mut self) -> Self {
+        self.compress = true;
+        self
+    }
+
+    /// Development mode: look up the requested file on disk on each
+    /// request instead of routing the files at startup, so that
+    /// modifications, additions and removals of files are reflected
+    /// without restarting the server.
+    ///
+    /// The other options work as usual except `.compress()` and
+    /// `.stream_from_disk()`, which have no effect.
+    ///
+    /// This is not available for embedded files. In this mode the route is
+    /// resolved against the whole request path, so mount the `Dir` on the
+    /// top-level `Ohkami` rather than on one nested by `By`.
+    ///
+    /// ```no_run
+    /// use ohkami::prelude::*;
+    ///
+    /// # let _ =
+    /// Ohkami::new((
+    ///     "/".Dir("./public")
+    ///         .dev(cfg!(debug_assertions)),
+    /// ))
+    /// # ;
+    /// ```
+    pub fn dev(mut self, yes: bool) -> Self {
+        self.dev = yes;
+        self
+    }
+
+    /// With `.dev(true)`, inject a script into served HTML pages to
+    /// reload them when any file in the directory is modified, added
+    /// or removed. The script listens to Server-Sent Events from
+    /// `{route}/ohkami-live-reload`.
+    #[cfg(all(feature="sse", feature="__rt_native__"))]
+    pub fn live_reload(mut self) -> Self {
+        self.live_reload = true;
+        self
+    }
+}
+
+impl Dir {
+    /// number of leading bytes to sniff MIME type of a file from disk
+    const SNIFF_LEN: u64 = 512;
+
+    /// MIME type of the file by the overrides or the builtin table
+    fn mime_by_filename(&self, filename: &str) -> Option<&'static str> {
+        let extension = Self::extension_of(filename)?;
+
+        self.mime_overrides.iter()
+            .find_map(|(ext, mime)| (*ext == extension).then_some(*mime))
+            .or_else(|| ohkami_lib::mime::get_by_extension(&extension))
+    }
+
+    /// MIME type of the file by the overrides only
+    fn mime_override_by_filename(&self, filename: &str) -> Option<&'static str> {
+        let extension = Self::extension_of(filename)?;
+
+        self.mime_overrides.iter()
+            .find_map(|(ext, mime)| (*ext == extension).then_some(*mime))
+    }
+
+    /// lowercased extension of the file name
+    fn extension_of(filename: &str) -> Option<String> {
+        let (_, extension) = filename.rsplit_once('.')
+            .filter(|(stem, _)| !stem.is_empty()/* not like `.gitignore` */)?;
+        Some(extension.to_ascii_lowercase())
+    }
+}
+
+/// What `Route::Dir` accepts: a directory path ( relative to the working
+/// directory at runtime ) or static files embedded by `ohkami::embed_dir!`
+pub trait DirSource {
+    #[doc(hidden)]
+    fn into_dir(self, route: &'static str) -> std::io::Result<Dir>;
+}
+const _: () = {
+    impl DirSource for &'static str {
+        fn into_dir(self, route: &'static str) -> std::io::Result<Dir> {
+            Dir::new(route, self.into())
+        }
+    }
+
+    impl DirSource for EmbeddedDir {
+        fn into_dir(self, route: &'static str) -> std::io::Result<Dir> {
+            Ok(Dir::embedded(route, self))
+        }
+    }
+};
+
+/// Static files embedded into the binary by `ohkami::embed_dir!`
+pub struct EmbeddedDir {
+    files: &'static [EmbeddedFile],
+}
+impl EmbeddedDir {
+    #[doc(hidden)]
+    pub const fn new(files: &'static [EmbeddedFile]) -> Self {
+        Self { files }
+    }
+}
+
+#[doc(hidden)]
+pub struct EmbeddedFile {
+    /// relative to the embedded directory, joined by `/`
+    pub path:    &'static str,
+    pub mime:    &'static str,
+    pub etag:    &'static str,
+    pub content: &'static [u8],
+    /// precompressed variants by content-coding
+    pub encoded: &'static [(&'static str, &'static [u8])],
+}
+impl std::fmt::Debug for EmbeddedFile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EmbeddedFile")
+            .field("path", &self.path)
+            .field("mime", &self.mime)
+            .field("etag", &self.etag)
+            .field("len", &self.content.len())
+            .field("encoded", &self.encoded.iter().map(|(e, _)| e).collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(crate) enum DirFile {
+    Path(PathBuf),
+    Embedded(&'static EmbeddedFile),
+}
+
+/// Validators of a file for conditional requests
+#[derive(Clone)]
+pub(crate) struct Validators {
+    pub(crate) etag:          String,
+    pub(crate) last_modified: Option<u64>,
+}
+impl Validators {
+    pub(crate) fn of(metadata: &std::fs::Metadata) -> Self {
+        let last_modified = metadata.modified().ok()
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|d| d.as_secs());
+        let etag = format!("\"{:x}-{:x}\"", last_modified.unwrap_or(0), metadata.len());
+        Self { etag, last_modified }
+    }
+
+    /// set `ETag`, `Last-Modified` and `Accept-Ranges` of the response
+    pub(crate) fn set_to(&self, res: &mut Response) {
+        res.headers.set()
+            .ETag(self.etag.clone())
+            .AcceptRanges("bytes");
+        if let Some(last_modified) = self.last_modified {
+            res.headers.set().LastModified(ohkami_lib::time::imf_fixdate(last_modified));
+        }
+    }
+
+    /// distinguish the `ETag` of an encoded representation from the original's
+    pub(crate) fn with_encoding(mut self, encoding: Option<Encoding>) -> Self {
+        if let Some(e) = encoding {
+            self.etag.insert_str(self.etag.len() - 1, &format!("-{}", e.as_str()));
+        }
+        self
+    }
+}
+
+/// How to respond a file of `len` bytes to `req` ( `GET` or `HEAD` ), by
+///
+/// - `If-None-Match` and `If-Modified-Since` for `304 Not Modified`
+/// - `Range` and `If-Range` for `206 Partial Content` or `416 Range Not Satisfiable`
+pub(crate) enum Selection {
+    NotModified,
+    Full,
+    Partial(Vec<Range<u64>>),
+    Unsatisfiable,
+}
+impl Selection {
+    pub(crate) fn of(req: &Request, len: u64, validators: &Validators) -> Self {
+        let not_modified = match req.headers.IfNoneMatch() {
+            Some(if_none_match) => etag::matches_weak(if_none_match, &validators.etag),
+            None => match (req.headers.IfModifiedSince(), validators.last_modified) {
+                (Some(if_modified_since), Some(last_modified)) => ohkami_lib::time::parse_imf_fixdate(if_modified_since)
+                    .is_some_and(|since| last_modified <= since),
+                _ => false
+            }
+        };
+        if not_modified {
+            return Self::NotModified
+        }
+
+        let ranges = req.headers.Range()
+            .filter(|_| match req.headers.IfRange() {
+                None => true,
+                Some(if_range) if if_range.starts_with('"') || if_range.starts_with("W/") => {
+                    etag::matches_strong(if_range, &validators.etag)
+                }
+                Some(if_range) => ohkami_lib::time::parse_imf_fixdate(if_range)
+                    .is_some_and(|date| validators.last_modified == Some(date))
+            })
+            .and_then(|range| ByteRanges::parse(range, len));
+
+        match ranges {
+            None                                  => Self::Full,
+            Some(ByteRanges::Unsatisfiable)       => Self::Unsatisfiable,
+            Some(ByteRanges::Satisfiable(ranges)) => Self::Partial(ranges),
+        }
+    }
+}
+
+/// `multipart/byteranges` of `ranges` of a content of `len` bytes
+pub(crate) struct Multipart {
+    boundary: String,
+    /// header and range of each part
+    pub(crate) parts:   Vec<(String, Range<u64>)>,
+    pub(crate) closing: String,
+}
+impl Multipart {
+    pub(crate) fn new(mime: &str, len: u64, ranges: Vec<Range<u64>>) -> Self {
+        let boundary = {
+            use std::hash::{BuildHasher, Hasher};
+            let mut h = std::collections::hash_map::RandomState::new().build_hasher();
+            h.write_u64(len);
+            format!("{:016x}", h.finish())
+        };
+        let parts = ranges.into_iter()
+            .map(|range| (format!(
+                "--{boundary}\r\nContent-Type: {mime}\r\nContent-Range: {}\r\n\r\n",
+                crate::header::range::content_range(&range, len)
+            ), range))
+            .collect();
+        let closing = format!("--{boundary}--\r\n");
+        Self { boundary, parts, closing }
+    }
+
+    pub(crate) fn content_type(&self) -> String {
+        format!("multipart/byteranges; boundary={}", self.boundary)
+    }
+
+    /// length of the whole body
+    pub(crate) fn content_length(&self) -> u64 {
+        self.parts.iter()
+            .map(|(head, range)| head.len() as u64 + (range.end - range.start) + 2/* \r\n */)
+            .sum::<u64>() + self.closing.len() as u64
+    }
+}
+
+/// `multipart/byteranges` body of `ranges` of a content of `len` bytes,
+/// and its `Content-Type`
+pub(crate) fn multipart_byteranges(
+    mime:     &str,
+    len:      u64,
+    ranges:   Vec<Range<u64>>,
+    mut read: impl FnMut(Range<u64>) -> std::io::Result<Cow<'static, [u8]>>,
+) -> std::io::Result<(String, Vec<u8>)> {
+    let multipart = Multipart::new(mime, len, ranges);
+    let content_type = multipart.content_type();
+
+    let mut body = Vec::with_capacity(multipart.content_length() as usize);
+    for (head, range) in multipart.parts {
+        body.extend_from_slice(head.as_bytes());
+        body.extend_from_slice(&read(range)?);
+        body.extend_from_slice(b"\r\n");
+    }
+    body.extend_from_slice(multipart.closing.as_bytes());
+
+    Ok((content_type, body))
+}
+
+/// Respond a file of `len` bytes to `req` ( `GET` or `HEAD` ), handling
+///
+/// - `If-None-Match` and `If-Modified-Since` with `304 Not Modified`
+/// - `Range` and `If-Range` with `206 Partial Content` ( including `multipart/byteranges` )
+///   or `416 Range Not Satisfiable`
+///
+/// `read` is called with each range of the content to send.
+pub(crate) fn serve_file(
+    req:           &Request,
+    mime:          &'static str,
+    len:           u64,
+    validators:    &Validators,
+    cache_control: Option<&'static str>,
+    mut read:      impl FnMut(Range<u64>) -> std::io::Result<Cow<'static, [u8]>>,
+) -> Response {
+    let res = match Selection::of(req, len, validators) {
+        Selection::NotModified => Ok(Response::NotModified()),
+
+        Selection::Full => read(0..len).map(|content| {
+            Response::OK().with_payload(mime, content)
+        }),
+
+        Selection::Unsatisfiable => Ok({
+            let mut res = Response::RangeNotSatisfiable();
+            res.headers.set().ContentRange(format!("bytes */{len}"));
+            res
+        }),
+
+        Selection::Partial(ranges) if ranges.len() == 1 => {
+            let range = ranges.into_iter().next().unwrap();
+            let content_range = crate::header::range::content_range(&range, len);
+            read(range).map(|content| {
+                let mut res = Response::PartialContent().with_payload(mime, content);
+                res.headers.set().ContentRange(content_range);
+                res
+            })
+        }
+
+        Selection::Partial(ranges) => multipart_byteranges(mime, len, ranges, read)
+            .map(|(content_type, body)| {
+                let mut res = Response::PartialContent().with_payload("", body);
+                res.headers.set().ContentType(content_type);
+                res
+            }),
+    };
+
+    let mut res = match res {
+        Ok(res) => res,
+        Err(e) => {
+            crate::warning!("[Dir] failed to read file: {e}");
+            return reject(Status::InternalServerError, "")
+        }
+    };
+
+    validators.set_to(&mut res);
+    if let Some(cache_control) = cache_control {
+        res.headers.set().CacheControl(cache_control);
+    }
+
+    res
+}
+
+/// a file content to serve
+enum Source {
+    Memory {
+        content:    &'static [u8],
+        validators: Validators,
+    },
+    Disk(PathBuf),
+}
+const _: () = {
+    impl Source {
+        async fn serve(&self,
+            req:           &Request,
+            mime:          &'static str,
+            cache_control: Option<&'static str>,
+            encoding:      Option<Encoding>,
+        ) -> Response {
+            match self {
+                Source::Memory { content, validators } => {
+                    let content: &'static [u8] = content;
+                    serve_file(req, mime, content.len() as u64, validators, cache_control,
+                        |range| Ok(Cow::Borrowed(&content[range.start as usize..range.end as usize]))
+                    )
+                }
+                #[cfg(feature="__rt_native__")]
+                Source::Disk(path) => {
+                    let mut file = match crate::NamedFile::open(path).await {
+                        Ok(file) => file.with_mime(mime),
+                        Err(e) => {
+                            crate::warning!("[Dir] failed to open `{}`: {e}", path.display());
+                            return reject(Status::NotFound, "")
+                        }
+                    };
+                    file.validators = file.validators.with_encoding(encoding);
+
+                    let mut res = crate::IntoResponse::into_response(file);
+                    if let Some(cache_control) = cache_control {
+                        res.headers.set().CacheControl(cache_control);
+                    }
+                    /* done here ( again by the router, no-op ) to be seen by the caller */
+                    res.select_file_range(req);
+                    res
+                }
+                #[cfg(not(feature="__rt_native__"))]
+                Source::Disk(path) => {
+                    /* `NamedFile` is not available on Workers or Lambda */
+                    use std::io::{Read, Seek, SeekFrom};
+
+                    let (mut file, metadata) = match std::fs::File::open(path)
+                        .and_then(|f| f.metadata().map(|m| (f, m)))
+                    {
+                        Ok(fm) => fm,
+                        Err(e) => {
+                            crate::warning!("[Dir] failed to open `{}`: {e}", path.display());
+                            return reject(Status::NotFound, "")
+                        }
+                    };
+                    let validators = Validators::of(&metadata).with_encoding(encoding);
+                    serve_file(req, mime, metadata.len(), &validators, cache_control,
+                        |range| {
+                            let mut buf = vec![0; (range.end - range.start) as usize];
+                            file.seek(SeekFrom::Start(range.start))?;
+                            file.read_exact(&mut buf)?;
+                            Ok(Cow::Owned(buf))
+                        }
+                    )
+                }
+            }
+        }
+    }
+};
+
+impl RoutingItem for Dir {
+    fn apply(self, router: &mut Router) {
+        if self.dev {
+            return DevDir::register(self, router)
+        }
+
+        #[derive(Clone)]
+        struct StaticFileHandler {
+            mime:          &'static str,
+            identity:      std::sync::Arc<Source>,
+            /// in the server's preference order
+            encoded:       std::sync::Arc<[(Encoding, Source)]>,
+            cache_control: Option<&'static str>,
+        }
+        const _: () = {
+            impl StaticFileHandler {
+                fn new(
+                    path_Segments: &[String],
+                    file:          DirFile,
+                    encoded:       Vec<(Encoding, PathBuf)>,
+                    config:        &Dir,
+                    cache_control: Option<&'static str>,
+                ) -> Result<Self, String> {
+                    let filename = path_Segments.last()
+                        .ok_or_else(|| format!("[.Dir] got empty file path"))?;
+
+                    let (mime, identity, mut encoded) = match file {
+                        DirFile::Path(path) => Self::load(filename, path, encoded, config)?,
+                        DirFile::Embedded(file) => {
+                            let validators = Validators {
+                                etag:          file.etag.into(),
+                                last_modified: None,
+                            };
+                            let mut encoded = file.encoded.iter()
+                                .filter_map(|(e, content)| Encoding::from_str(e).map(|e| (e, Source::Memory {
+                                    content,
+                                    validators: validators.clone().with_encoding(Some(e)),
+                                })))
+                                .collect::<Vec<_>>();
+                            encoded.sort_by_key(|(e, _)| Encoding::ALL.iter().position(|a| a == e));
+                            (
+                                config.mime_override_by_filename(filename).unwrap_or(file.mime),
+                                Source::Memory { content: file.content, validators },
+                                encoded
+                            )
+                        }
+                    };
+
+                    if encoded.is_empty() && config.compress && crate::header::encoding::is_compressible(mime) {
+                        if let Source::Memory { content, validators } = &identity {
+                            for &e in Encoding::AVAILABLE {
+                                let compressed = e.encode(content)
+                                    .map_err(|err| format!("[.Dir] failed to compress `{filename}` with {}: {err}", e.as_str()))?;
+                                if compressed.len() < content.len() {
+                                    encoded.push((e, Source::Memory {
+                                        content:    Box::leak(compressed.into_boxed_slice()),
+                                        validators: validators.clone().with_encoding(Some(e)),
+                                    }));
+                                }
+                            }
+                        }
+                    }
+
+                    Ok(Self {
+                        mime,
+                        identity: std::sync::Arc::new(identity),
+                        encoded:  encoded.into(),
+                        cache_control,
+                    })
+                }
+
+                /// load the file on disk and its precompressed variants
+                #[allow(clippy::type_complexity)]
+                fn load(
+                    filename:    &str,
+                    path:        PathBuf,
+                    mut encoded: Vec<(Encoding, PathBuf)>,
+                    config:      &Dir,
+                ) -> Result<(&'static str, Source, Vec<(Encoding, Source)>), String> {
+                    let mime = match config.mime_by_filename(filename) {
+                        Some(mime) => mime,
+                        None => {
+                            /* sniff the content ( or its head when streaming from disk ) */
+                            let sample = if config.stream_from_disk {
+                                use std::io::Read;
+                                let mut head = Vec::new();
+                                std::fs::File::open(&path)
+                                    .and_then(|f| f.take(Dir::SNIFF_LEN).read_to_end(&mut head))
+                                    .map_err(|e| e.to_string())?;
+                                head
+                            } else {
+                                std::fs::read(&path)
+                                    .map_err(|e| e.to_string())?
+                            };
+                            ohkami_lib::mime::sniff(&sample)
+                                .unwrap_or("application/octet-stream")
+                        }
+                    };
+
+                    let load = |path: PathBuf, encoding: Option<Encoding>| -> Result<Source, String> {
+                        if config.stream_from_disk {
+                            return Ok(Source::Disk(path))
+                        }
+
+                        let validators = std::fs::metadata(&path)
+                            .map(|m| Validators::of(&m).with_encoding(encoding))
+                            .map_err(|e| e.to_string())?;
+                        let content = std::fs::read(&path)
+                            .map_err(|e| e.to_string())?;
+
+                        if encoding.is_none()
+                        && mime.starts_with("text/")
+                        && std::str::from_utf8(&content).is_err() {
+                            return Err(format!("[.Dir] got `{filename}`: Ohkami doesn't support non UTF-8 text file"))
+                        }
+
+                        Ok(Source::Memory {
+                            content: Box::leak(content.into_boxed_slice()),
+                            validators,
+                        })
+                    };
+
+                    let identity = load(path, None)?;
+
+                    encoded.sort_by_key(|(e, _)| Encoding::ALL.iter().position(|a| a == e));
+                    let encoded = encoded.into_iter()
+                        .map(|(e, path)| load(path, Some(e)).map(|source| (e, source)))
+                        .collect::<Result<Vec<_>, _>>()?;
+
+                    Ok((mime, identity, encoded))
+                }
+
+                async fn respond(&self, req: &Request) -> Response {
+                    let encoding = negotiate(
+                        req.headers.AcceptEncoding(),
+                        self.encoded.iter().map(|(e, _)| *e)
+                    );
+
+                    let source = encoding
+                        .and_then(|e| self.encoded.iter().find(|(it, _)| *it == e))
+                        .map_or(&*self.identity, |(_, source)| source);
+
+                    let mut res = source.serve(req, self.mime, self.cache_control, encoding).await;
+
+                    if !self.encoded.is_empty() {
+                        res.headers.set().Vary("Accept-Encoding");
+                        if let Some(e) = encoding {
+                            if matches!(res.status, Status::OK | Status::PartialContent) {
+                                res.headers.set().ContentEncoding(e.as_str());
+                            }
+                        }
+                    }
+
+                    res
+                }
+            }
+
+            impl IntoHandler<std::fs::File> for StaticFileHandler {
+                fn n_params(&self) -> usize {0}
+
+                fn into_handler(self) -> Handler {
+                    let this: &'static StaticFileHandler
+                        = Box::leak(Box::new(self));
+
+                    Handler::new(|req| Box::pin(async {
+                        this.respond(req).await
+                    }), #[cfg(feature="openapi")] {use crate::openapi;
+                        openapi::Operation::with(openapi::Responses::new([
+                            (200, openapi::Response::when("OK")
+                                .content(this.mime, openapi::string().format("binary"))),
+                            (206, openapi::Response::when("Partial Content")
+                                .content(this.mime, openapi::string().format("binary"))),
+                            (304, openapi::Response::when("Not Modified")),
+                            (416, openapi::Response::when("Range Not Satisfiable")),
+                        ]))
+                    })
+                }
+            }
+        };
+
+        #[cfg(feature="DEBUG")]
+        println!{ "[Dir] .files = {:#?}", self.files }
+
+        let cache_control: Option<&'static str> = self.cache_control.clone()
+            .map(|cc| match cc {
+                Cow::Borrowed(s) => s,
+                Cow::Owned(s)    => s.leak(),
+            });
+
+        /* precompressed siblings like `app.js.br` of `app.js` are served as
+           encoded variants of the original, not as independent files */
+        let mut encoded_variants = HashMap::<Vec<String>, Vec<(Encoding, PathBuf)>>::new();
+        let files = {
+            let all_paths = self.files.iter()
+                .map(|(path, _)| path.clone())
+                .collect::<HashSet<_>>();
+
+            let mut files = Vec::with_capacity(self.files.len());
+            for (path, file) in &self.files {
+                let variant_of = Encoding::ALL.iter().find_map(|&e| {
+                    let original_filename = path.last()?
+                        .strip_suffix(e.file_extension()?)?
+                        .strip_suffix('.')?;
+                    let mut original = path.clone();
+                    *original.last_mut().unwrap() = original_filename.to_string();
+                    all_paths.contains(&original).then_some((e, original))
+                });
+                match (variant_of, file) {
+                    (Some((e, original)), DirFile::Path(file)) => encoded_variants.entry(original).or_default().push((e, file.clone())),
+                    _ => files.push((path.clone(), file.clone())),
+                }
+            }
+            files
+        };
+
+        let route_of = |path: &[String]| -> &'static str {Box::leak({
+            let base_path = self.route.trim_end_matches('/').to_string();
+            match &*path.join("/") {
+                ""   => if !base_path.is_empty() {base_path} else {"/".into()},
+                some => base_path + "/" + some,
+            }
+        }.into_boxed_str())};
+
+        let mut registered = HashSet::<Vec<String>>::new();
+        let mut register = |path: Vec<String>, handler: StaticFileHandler| {
+            router.register_handlers(HandlerSet::new(route_of(&path)).GET(handler));
+            registered.insert(path);
+        };
+
+        /* index file of each directory */
+        let index_of = {
+            let mut index_of = HashMap::<&[String], &str>::new();
+            for (path, _) in &files {
+                let (filename, dir) = path.split_last().unwrap();
+                if let Some(priority) = self.index_files.iter().position(|i| i == filename) {
+                    let current = index_of.get(dir)
+                        .and_then(|i| self.index_files.iter().position(|it| it == i));
+                    if current.is_none_or(|c| priority < c) {
+                        index_of.insert(dir, filename);
+                    }
+                }
+            }
+            index_of.into_iter()
+                .map(|(dir, filename)| (dir.to_vec(), filename.to_string()))
+                .collect::<HashMap<_, _>>()
+        };
+
+        let mut spa_fallback = None;
+        let mut listings = HashMap::<Vec<String>, Vec<ListingEntry>>::new();
+
+        for (mut path, file) in files {
+            let size = self.listing.then(|| match &file {
+                DirFile::Path(path)     => std::fs::metadata(path).map(|m| m.len()).ok(),
+                DirFile::Embedded(file) => Some(file.content.len() as u64),
+            }).flatten();
+            let encoded = encoded_variants.remove(&path).unwrap_or_default();
+            let handler = match StaticFileHandler::new(&path, file, encoded, &self, cache_control) {
+                Ok(h) => h,
+                Err(msg) => panic!("{msg}")
+            };
+
+            if self.spa_fallback.is_some_and(|f| f.trim_start_matches('/') == path.join("/")) {
+                spa_fallback = Some(handler.clone());
+            }
+
+            let (filename, dir) = path.split_last().unwrap();
+            let is_index = index_of.get(dir) == Some(filename);
+            if is_index {
+                let extension = filename.rsplit_once('.').map(|(_, ext)| ext);
+                if !(self.omit_extensions.as_ref().is_some_and(|exts| extension.is_some_and(|ext| exts.contains(&ext)))) {
+                    register(path.clone(), handler.clone());
+                }
+
+                path.pop();
+            }
+
+            if let Some(exts) = self.omit_extensions.as_ref() {
+                for ext in exts.iter() {
+                    if let Some(filename) = path.last().and_then(|p| p.strip_suffix(&format!(".{ext}"))) {
+                        let filename_len = filename.len();
+                        path.last_mut().unwrap().truncate(filename_len);
+                        break
+                    }
+                }
+            }
+
+            if self.listing && is_index {
+                /* not listed as a file, but makes its directory listed in the parent */
+                listings.entry(path.clone()).or_default();
+            } else if self.listing {
+                let (name, dir) = path.split_last().unwrap();
+                listings.entry(dir.to_vec()).or_default().push(ListingEntry {
+                    name: name.clone(),
+                    kind: ListingEntryKind::File,
+                    size,
+                });
+            }
+
+            register(path, handler);
+        }
+
+        if self.listing {
+            /* directories themselves */
+            for dir in listings.keys().cloned().collect::<Vec<_>>() {
+                for depth in 0..dir.len() {
+                    let entries = listings.entry(dir[..depth].to_vec()).or_default();
+                    if !entries.iter().any(|e| e.name == dir[depth]) {
+                        entries.push(ListingEntry {
+                            name: dir[depth].clone(),
+                            kind: ListingEntryKind::Directory,
+                            size: None,
+                        });
+                    }
+                }
+            }
+
+            for (dir, mut entries) in listings {
+                if registered.contains(&dir) || dir.iter().any(|d| d.starts_with('.')) {
+                    continue
+                }
+                entries.retain(|e| !e.name.starts_with('.'));
+                entries.sort_by(|a, b| Ord::cmp(&(a.kind, &a.name), &(b.kind, &b.name)));
+
+                let listing = DirectoryListing { is_root: dir.is_empty(), entries };
+                router.register_handlers(HandlerSet::new(route_of(&dir)).GET(listing));
+            }
+        }
+
+        if let Some(file) = self.spa_fallback {
+            let Some(handler) = spa_fallback else {
+                panic!("[.Dir] `spa_fallback` file `{file}` is not found in the directory")
+            };
+            router.register_fallback(
+                RouteSegments::from_literal(route_of(&[])),
+                handler.into_handler()
+            );
+        }
+    }
+}
+
+#[derive(Clone, serde::Serialize)]
+struct ListingEntry {
+    name: String,
+    #[serde(rename = "type")]
+    kind: ListingEntryKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    size: Option<u64>,
+}
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+enum ListingEntryKind {
+    /* directories first */
+    Directory,
+    File,
+}
+
+#[derive(Clone)]
+struct DirectoryListing {
+    is_root: bool,
+    entries: Vec<ListingEntry>,
+}
+const _: () = {
+    impl DirectoryListing {
+        fn respond(&self, req: &Request) -> Response {
+            let prefers_json = req.headers.Accept().is_some_and(|accept| {
+                match (accept.find("application/json"), accept.find("text/html")) {
+                    (Some(_), None)       => true,
+                    (Some(json), Some(html)) => json < html,
+                    _ => false
+                }
+            });
+
+            let mut res = if prefers_json {
+                Response::OK().with_json(&self.entries)
+            } else {
+                /* entry names in dev mode are raw file names, and `base` is
+                   from the request, so both are escaped */
+                let base = req.path.str();
+                let base = escape_html(base.trim_end_matches('/'));
+
+                let mut html = format!("\
+                    <!DOCTYPE html>\n\
+                    <html>\n\
+                    <head><meta charset=\"UTF-8\"><title>Index of {base}/</title></head>\n\
+                    <body>\n\
+                    <h1>Index of {base}/</h1>\n\
+                    <ul>\n\
+                ");
+                if !self.is_root {
+                    let parent = base.rsplit_once('/').map(|(p, _)| p).unwrap_or("");
+                    html += &format!("<li><a href=\"{parent}/\">../</a></li>\n");
+                }
+                for ListingEntry { name, kind, .. } in &self.entries {
+                    let slash = if *kind == ListingEntryKind::Directory {"/"} else {""};
+                    let name = escape_html(name);
+                    html += &format!("<li><a href=\"{base}/{name}\">{name}{slash}</a></li>\n");
+                }
+                html += "</ul>\n</body>\n</html>\n";
+
+                Response::OK().with_html(html)
+            };
+            res.headers.set().Vary("Accept");
+            res
+        }
+    }
+
+    fn escape_html(s: &str) -> Cow<'_, str> {
+        if !s.contains(['&', '<', '>', '"', '\'']) {
+            return Cow::Borrowed(s)
+        }
+        let mut escaped = String::with_capacity(s.len() + 16);
+        for c in s.chars() {
+            match c {
+                '&'  => escaped.push_str("&amp;"),
+                '<'  => escaped.push_str("&lt;"),
+                '>'  => escaped.push_str("&gt;"),
+                '"'  => escaped.push_str("&quot;"),
+                '\'' => escaped.push_str("&#39;"),
+                _    => escaped.push(c),
+            }
+        }
+        Cow::Owned(escaped)
+    }
+
+    impl IntoHandler<std::fs::ReadDir> for DirectoryListing {
+        fn n_params(&self) -> usize {0}
+
+        fn into_handler(self) -> Handler {
+            let this: &'static DirectoryListing
+                = Box::leak(Box::new(self));
+
+            Handler::new(|req| Box::pin(async {
+                this.respond(req)
+            }), #[cfg(feature="openapi")] {use crate::openapi;
+                openapi::Operation::with(openapi::Responses::new([
+                    (200, openapi::Response::when("OK")
+                        .content("text/html", openapi::string())
+                        .content("application/json", openapi::array(openapi::object()
+                            .property("name", openapi::string())
+                            .property("type", openapi::string().enumerates(["directory", "file"]))
+                            .optional("size", openapi::integer())
+                        ))),
+                ]))
+            })
+        }
+    }
+};
+
+/// `Dir` in development mode, looking up the requested file on disk on each request
+struct DevDir {
+    config:        Dir,
+    root:          PathBuf,
+    cache_control: Option<&'static str>,
+    #[cfg(all(feature="sse", feature="__rt_native__"))]
+    live_reload:   Option<&'static str>,
+}
+const _: () = {
+    impl DevDir {
+        fn register(config: Dir, router: &mut Router) {
+            let Some(root) = config.root.clone() else {
+                panic!("[.Dir] `dev` is not available for embedded files")
+            };
+
+            let route: &'static str = match config.route.trim_end_matches('/') {
+                "" => "/",
+                some => some,
+            };
+            let cache_control: Option<&'static str> = config.cache_control.clone()
+                .map(|cc| match cc {
+                    Cow::Borrowed(s) => s,
+                    Cow::Owned(s)    => s.leak(),
+                });
+
+            #[cfg(all(feature="sse", feature="__rt_native__"))]
+            let live_reload = config.live_reload.then(|| {
+                let path: &'static str = format!("{}/ohkami-live-reload", route.trim_end_matches('/')).leak();
+                router.register_handlers(HandlerSet::new(path).GET(LiveReload { root: root.clone() }));
+                path
+            });
+
+            router.register_fallback(
+                RouteSegments::from_literal(route),
+                DevDir {
+                    config,
+                    root,
+                    cache_control,
+                    #[cfg(all(feature="sse", feature="__rt_native__"))]
+                    live_reload,
+                }.into_handler()
+            );
+        }
+
+        async fn respond(&'static self, req: &Request) -> Response {
+            let path = req.path.str();
+            let relative = path.strip_prefix(self.config.route.trim_end_matches('/')).unwrap_or(&path);
+
+            let segments = relative.split('/').filter(|s| !s.is_empty()).map(String::from).collect::<Vec<_>>();
+            if segments.iter().any(|s| s.starts_with('.')/* hidden files or `..` */) {
+                return reject(Status::NotFound, "")
+            }
+            let is_root = segments.is_empty();
+
+            match blocking(move || self.resolve(&segments)).await {
+                Resolved::File(file)   => self.serve(req, file).await,
+                Resolved::Listing(dir) => self.listing(req, dir, is_root).await,
+                Resolved::NotFound     => reject(Status::NotFound, ""),
+            }
+        }
+
+        /// looks up what to serve for the path segments on disk ( blocking )
+        fn resolve(&self, segments: &[String]) -> Resolved {
+            let target = segments.iter().fold(self.root.clone(), |path, s| path.join(s));
+            if target.is_dir() {
+                if let Some(index) = self.config.index_files.iter()
+                    .map(|i| target.join(i))
+                    .find(|i| i.is_file())
+                {
+                    return Resolved::File(index)
+                }
+                if self.config.listing {
+                    return Resolved::Listing(target)
+                }
+            } else if target.is_file() {
+                if !self.is_omitted(&target) {
+                    return Resolved::File(target)
+                }
+            } else if let (Some(exts), Some(filename)) = (&self.config.omit_extensions, segments.last()) {
+                if let Some(file) = exts.iter()
+                    .map(|ext| target.with_file_name(format!("{filename}.{ext}")))
+                    .find(|f| f.is_file())
+                {
+                    return Resolved::File(file)
+                }
+            }
+
+            match self.config.spa_fallback {
+                Some(file) => Resolved::File(self.root.join(file.trim_start_matches('/'))),
+                None => Resolved::NotFound
+            }
+        }
+
+        /// whether the file is routed only without its extension by `omit_extensions`
+        fn is_omitted(&self, file: &std::path::Path) -> bool {
+            let extension = file.extension().and_then(|e| e.to_str());
+            self.config.omit_extensions.as_ref()
+                .is_some_and(|exts| extension.is_some_and(|ext| exts.contains(&ext)))
+        }
+
+        async fn serve(&'static self, req: &Request, file: PathBuf) -> Response {
+            let Some(filename) = file.file_name().and_then(|n| n.to_str()).map(String::from) else {
+                return reject(Status::NotFound, "")
+            };
+            let mime = self.config.mime_by_filename(&filename);
+
+            /* sniffing the content and looking up the precompressed variants */
+            let found = blocking({let file = file.clone(); move || {
+                let mime = match mime {
+                    Some(mime) => mime,
+                    None => {
+                        use std::io::Read;
+                        let mut head = Vec::new();
+                        std::fs::File::open(&file)
+                            .and_then(|f| f.take(Dir::SNIFF_LEN).read_to_end(&mut head))?;
+                        ohkami_lib::mime::sniff(&head)
+                            .unwrap_or("application/octet-stream")
+                    }
+                };
+                let encoded = Encoding::ALL.iter()
+                    .filter_map(|e| {
+                        let variant = file.with_file_name(format!("{filename}.{}", e.file_extension()?));
+                        variant.is_file().then_some((*e, variant))
+                    })
+                    .collect::<Vec<_>>();
+                std::io::Result::Ok((mime, encoded))
+            }}).await;
+            let (mime, encoded) = match found {
+                Ok(found) => found,
+                Err(e) => {
+                    crate::warning!("[Dir] failed to open `{}`: {e}", file.display());
+                    return reject(Status::NotFound, "")
+                }
+            };
+
+            #[cfg(all(feature="sse", feature="__rt_native__"))]
+            if let Some(live_reload) = self.live_reload {
+                if mime == "text/html" {
+                    return self.serve_with_live_reload(req, file, live_reload).await
+                }
+            }
+
+            let encoding = negotiate(
+                req.headers.AcceptEncoding(),
+                encoded.iter().map(|(e, _)| *e)
+            );
+
+            let source = Source::Disk(encoding
+                .and_then(|e| encoded.iter().find(|(it, _)| *it == e))
+                .map_or(file, |(_, variant)| variant.clone())
+            );
+
+            let mut res = source.serve(req, mime, self.cache_control, encoding).await;
+
+            if !encoded.is_empty() {
+                res.headers.set().Vary("Accept-Encoding");
+                if let Some(e) = encoding {
+                    if matches!(res.status, Status::OK | Status::PartialContent) {
+                        res.headers.set().ContentEncoding(e.as_str());
+                    }
+                }
+            }
+
+            res
+        }
+
+        #[cfg(all(feature="sse", feature="__rt_native__"))]
+        async fn serve_with_live_reload(&self, req: &Request, file: PathBuf, live_reload: &str) -> Response {
+            let read = blocking({let file = file.clone(); move || {
+                std::fs::read_to_string(&file)
+                    .and_then(|html| std::fs::metadata(&file).map(|m| (html, m)))
+            }}).await;
+            let (html, metadata) = match read {
+                Ok(hm) => hm,
+                Err(e) => {
+                    crate::warning!("[Dir] failed to read `{}`: {e}", file.display());
+                    return reject(Status::NotFound, "")
+                }
+            };
+
+            let script = format!("<script>new EventSource(\"{live_reload}\").onmessage = () => location.reload();</script>\n");
+            let html = match html.rfind("</body>") {
+                Some(i) => [&html[..i], &script, &html[i..]].concat(),
+                None    => html + &script,
+            };
+
+            serve_file(req, "text/html", html.len() as u64, &Validators::of(&metadata), self.cache_control,
+                |range| Ok(Cow::Owned(html.as_bytes()[range.start as usize..range.end as usize].to_vec()))
+            )
+        }
+
+        async fn listing(&'static self, req: &Request, dir: PathBuf, is_root: bool) -> Response {
+            let read = blocking({let dir = dir.clone(); move || {
+                let mut entries = Vec::new();
+                for entry in std::fs::read_dir(&dir)?.filter_map(Result::ok) {
+                    let Ok(mut name) = entry.file_name().into_string() else {continue};
+                    if name.starts_with('.') {
+                        continue
+                    }
+
+                    let path = entry.path();
+                    if path.is_dir() {
+                        entries.push(ListingEntry { name, kind: ListingEntryKind::Directory, size: None });
+                    } else {
+                        /* precompressed siblings are not served by themselves */
+                        if Encoding::ALL.iter().any(|e| e.file_extension().is_some_and(|ext|
+                            name.strip_suffix(ext).and_then(|n| n.strip_suffix('.')).is_some_and(|original| dir.join(original).is_file())
+                        )) {
+                            continue
+                        }
+                        if self.is_omitted(&path) {
+                            name.truncate(name.rfind('.').unwrap());
+                        }
+                        let size = entry.metadata().map(|m| m.len()).ok();
+                        entries.push(ListingEntry { name, kind: ListingEntryKind::File, size });
+                    }
+                }
+                std::io::Result::Ok(entries)
+            }}).await;
+            let mut entries = match read {
+                Ok(entries) => entries,
+                Err(e) => {
+                    crate::warning!("[Dir] failed to read `{}`: {e}", dir.display());
+                    return reject(Status::NotFound, "")
+                }
+            };
+            entries.sort_by(|a, b| Ord::cmp(&(a.kind, &a.name), &(b.kind, &b.name)));
+
+            DirectoryListing { is_root, entries }.respond(req)
+        }
+    }
+
+    /// what `DevDir` serves for a request
+    enum Resolved {
+        File(PathBuf),
+        Listing(PathBuf),
+        NotFound,
+    }
+
+    /// runs blocking `f` off the executor where the runtime allows
+    async fn blocking<T: Send + 'static>(f: impl FnOnce() -> T + Send + 'static) -> T {
+        #[cfg(feature="__rt_native__")]
+        return crate::__rt__::unblock(f).await;
+
+        #[cfg(not(feature="__rt_native__"))]
+        return f();
+    }
+
+    impl IntoHandler<std::fs::DirEntry> for DevDir {
+        fn n_params(&self) -> usize {0}
+
+        fn into_handler(self) -> Handler {
+            let this: &'static DevDir
+                = Box::leak(Box::new(self));
+
+            Handler::new(|req| Box::pin(async {
+                this.respond(req).await
+            }), #[cfg(feature="openapi")] {use crate::openapi;
+                openapi::Operation::with(openapi::Responses::new([
+                    (200, openapi::Response::when("OK")
+                        .content("*/*", openapi::string().format("binary"))),
+                ]))
+            })
+        }
+    }
+};
+
+/// Server-Sent Events endpoint sending `reload` on changes in the directory
+#[cfg(all(feature="sse", feature="__rt_native__"))]
+struct LiveReload {
+    root: PathBuf,
+}
+#[cfg(all(feature="sse", feature="__rt_native__"))]
+const _: () = {
+    impl LiveReload {
+        /// interval of polling the directory for changes
+        const INTERVAL: std::time::Duration = std::time::Duration::from_millis(300);
+
+        /// hash of paths, modified times and sizes of the files in the directory
+        fn fingerprint(root: &std::path::Path) -> u64 {
+            use std::hash::{Hash, Hasher};
+
+            let mut h = std::collections::hash_map::DefaultHasher::new();
+            let mut dirs = vec![root.to_path_buf()];
+            while let Some(dir) = dirs.pop() {
+                let Ok(read_dir) = std::fs::read_dir(&dir) else {continue};
+                let mut entries = read_dir.filter_map(Result::ok)
+                    .filter(|e| !e.file_name().as_encoded_bytes().starts_with(b"."))
+                    .collect::<Vec<_>>();
+                entries.sort_by_key(|e| e.file_name());
+                for entry in entries {
+                    let Ok(metadata) = entry.metadata() else {continue};
+                    entry.path().hash(&mut h);
+                    if metadata.is_dir() {
+                        dirs.push(entry.path());
+                    } else {
+                        metadata.modified().ok().hash(&mut h);
+                        metadata.len().hash(&mut h);
+                    }
+                }
+            }
+            h.finish()
+        }
+    }
+
+    impl IntoHandler<std::fs::Metadata> for LiveReload {
+        fn n_params(&self) -> usize {0}
+
+        fn into_handler(self) -> Handler {
+            let this: &'static LiveReload
+                = Box::leak(Box::new(self));
+
+            Handler::new(move |_| Box::pin(async move {
+                use crate::IntoResponse;
+
+                /* walking the directory is blocking */
+                let walk = || crate::__rt__::unblock(|| LiveReload::fingerprint(&this.root));
+
+                crate::sse::DataStream::<&'static str>::new(move |mut s| async move {
+                    let mut fingerprint = walk().await;
+                    loop {
+                        crate::__rt__::sleep(LiveReload::INTERVAL).await;
+                        let current = walk().await;
+                        if current != fingerprint {
+                            fingerprint = current;
+                            s.send("reload");
+                        }
+                    }
+                }).into_response()
+            }), #[cfg(feature="openapi")] {use crate::openapi;
+                openapi::Operation::with(openapi::Responses::new([
+                    (200, openapi::Response::when("Streaming")
+                        .content("text/event-stream", openapi::string())),
+                ]))
+            })
+        }
+    }
+};
+
+#[cfg(all(test, feature="__rt_native__", feature="DEBUG"))]
+mod test {
+    use crate::prelude::*;
+    use crate::testing::*;
+
+    fn temp_dir(name: &str, files: &[(&str, &[u8])]) -> &'static str {
+        let dir = std::env::temp_dir().join(format!("ohkami-dir-test-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        for (path, content) in files {
+            std::fs::create_dir_all(dir.join(path).parent().unwrap()).unwrap();
+            std::fs::write(dir.join(path), content).unwrap();
+        }
+        dir.into_os_string().into_string().unwrap().leak()
+    }
+
+    #[test] fn dir_conditional_get() {
+        let dir = temp_dir("conditional", &[("hello.txt", b"Hello, world!")]);
+        for t in [
+            Ohkami::new(("/static".Dir(dir).cache_control("public, max-age=60"),)).test(),
+            Ohkami::new(("/static".Dir(dir).cache_control("public, max-age=60").stream_from_disk(),)).test(),
+        ] {
+            crate::__rt__::testing::block_on(async {
+                let req = TestRequest::GET("/static/hello.txt");
+                let res = t.oneshot(req).await.collect_body().await;
+                assert_eq!(res.status().code(), 200);
+                assert_eq!(res.text(), Some("Hello, world!"));
+                assert_eq!(res.header("Cache-Control"), Some("public, max-age=60"));
+                assert_eq!(res.header("Accept-Ranges"), Some("bytes"));
+                let etag = res.header("ETag").unwrap().to_string();
+                let last_modified = res.header("Last-Modified").unwrap().to_string();
+
+                let req = TestRequest::GET("/static/hello.txt")
+                    .header("If-None-Match", format!(r#""xyz", {etag}"#));
+                let res = t.oneshot(req).await;
+                assert_eq!(res.status().code(), 304);
+                assert_eq!(res.text(), None);
+                assert_eq!(res.header("ETag"), Some(&*etag));
+
+                let req = TestRequest::GET("/static/hello.txt")
+                    .header("If-None-Match", r#""xyz""#);
+                let res = t.oneshot(req).await;
+                assert_eq!(res.status().code(), 200);
+
+                let req = TestRequest::GET("/static/hello.txt")
+                    .header("If-Modified-Since", last_modified.clone());
+                let res = t.oneshot(req).await;
+                assert_eq!(res.status().code(), 304);
+
+                let req = TestRequest::GET("/static/hello.txt")
+                    .header("If-Modified-Since", "Thu, 01 Jan 1970 00:00:00 GMT");
+                let res = t.oneshot(req).await;
+                assert_eq!(res.status().code(), 200);
+            });
+        }
+    }
+
+    #[test] fn dir_range_requests() {
+        let dir = temp_dir("range", &[("data.txt", b"0123456789")]);
+
+        for t in [
+            Ohkami::new(("/".Dir(dir),)).test(),
+            Ohkami::new(("/".Dir(dir).stream_from_disk(),)).test(),
+        ] {
+            crate::__rt__::testing::block_on(async {
+                let req = TestRequest::GET("/data.txt")
+                    .header("Range", "bytes=2-4");
+                let res = t.oneshot(req).await.collect_body().await;
+                assert_eq!(res.status().code(), 206);
+                assert_eq!(res.header("Content-Range"), Some("bytes 2-4/10"));
+                assert_eq!(res.text(), Some("234"));
+
+                let req = TestRequest::GET("/data.txt")
+                    .header("Range", "bytes=-3");
+                let res = t.oneshot(req).await.collect_body().await;
+                assert_eq!(res.status().code(), 206);
+                assert_eq!(res.header("Content-Range"), Some("bytes 7-9/10"));
+                assert_eq!(res.text(), Some("789"));
+
+                let req = TestRequest::GET("/data.txt")
+                    .header("Range", "bytes=20-");
+                let res = t.oneshot(req).await;
+                assert_eq!(res.status().code(), 416);
+                assert_eq!(res.header("Content-Range"), Some("bytes */10"));
+
+                let req = TestRequest::GET("/data.txt")
+                    .header("Range", "bytes=0-1, 8-");
+                let res = t.oneshot(req).await.collect_body().await;
+                assert_eq!(res.status().code(), 206);
+                let content_type = res.header("Content-Type").unwrap();
+                let boundary = content_type.strip_prefix("multipart/byteranges; boundary=").unwrap();
+                let body = std::str::from_utf8(res.content(content_type).unwrap()).unwrap();
+                assert_eq!(body, format!("\
+                    --{boundary}\r\n\
+                    Content-Type: text/plain\r\n\
+                    Content-Range: bytes 0-1/10\r\n\
+                    \r\n\
+                    01\r\n\
+                    --{boundary}\r\n\
+                    Content-Type: text/plain\r\n\
+                    Content-Range: bytes 8-9/10\r\n\
+                    \r\n\
+                    89\r\n\
+                    --{boundary}--\r\n\
+                "));
+
+                let etag = t.oneshot(TestRequest::GET("/data.txt")).await
+                    .header("ETag").unwrap().to_string();
+
+                let req = TestRequest::GET("/data.txt")
+                    .header("Range", "bytes=2-4")
+                    .header("If-Range", etag);
+                let res = t.oneshot(req).await.collect_body().await;
+                assert_eq!(res.status().code(), 206);
+                assert_eq!(res.text(), Some("234"));
+
+                let req = TestRequest::GET("/data.txt")
+                    .header("Range", "bytes=2-4")
+                    .header("If-Range", r#""outdated""#);
+                let res = t.oneshot(req).await.collect_body().await;
+                assert_eq!(res.status().code(), 200);
+                assert_eq!(res.text(), Some("0123456789"));
+            });
+        }
+    }
+
+    #[test] fn dir_mime_types() {
+        let dir = temp_dir("mime", &[
+            ("photo.JPG",  b"\xFF\xD8\xFF\xE0\0\x10JFIF"),
+            ("app.wasm",   b"\0asm\x01\0\0\0"),
+            ("data.foo",   b"foo"),
+            ("LICENSE",    b"MIT License"),
+            ("image",      b"\x89PNG\r\n\x1A\n\0\0\0\rIHDR"),
+            ("blob.xyz",   b"\x01\x02\x03"),
+        ]);
+
+        for t in [
+            Ohkami::new(("/".Dir(dir).mime(".foo", "application/x-foo"),)).test(),
+            Ohkami::new(("/".Dir(dir).mime("foo", "application/x-foo").stream_from_disk(),)).test(),
+        ] {
+            crate::__rt__::testing::block_on(async {
+                for (path, mime) in [
+                    ("/photo.JPG", "image/jpeg"),
+                    ("/app.wasm",  "application/wasm"),
+                    ("/data.foo",  "application/x-foo"),
+                    ("/LICENSE",   "text/plain"),
+                    ("/image",     "image/png"),
+                    ("/blob.xyz",  "application/octet-stream"),
+                ] {
+                    let res = t.oneshot(TestRequest::GET(path)).await;
+                    assert_eq!(res.status().code(), 200, "{path}");
+                    assert_eq!(res.header("Content-Type"), Some(mime), "{path}");
+                }
+            });
+        }
+    }
+
+    #[test] fn dir_precompressed_variants() {
+        let dir = temp_dir("precompressed", &[
+            ("app.js",    b"console.log('ohkami')"),
+            ("app.js.br", b"<br>"),
+            ("app.js.gz", b"<gz>"),
+        ]);
+        let t = Ohkami::new(("/".Dir(dir),)).test();
+
+        crate::__rt__::testing::block_on(async {
+            let req = TestRequest::GET("/app.js");
+            let res = t.oneshot(req).await;
+            assert_eq!(res.status().code(), 200);
+            assert_eq!(res.header("Content-Encoding"), None);
+            assert_eq!(res.header("Vary"), Some("Accept-Encoding"));
+            assert_eq!(res.content("text/javascript"), Some(&b"console.log('ohkami')"[..]));
+
+            let req = TestRequest::GET("/app.js")
+                .header("Accept-Encoding", "gzip, deflate, br");
+            let res = t.oneshot(req).await;
+            assert_eq!(res.status().code(), 200);
+            assert_eq!(res.header("Content-Encoding"), Some("br"));
+            assert_eq!(res.header("Content-Type"), Some("text/javascript"));
+            assert_eq!(res.content("text/javascript"), Some(&b"<br>"[..]));
+            let br_etag = res.header("ETag").unwrap().to_string();
+
+            let req = TestRequest::GET("/app.js")
+                .header("Accept-Encoding", "gzip;q=1, br;q=0.5");
+            let res = t.oneshot(req).await;
+            assert_eq!(res.header("Content-Encoding"), Some("gzip"));
+            assert_eq!(res.content("text/javascript"), Some(&b"<gz>"[..]));
+            assert_ne!(res.header("ETag"), Some(&*br_etag));
+
+            let req = TestRequest::GET("/app.js")
+                .header("Accept-Encoding", "br")
+                .header("If-None-Match", br_etag);
+            let res = t.oneshot(req).await;
+            assert_eq!(res.status().code(), 304);
+
+            let req = TestRequest::GET("/app.js.br");
+            let res = t.oneshot(req).await;
+            assert_eq!(res.status().code(), 404);
+        });
+    }
+
+    #[cfg(feature="gzip")]
+    #[test] fn dir_compress_at_load_time() {
+        let dir = temp_dir("compress", &[
+            ("index.html", "<p>ohkami</p>".repeat(100).as_bytes()),
+        ]);
+        let t = Ohkami::new(("/".Dir(dir).compress(),)).test();
+
+        crate::__rt__::testing::block_on(async {
+            let req = TestRequest::GET("/")
+                .header("Accept-Encoding", "gzip");
+            let res = t.oneshot(req).await;
+            assert_eq!(res.status().code(), 200);
+            assert_eq!(res.header("Content-Encoding"), Some("gzip"));
+            assert_eq!(res.header("Vary"), Some("Accept-Encoding"));
+
+            let compressed = res.content("text/html").unwrap();
+            let mut decompressed = String::new();
+            {use std::io::Read;
+                flate2::read::GzDecoder::new(compressed).read_to_string(&mut decompressed).unwrap();
+            }
+            assert_eq!(decompressed, "<p>ohkami</p>".repeat(100));
+
+            let req = TestRequest::GET("/");
+            let res = t.oneshot(req).await;
+            assert_eq!(res.header("Content-Encoding"), None);
+            assert_eq!(res.html(), Some(&*"<p>ohkami</p>".repeat(100)));
+        });
+    }
+
+    #[test] fn dir_embedded() {
+        use crate::__internal__::{EmbeddedDir, EmbeddedFile};
+
+        /* what `embed_dir!` expands to */
+        static FILES: &[EmbeddedFile] = &[
+            EmbeddedFile {
+                path:    "index.html",
+                mime:    "text/html",
+                etag:    "\"0123456789abcdef-d\"",
+                content: b"<p>ohkami</p>",
+                encoded: &[("br", b"<br>"), ("gzip", b"<gz>")],
+            },
+            EmbeddedFile {
+                path:    "data/sample.dat",
+                mime:    "application/octet-stream",
+                etag:    "\"fedcba9876543210-4\"",
+                content: b"\x01\x02\x03\x04",
+                encoded: &[],
+            },
+        ];
+        let t = Ohkami::new((
+            "/".Dir(EmbeddedDir::new(FILES)).mime("dat", "application/x-sample"),
+        )).test();
+
+        crate::__rt__::testing::block_on(async {
+            let req = TestRequest::GET("/");
+            let res = t.oneshot(req).await;
+            assert_eq!(res.status().code(), 200);
+            assert_eq!(res.header("ETag"), Some("\"0123456789abcdef-d\""));
+            assert_eq!(res.header("Last-Modified"), None);
+            assert_eq!(res.header("Vary"), Some("Accept-Encoding"));
+            assert_eq!(res.html(), Some("<p>ohkami</p>"));
+
+            let req = TestRequest::GET("/index.html")
+                .header("Accept-Encoding", "gzip, br");
+            let res = t.oneshot(req).await;
+            assert_eq!(res.header("Content-Encoding"), Some("br"));
+            assert_eq!(res.header("ETag"), Some("\"0123456789abcdef-d-br\""));
+            assert_eq!(res.content("text/html"), Some(&b"<br>"[..]));
+
+            let req = TestRequest::GET("/")
+                .header("If-None-Match", "\"0123456789abcdef-d\"");
+            let res = t.oneshot(req).await;
+            assert_eq!(res.status().code(), 304);
+
+            let req = TestRequest::GET("/data/sample.dat")
+                .header("Range", "bytes=1-2");
+            let res = t.oneshot(req).await;
+            assert_eq!(res.status().code(), 206);
+            assert_eq!(res.content("application/x-sample"), Some(&b"\x02\x03"[..]));
+        });
+    }
+
+    #[test] fn dir_dev() {
+        let dir = temp_dir("dev", &[
+            ("index.html", b"<p>top</p>"),
+            ("style.css",  b"p {}"),
+            (".env",       b"SECRET=ohkami"),
+        ]);
+        let t = Ohkami::new(("/".Dir(dir).dev(true).omit_extensions(["html"]),)).test();
+
+        crate::__rt__::testing::block_on(async {
+            let req = TestRequest::GET("/");
+            let res = t.oneshot(req).await.collect_body().await;
+            assert_eq!(res.html(), Some("<p>top</p>"));
+
+            let req = TestRequest::GET("/style.css");
+            let res = t.oneshot(req).await.collect_body().await;
+            assert_eq!(res.content("text/css"), Some(&b"p {}"[..]));
+
+            std::fs::write(format!("{dir}/style.css"), "p {color: red}").unwrap();
+            let req = TestRequest::GET("/style.css");
+            let res = t.oneshot(req).await.collect_body().await;
+            assert_eq!(res.content("text/css"), Some(&b"p {color: red}"[..]));
+
+            std::fs::create_dir(format!("{dir}/docs")).unwrap();
+            std::fs::write(format!("{dir}/docs/guide.html"), "<p>guide</p>").unwrap();
+            let req = TestRequest::GET("/docs/guide");
+            let res = t.oneshot(req).await.collect_body().await;
+            assert_eq!(res.html(), Some("<p>guide</p>"));
+            let req = TestRequest::GET("/docs/guide.html");
+            let res = t.oneshot(req).await;
+            assert_eq!(res.status().code(), 404);
+
+            std::fs::remove_file(format!("{dir}/style.css")).unwrap();
+            let req = TestRequest::GET("/style.css");
+            let res = t.oneshot(req).await;
+            assert_eq!(res.status().code(), 404);
+
+            let req = TestRequest::GET("/.env");
+            let res = t.oneshot(req).await;
+            assert_eq!(res.status().code(), 404);
+        });
+    }
+
+    #[cfg(feature="sse")]
+    #[test] fn dir_live_reload() {
+        let dir = temp_dir("live-reload", &[
+            ("index.html", b"<html><body><p>top</p></body></html>"),
+            ("data.json",  b"{}"),
+        ]);
+        let t = Ohkami::new(("/app".Dir(dir).dev(true).live_reload(),)).test();
+
+        crate::__rt__::testing::block_on(async {
+            let req = TestRequest::GET("/app");
+            let res = t.oneshot(req).await.collect_body().await;
+            assert_eq!(res.html(), Some("<html><body><p>top</p>\
+                <script>new EventSource(\"/app/ohkami-live-reload\").onmessage = () => location.reload();</script>\n\
+                </body></html>"));
+
+            let req = TestRequest::GET("/app/data.json");
+            let res = t.oneshot(req).await.collect_body().await;
+            assert_eq!(res.content("application/json"), Some(&b"{}"[..]));
+        });
+
+        let fingerprint = super::LiveReload::fingerprint(dir.as_ref());
+        assert_eq!(super::LiveReload::fingerprint(dir.as_ref()), fingerprint);
+        std::fs::write(format!("{dir}/new.css"), "p {}").unwrap();
+        assert_ne!(super::LiveReload::fingerprint(dir.as_ref()), fingerprint);
+    }
+
+    #[test] fn dir_index_files() {
+        let dir = temp_dir("index", &[
+            ("index.html",       b"<p>top</p>"),
+            ("docs/index.htm",   b"<p>docs</p>"),
+            ("docs/index.html",  b"<p>docs (html)</p>"),
+            ("guide/index.htm",  b"<p>guide</p>"),
+            ("guide/intro.html", b"<p>intro</p>"),
+        ]);
+        let t = Ohkami::new((
+            "/site".Dir(dir).index_files(["index.htm", "index.html"]),
+        )).test();
+
+        crate::__rt__::testing::block_on(async {
+            for (path, html) in [
+                ("/site",             "<p>top</p>"),
+                ("/site/",            "<p>top</p>"),
+                ("/site/index.html",  "<p>top</p>"),
+                ("/site/docs",        "<p>docs</p>"),
+                ("/site/docs/",       "<p>docs</p>"),
+                ("/site/docs/index.html", "<p>docs (html)</p>"),
+                ("/site/guide/",      "<p>guide</p>"),
+                ("/site/guide/intro.html", "<p>intro</p>"),
+            ] {
+                let res = t.oneshot(TestRequest::GET(path)).await;
+                assert_eq!(res.html(), Some(html), "{path}");
+            }
+        });
+    }
+
+    #[test] fn dir_spa_fallback() {
+        let dir = temp_dir("spa", &[
+            ("index.html",    b"<p>app</p>"),
+            ("assets/app.js", b"console.log('app')"),
+        ]);
+        let t = Ohkami::new((
+            "/api/hello".GET(|| async {"Hello!"}),
+            "/app".Dir(dir).spa_fallback("index.html"),
+        )).test();
+
+        crate::__rt__::testing::block_on(async {
+            for path in ["/app", "/app/settings", "/app/users/42/edit", "/app/assets"] {
+                let res = t.oneshot(TestRequest::GET(path)).await;
+                assert_eq!(res.status().code(), 200, "{path}");
+                assert_eq!(res.html(), Some("<p>app</p>"), "{path}");
+            }
+
+            let res = t.oneshot(TestRequest::GET("/app/assets/app.js")).await;
+            assert_eq!(res.content("text/javascript"), Some(&b"console.log('app')"[..]));
+
+            let res = t.oneshot(TestRequest::HEAD("/app/settings")).await;
+            assert_eq!(res.status().code(), 200);
+            assert_eq!(res.html(), None);
+
+            let res = t.oneshot(TestRequest::GET("/api/hello")).await;
+            assert_eq!(res.text(), Some("Hello!"));
+
+            for path in ["/api/unknown", "/application", "/"] {
+                let res = t.oneshot(TestRequest::GET(path)).await;
+                assert_eq!(res.status().code(), 404, "{path}");
+            }
+
+            let res = t.oneshot(TestRequest::POST("/app/settings")).await;
+            assert_eq!(res.status().code(), 404);
+        });
+    }
+
+    #[test] fn dir_listing() {
+        let dir = temp_dir("listing", &[
+            ("a.txt",           b"a"),
+            ("sub/b.txt",       b"bb"),
+            ("docs/index.html", b"<p>docs</p>"),
+        ]);
+        let t = Ohkami::new((
+            "/files".Dir(dir).listing(),
+        )).test();
+
+        crate::__rt__::testing::block_on(async {
+            let res = t.oneshot(TestRequest::GET("/files")).await;
+            assert_eq!(res.status().code(), 200);
+            assert_eq!(res.header("Vary"), Some("Accept"));
+            let html = res.html().unwrap();
+            assert!(html.contains(r#"<a href="/files/docs">docs/</a>"#), "{html}");
+            assert!(html.contains(r#"<a href="/files/sub">sub/</a>"#), "{html}");
+            assert!(html.contains(r#"<a href="/files/a.txt">a.txt</a>"#), "{html}");
+            assert!(!html.contains("../"), "{html}");
+
+            let res = t.oneshot(TestRequest::GET("/files/sub/")).await;
+            let html = res.html().unwrap();
+            assert!(html.contains(r#"<a href="/files/">../</a>"#), "{html}");
+            assert!(html.contains(r#"<a href="/files/sub/b.txt">b.txt</a>"#), "{html}");
+
+            let req = TestRequest::GET("/files")
+                .header("Accept", "application/json");
+            let res = t.oneshot(req).await;
+            assert_eq!(res.json::<serde_json::Value>().unwrap().unwrap(), serde_json::json!([
+                {"name": "docs", "type": "directory"},
+                {"name": "sub",  "type": "directory"},
+                {"name": "a.txt", "type": "file", "size": 1},
+            ]));
+
+            let res = t.oneshot(TestRequest::GET("/files/docs")).await;
+            assert_eq!(res.html(), Some("<p>docs</p>"));
+        });
+
+        /* raw file names in dev mode are escaped */
+        let dir = temp_dir("listing-dev", &[
+            (r#"<img src=x onerror="alert(1)">.txt"#, b"x"),
+        ]);
+        let t = Ohkami::new((
+            "/files".Dir(dir).dev(true).listing(),
+        )).test();
+
+        crate::__rt__::testing::block_on(async {
+            let res = t.oneshot(TestRequest::GET("/files")).await;
+            let html = res.html().unwrap();
+            assert!(!html.contains("<img"), "{html}");
+            assert!(html.contains("&lt;img src=x onerror=&quot;alert(1)&quot;&gt;.txt</a>"), "{html}");
+        });
+    }
+}
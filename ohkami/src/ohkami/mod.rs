@@ -2,7 +2,13 @@
 mod _test;
 
 pub(crate) mod routing;
-pub use routing::{Route, Routing};
+pub use routing::{Route, Routing, RouteInfo};
+
+mod versioning;
+pub use versioning::{Versioned, Version};
+
+pub(crate) mod dir;
+pub use dir::{EmbeddedDir, EmbeddedFile};
 
 use crate::fang::Fangs;
 use crate::router::base::Router;
@@ -310,18 +316,20 @@ impl Ohkami {
     /// `.Dir` mounts a directory and generates handlers
     /// for serving each static file in it.
     /// 
-    /// This doesn't work on `rt_worker` ( of course because there Ohkami can't
-    /// touch your local file system ). Consider using `asset` of wrangler.{toml/json}
-    /// ( or `--asset` flag of `npm run {dev/deploy}` )
+    /// A directory path doesn't work on `rt_worker` ( of course because there
+    /// Ohkami can't touch your local file system ). Embed the files into the
+    /// binary by `ohkami::embed_dir!` instead, or consider using `asset` of
+    /// wrangler.{toml/json} ( or `--asset` flag of `npm run {dev/deploy}` )
     /// 
     /// ```
     /// use ohkami::{Ohkami, Route};
     /// 
     /// # fn __() -> Ohkami {
     /// # let another_ohkami = Ohkami::new(());
-    /// Ohkami::new(
+    /// Ohkami::new((
     ///     "/public".Dir("./path/to/dir"),
-    /// )
+    ///     "/assets".Dir(ohkami::embed_dir!("./src")),
+    /// ))
     /// # }
     /// ```
     /// 
@@ -370,6 +378,37 @@ impl Ohkami {
         this
     }
 
+    /// Get all the routes registered to this `Ohkami`, including the ones
+    /// of nested `Ohkami`s, sorted by path.
+    /// 
+    /// Each `RouteInfo` holds the type names of the handler and all the fangs
+    /// applied to it ( from the most outer one ), so you can write a test like:
+    /// 
+    /// ```
+    /// use ohkami::prelude::*;
+    /// 
+    /// # #[derive(Clone)] struct Auth;
+    /// # impl FangAction for Auth {}
+    /// # async fn list_users() {}
+    /// # async fn create_user() {}
+    /// fn my_ohkami() -> Ohkami {
+    ///     Ohkami::new((
+    ///         "/users"
+    ///             .GET(list_users)
+    ///             .POST((Auth, create_user)),
+    ///     ))
+    /// }
+    /// 
+    /// let routes = my_ohkami().routes();
+    /// assert!(routes.iter().any(|r| r.method == Method::POST && r.has_fang::<Auth>()));
+    /// assert!(routes.iter().any(|r| r.method == Method::GET && !r.has_fang::<Auth>()));
+    /// ```
+    /// 
+    /// This doesn't include the auto-generated `OPTIONS` and `HEAD` handlers.
+    pub fn routes(&self) -> Vec<RouteInfo> {
+        self.router.routes(self.fangs.as_deref())
+    }
+
     pub(crate) fn into_router(self) -> Router {
         let Self { fangs, mut router } = self;
 
@@ -397,6 +436,9 @@ impl Ohkami {
     /// This is configureable by `OHKAMI_KEEPALIVE_TIMEOUT`
     /// environment variable.
     /// 
+    /// *note* : Setting `OHKAMI_PRINT_ROUTES=1` prints the table of
+    /// registered routes ( see [`Ohkami::routes`] ) at startup.
+    /// 
     /// <br>
     /// 
     /// *example.rs*
@@ -445,6 +487,10 @@ impl Ohkami {
     /// }
     /// ```
     pub async fn howl(self, address: impl __rt__::ToSocketAddrs) {
+        if crate::CONFIG.print_routes() {
+            println!("{}", routing::RouteTable(&self.routes()));
+        }
+
         let (router, _) = self.into_router().finalize();
         let router = Arc::new(router);
 
@@ -615,7 +661,7 @@ const _: () = {
     > for Ohkami {
         type Response = lambda_runtime::FunctionResponse<
             crate::x_lambda::LambdaResponse,
-            std::pin::Pin<Box<dyn ohkami_lib::Stream<Item = Result<String, std::convert::Infallible>> + Send>>
+            std::pin::Pin<Box<dyn ohkami_lib::Stream<Item = Result<Vec<u8>, lambda_runtime::Error>> + Send>>
         >;
         type Error = lambda_runtime::Error;
 
@@ -1,10 +1,10 @@
 #![allow(non_snake_case, unused_mut)]
 
 use crate::router::{base::Router, segments::RouteSegments};
-use crate::fang::{Fang, BoxedFPC};
+use crate::fang::{Fang, Fangs, BoxedFPC};
 use crate::fang::handler::{Handler, IntoHandler};
-use crate::response::Content;
 use crate::Ohkami;
+use super::dir::{Dir, DirSource};
 use std::sync::Arc;
 
 
@@ -12,12 +12,15 @@ use std::sync::Arc;
 pub(crate) struct HandlerMeta {
     pub(crate) name: &'static str,
     pub(crate) n_params: usize,
+    /// local fangs, from the most outer one
+    pub(crate) fangs: Vec<&'static str>,
 }
 impl HandlerMeta {
-    fn new<T, H: IntoHandler<T>>(h: &H) -> Self {
+    pub(crate) fn new<T, H: IntoHandler<T>>(h: &H) -> Self {
         Self {
-            name: std::any::type_name::<H>(),
+            name: h.__handler_name__(),
             n_params: h.n_params(),
+            fangs: h.__fang_names__(),
         }
     }
 }
@@ -26,10 +29,84 @@ impl std::fmt::Debug for HandlerMeta {
         f.debug_struct("HandlerMeta")
             .field("name", &self.name)
             .field("n_params", &self.n_params)
+            .field("fangs", &self.fangs)
             .finish()
     }
 }
 
+/// A route registered to an `Ohkami`, returned by [`Ohkami::routes`](crate::Ohkami::routes)
+#[derive(Clone, Debug)]
+pub struct RouteInfo {
+    /// route literal like `/users/:id`
+    pub path: String,
+    pub method: crate::Method,
+    /// type name of the handler
+    pub handler: &'static str,
+    /// type names of all the fangs applied to the handler, from the most outer one
+    pub fangs: Vec<&'static str>,
+
+    #[cfg(feature="openapi")]
+    pub operation_id: Option<&'static str>,
+}
+
+impl RouteInfo {
+    /// Whether the fang type `F` is applied to this route
+    pub fn has_fang<F>(&self) -> bool {
+        self.fangs.contains(&std::any::type_name::<F>())
+    }
+}
+
+/// The route table printed at `howl` when `OHKAMI_PRINT_ROUTES` is set
+#[cfg(feature="__rt_native__")]
+pub(crate) struct RouteTable<'r>(pub(crate) &'r [RouteInfo]);
+#[cfg(feature="__rt_native__")]
+impl std::fmt::Display for RouteTable<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        /// `alloc::vec::Vec<my_app::User>` -> `Vec<User>`
+        fn short(type_name: &str) -> String {
+            let mut short = String::with_capacity(type_name.len());
+            for ch in type_name.chars() {
+                short.push(ch);
+                if short.ends_with("::") {
+                    short.truncate(short.len() - 2);
+                    while short.ends_with(|c: char| c.is_alphanumeric() || c == '_') {
+                        short.pop();
+                    }
+                }
+            }
+            short
+        }
+
+        let rows = self.0.iter()
+            .map(|r| [
+                r.method.as_str().to_owned(),
+                r.path.clone(),
+                short(r.handler),
+                r.fangs.iter().map(|f| short(f)).collect::<Vec<_>>().join(", "),
+            ])
+            .collect::<Vec<_>>();
+
+        let header = ["METHOD", "PATH", "HANDLER", "FANGS"];
+        let mut widths = header.map(str::len);
+        for row in &rows {
+            for (w, cell) in widths.iter_mut().zip(row) {
+                *w = (*w).max(cell.len());
+            }
+        }
+
+        for row in [header.map(str::to_owned)].iter().chain(&rows) {
+            for (i, (w, cell)) in widths.iter().zip(row).enumerate() {
+                if i == row.len() - 1 {
+                    writeln!(f, "{cell}")?
+                } else {
+                    write!(f, "{cell:w$}  ")?
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
 macro_rules! HandlerSet {
     ($( $method:ident ),*) => {
         pub struct HandlerSet {
@@ -37,15 +114,22 @@ macro_rules! HandlerSet {
             $(
                 pub(crate) $method: Option<(Handler, HandlerMeta)>,
             )*
+            /// route-level fangs, from the most outer one
+            pub(crate) fangs: Vec<Arc<dyn Fangs>>,
         }
         
         impl HandlerSet {
             pub(crate) fn new(route_str: &'static str) -> Self {
+                Self::from_segments(RouteSegments::from_literal(route_str))
+            }
+
+            pub(crate) fn from_segments(route: RouteSegments) -> Self {
                 Self {
-                    route: RouteSegments::from_literal(route_str),
+                    route,
                     $(
                         $method: None,
                     )*
+                    fangs: Vec::new(),
                 }
             }
         }
@@ -62,99 +146,52 @@ macro_rules! HandlerSet {
     };
 } HandlerSet! { GET, PUT, POST, PATCH, DELETE }
 
-pub struct ByAnother {
-    pub(crate) route:  RouteSegments,
-    pub(crate) ohkami: Ohkami,
-}
-
-pub struct Dir {
-    pub(crate) route: &'static str,
-    pub(crate) files: Vec<(
-        Vec<String>,
-        std::fs::File,
-    )>,
-
-    /*=== config ===*/
-
-    /// File extensions (leading `.` trimmed) that should not be appeared in handling path
-    pub(crate) omit_extensions: Option<Box<[&'static str]>>,
-}
-impl Dir {
-    fn new(route: &'static str, dir_path: std::path::PathBuf) -> std::io::Result<Self> {
-        let dir_path = dir_path.canonicalize()?;
-
-        if !dir_path.is_dir() {
-            return Err(std::io::Error::new(
-                std::io::ErrorKind::InvalidInput,
-                format!("{} is not directory", dir_path.display()))
-            )
-        }
-
-        let mut files = Vec::new(); {
-            fn fetch_entries(
-                dir: std::path::PathBuf
-            ) -> std::io::Result<Vec<std::path::PathBuf>> {
-                dir.read_dir()?
-                    .map(|de| de.map(|de| de.path()))
-                    .collect()
-            }
-
-            let mut entries = fetch_entries(dir_path.clone())?;
-            while let Some(entry) = entries.pop() {
-                if entry.is_file() {
-                    let path_Segments = entry.canonicalize()?
-                        .components()
-                        .skip(dir_path.components().count())
-                        .map(|c| c.as_os_str().to_os_string()
-                            .into_string()
-                            .map_err(|os_string| std::io::Error::new(
-                                std::io::ErrorKind::InvalidData,
-                                format!("Can't read a path segment `{}`", os_string.as_encoded_bytes().escape_ascii())
-                            ))
-                        )
-                        .collect::<std::io::Result<Vec<_>>>()?;
-
-                    if path_Segments.last().unwrap().starts_with('.') {
-                        crate::warning!("\
-                            =========\n\
-                            [WARNING] `Route::Dir`: found `{}` in directory `{}`, \
-                            are you sure to serve this file？\n\
-                            =========\n",
-                            entry.display(),
-                            dir_path.display(),
-                        )
-                    }
-
-                    files.push((
-                        path_Segments,
-                        std::fs::File::open(entry)?
-                    ));
-
-                } else if entry.is_dir() {
-                    entries.append(&mut fetch_entries(entry)?)
-
-                } else {
-                    continue
-                }
-            }
-        }
-
-        Ok(Self {
-            route,
-            files,
-
-            omit_extensions: None,
-        })
-    }
-
-    pub fn omit_extensions<const N: usize>(mut self, target_extensions: [&'static str; N]) -> Self {
-        self.omit_extensions = Some(Box::new(
-            target_extensions.map(|ext| ext.trim_start_matches('.'))
-        ));
+impl HandlerSet {
+    /// Apply `fangs` to all handlers of this route, without
+    /// wrapping them in a nested `Ohkami`.
+    /// 
+    /// These fangs are applied *inside* the fangs of `Ohkami`s and
+    /// *outside* the local fangs of each handler like `(Auth, handler)`.
+    /// `.with_fangs(A).with_fangs(B)` is the same as `.with_fangs((A, B))`.
+    /// 
+    /// The auto-generated `OPTIONS` handler ( for CORS preflight etc. )
+    /// is not affected.
+    /// 
+    /// ```
+    /// use ohkami::prelude::*;
+    /// 
+    /// # #[derive(Clone)] struct Auth;
+    /// # impl FangAction for Auth {}
+    /// # #[derive(Clone)] struct Logger;
+    /// # impl FangAction for Logger {}
+    /// # async fn get_item() {}
+    /// # async fn update_item() {}
+    /// # async fn list_items() {}
+    /// # async fn create_item() {}
+    /// # let _ =
+    /// Ohkami::new((Logger,
+    ///     "/items/:id"
+    ///         .GET(get_item)
+    ///         .PUT(update_item)
+    ///         .with_fangs(Auth),
+    ///     
+    ///     // or, to protect only `POST /items` :
+    ///     "/items"
+    ///         .GET(list_items)
+    ///         .POST((Auth, create_item)),
+    /// ))
+    /// # ;
+    /// ```
+    pub fn with_fangs(mut self, fangs: impl Fangs + 'static) -> Self {
+        self.fangs.push(Arc::new(fangs));
         self
     }
 }
 
+pub struct ByAnother {
+    pub(crate) route:  RouteSegments,
+    pub(crate) ohkami: Ohkami,
+}
 
 macro_rules! Route {
     ($( $method:ident ),*) => {
@@ -194,9 +231,9 @@ macro_rules! Route {
                 fn $method<T>(self, handler: impl IntoHandler<T>) -> HandlerSet;
             )*
 
-            fn By(self, another: Ohkami) -> ByAnother;
+            fn By(self, another: impl Into<Ohkami>) -> ByAnother;
 
-            fn Dir(self, static_files_dir_path: &'static str) -> Dir;
+            fn Dir(self, static_files: impl DirSource) -> Dir;
         }
 
         impl Route for &'static str {
@@ -206,21 +243,18 @@ macro_rules! Route {
                 }
             )*
 
-            fn By(self, another: Ohkami) -> ByAnother {
+            fn By(self, another: impl Into<Ohkami>) -> ByAnother {
                 ByAnother {
                     route:  RouteSegments::from_literal(self),
-                    ohkami: another,
+                    ohkami: another.into(),
                 }
             }
 
-            fn Dir(self, path: &'static str) -> Dir {
+            fn Dir(self, static_files: impl DirSource) -> Dir {
                 // Check `self` is valid route
                 let _ = RouteSegments::from_literal(self);
 
-                match Dir::new(
-                    self,
-                    path.into()
-                ) {
+                match static_files.into_dir(self) {
                     Ok(dir) => dir,
                     Err(e) => panic!("{e}")
                 }
@@ -230,7 +264,7 @@ macro_rules! Route {
 } Route! { GET, PUT, POST, PATCH, DELETE }
 
 
-trait RoutingItem {
+pub(super) trait RoutingItem {
     fn apply(self, router: &mut Router);
 }
 const _: () = {
@@ -255,111 +289,6 @@ const _: () = {
         }
     }
 
-    impl RoutingItem for Dir {
-        fn apply(self, router: &mut Router) {
-            #[derive(Clone)]
-            struct StaticFileHandler {
-                mime:     &'static str,
-                content:  std::sync::Arc<Vec<u8>>,
-            }
-            const _: () = {
-                impl StaticFileHandler {
-                    fn new(path_Segments: &[String], file: std::fs::File) -> Result<Self, String> {
-                        let filename = path_Segments.last()
-                            .ok_or_else(|| format!("[.Dir] got empty file path"))?;
-                        let (_, extension) = filename.rsplit_once('.')
-                            .ok_or_else(|| format!("[.Dir] got `{filename}`: In current version, ohkami doesn't support serving files that have no extenstion"))?;
-                        let mime = ohkami_lib::mime::get_by_extension(extension)
-                            .ok_or_else(|| format!("[.Dir] got `{filename}`: ohkami doesn't know extension `{extension}`"))?;
-
-                        let mut content = vec![
-                            u8::default();
-                            file.metadata().unwrap().len() as usize
-                        ]; {use std::io::Read;
-                            let mut file = file;
-                            file.read_exact(&mut content)
-                                .map_err(|e| e.to_string())?;
-                        }
-
-                        if mime.starts_with("text/")
-                        && std::str::from_utf8(&content).is_err() {
-                            return Err(format!("[.Dir] got `{filename}`: Ohkami doesn't support non UTF-8 text file"))
-                        }
-
-                        Ok(Self { mime, content:std::sync::Arc::new(content) })
-                    }
-                }
-                
-                impl IntoHandler<std::fs::File> for StaticFileHandler {
-                    fn n_params(&self) -> usize {0}
-                
-                    fn into_handler(self) -> Handler {
-                        let this: &'static StaticFileHandler
-                            = Box::leak(Box::new(self));
-
-                        Handler::new(|_| Box::pin(async {
-                            let mut res = crate::Response::OK();
-                            {
-                                res.headers.set().ContentType(this.mime);
-                                res.content = Content::Payload({
-                                    let content: &'static [u8] = &this.content;
-                                    content.into()
-                                });
-                            }
-                            res
-                        }), #[cfg(feature="openapi")] {use crate::openapi;
-                            openapi::Operation::with(openapi::Responses::new([(
-                                200,
-                                openapi::Response::when("OK")
-                                    .content(this.mime, openapi::string().format("binary"))
-                            )]))
-                        })
-                    }
-                }
-            };
-
-            #[cfg(feature="DEBUG")]
-            println!{ "[Dir] .files = {:#?}", self.files }
-
-            let mut register = |path: Vec<String>, handler: StaticFileHandler| router.register_handlers(
-                HandlerSet::new(Box::leak({
-                    let base_path = self.route.trim_end_matches('/').to_string();
-                    match &*path.join("/") {
-                        ""   => if !base_path.is_empty() {base_path} else {"/".into()},
-                        some => base_path + "/" + some,
-                    }
-                }.into_boxed_str())).GET(handler)
-            );
-
-            for (mut path, file) in self.files {
-                let mut handler = match StaticFileHandler::new(&path, file) {
-                    Ok(h) => h,
-                    Err(msg) => panic!("{msg}")
-                };
-
-                if matches!(&**path.last().unwrap(), "index.html") {
-                    if !(self.omit_extensions.as_ref().is_some_and(|exts| exts.contains(&"html"))) {
-                        register(path.clone(), handler.clone());
-                    }
-
-                    path.pop();
-                }
-
-                if let Some(exts) = self.omit_extensions.as_ref() {
-                    for ext in exts.iter() {
-                        if let Some(filename) = path.last().and_then(|p| p.strip_suffix(&format!(".{ext}"))) {
-                            let filename_len = filename.len();
-                            path.last_mut().unwrap().truncate(filename_len);
-                            break
-                        }
-                    }
-                }
-
-                register(path, handler);
-            }
-        }
-    }
-
     /// This is for better developer experience.
     /// 
     /// If we don't impl `Routes` `&str`, ohkami users
@@ -0,0 +1,548 @@
+#![allow(non_snake_case)]
+
+use super::routing::{HandlerSet, HandlerMeta};
+use crate::fang::{BoxedFPC, FangProc, handler::{Handler, IntoHandler}};
+use crate::header::append;
+use crate::router::{base::Router, segments::RouteSegments};
+use crate::{Ohkami, Request, Method};
+
+#[cfg(feature="openapi")]
+use crate::openapi;
+
+
+/// Condition for [`Versioned`] to pick a variant
+#[derive(Clone, Copy)]
+pub struct Version(Condition);
+
+#[derive(Clone, Copy)]
+enum Condition {
+    Accept(&'static str),
+    Header(&'static str, &'static str),
+    Custom(fn(&Request) -> bool),
+}
+
+impl Version {
+    /// matches when `Accept` header lists `media_type`, like
+    /// `Accept: application/vnd.acme.v2+json`
+    ///
+    /// When this is matched and the media type has a structured syntax suffix
+    /// ( like `+json` ) and the variant responds the corresponding content type
+    /// ( like `application/json` ), `Content-Type` is set to the `media_type`.
+    pub const fn accept(media_type: &'static str) -> Self {
+        Self(Condition::Accept(media_type))
+    }
+
+    /// matches when the header has exactly the value, like `Api-Version: 2`
+    pub const fn header(name: &'static str, value: &'static str) -> Self {
+        Self(Condition::Header(name, value))
+    }
+
+    /// matches when `predicate` returns `true`
+    pub const fn custom(predicate: fn(&Request) -> bool) -> Self {
+        Self(Condition::Custom(predicate))
+    }
+
+    fn matches(&self, req: &Request) -> bool {
+        match self.0 {
+            Condition::Accept(media_type) => req.headers.Accept().is_some_and(|accept| {
+                accept.split(',').any(|media_range| {
+                    let mut parts = media_range.split(';').map(str::trim);
+                    parts.next().is_some_and(|t| t.eq_ignore_ascii_case(media_type))
+                    && !parts.any(|param| param
+                        .strip_prefix("q=")
+                        .is_some_and(|q| q.parse::<f32>().is_ok_and(|q| q <= 0.))
+                    )
+                })
+            }),
+            Condition::Header(name, value) => req.headers.get(name).is_some_and(|v| v.trim() == value),
+            Condition::Custom(predicate) => predicate(req),
+        }
+    }
+
+    /// header name to be listed in `Vary`
+    fn vary(&self) -> Option<&'static str> {
+        match self.0 {
+            Condition::Accept(_) => Some("Accept"),
+            Condition::Header(name, _) => Some(name),
+            Condition::Custom(_) => None,
+        }
+    }
+}
+
+/// # Versioned handlers or `Ohkami`s
+///
+/// Picks the first variant whose [`Version`] matches the request, or the default one.
+///
+/// Each response gets `Vary` listing the headers versions depend on.
+/// With `openapi` feature, the variants are documented in one operation:
+/// contents of `Version::accept` variants under their media types, and
+/// headers of `Version::header` variants as header parameters.
+///
+/// ```
+/// use ohkami::prelude::*;
+/// use ohkami::{Versioned, Version};
+///
+/// # async fn get_user_v1(id: u32) {}
+/// # async fn get_user_v2(id: u32) {}
+/// # fn users_v1() -> Ohkami {Ohkami::new(())}
+/// # fn users_v2() -> Ohkami {Ohkami::new(())}
+/// # let _ =
+/// Ohkami::new((
+///     "/users/:id"
+///         .GET(Versioned::new(get_user_v1)
+///             .when(Version::accept("application/vnd.acme.v2+json"), get_user_v2)
+///             .when(Version::header("Api-Version", "2"), get_user_v2)
+///         ),
+///
+///     "/api/users".By(Versioned::by(users_v1())
+///         .when(Version::header("Api-Version", "2"), users_v2())
+///     ),
+/// ))
+/// # ;
+/// ```
+pub struct Versioned<V> {
+    default:  V,
+    variants: Vec<(Version, V)>,
+    /// from the default one
+    metas:    Vec<HandlerMeta>,
+}
+
+impl Versioned<Handler> {
+    pub fn new<T, H: IntoHandler<T>>(default: H) -> Self {
+        let meta = HandlerMeta::new::<T, H>(&default);
+        Self {
+            default:  default.into_handler(),
+            variants: Vec::new(),
+            metas:    vec![meta],
+        }
+    }
+
+    pub fn when<T, H: IntoHandler<T>>(mut self, version: Version, handler: H) -> Self {
+        self.metas.push(HandlerMeta::new::<T, H>(&handler));
+        self.variants.push((version, handler.into_handler()));
+        self
+    }
+}
+
+impl Versioned<Ohkami> {
+    pub fn by(default: Ohkami) -> Self {
+        Self {
+            default,
+            variants: Vec::new(),
+            metas:    Vec::new(),
+        }
+    }
+
+    pub fn when(mut self, version: Version, ohkami: Ohkami) -> Self {
+        self.variants.push((version, ohkami));
+        self
+    }
+}
+
+const _: () = {
+    impl IntoHandler<Versioned<Handler>> for Versioned<Handler> {
+        fn n_params(&self) -> usize {
+            self.metas.iter().map(|meta| meta.n_params).max().unwrap_or(0)
+        }
+
+        fn __handler_name__(&self) -> &'static str {
+            self.metas[0].name
+        }
+        fn __fang_names__(&self) -> Vec<&'static str> {
+            self.metas[0].fangs.clone()
+        }
+
+        fn into_handler(self) -> Handler {
+            struct Dispatch {
+                default:  BoxedFPC,
+                variants: Vec<(Version, BoxedFPC)>,
+                vary:     Vec<&'static str>,
+            }
+
+            let Self { default, variants, metas:_ } = self;
+
+            #[cfg(feature="openapi")]
+            let openapi_operation = {
+                let mut operation = default.openapi_operation;
+                for (version, handler) in &variants {
+                    let variant = handler.openapi_operation.clone();
+                    match version.0 {
+                        Condition::Accept(media_type) => {
+                            operation.merge_variant(variant, Some(media_type));
+                        }
+                        Condition::Header(name, _) => {
+                            operation.merge_variant(variant, None);
+                            operation.merge_variant(
+                                openapi::Operation::with(openapi::Responses::new([]))
+                                    .param(openapi::Parameter::maybe_in_header(name, openapi::string())),
+                                None
+                            );
+                        }
+                        Condition::Custom(_) => {
+                            operation.merge_variant(variant, None);
+                        }
+                    }
+                }
+                operation
+            };
+
+            let mut vary = Vec::new();
+            for name in variants.iter().filter_map(|(version, _)| version.vary()) {
+                if !vary.iter().any(|v: &&str| v.eq_ignore_ascii_case(name)) {
+                    vary.push(name);
+                }
+            }
+
+            let this: &'static Dispatch = Box::leak(Box::new(Dispatch {
+                default:  default.proc,
+                variants: variants.into_iter().map(|(version, h)| (version, h.proc)).collect(),
+                vary,
+            }));
+
+            Handler::new(move |req| {
+                let (proc, version) = this.variants.iter()
+                    .find(|(version, _)| version.matches(req))
+                    .map(|(version, proc)| (proc, Some(version)))
+                    .unwrap_or((&this.default, None));
+
+                Box::pin(async move {
+                    let mut res = proc.bite(req).await;
+
+                    if let Some(Condition::Accept(media_type)) = version.map(|v| v.0) {
+                        let suffix = media_type.rsplit_once('+').map(|(_, suffix)| suffix);
+                        let subtype = res.headers.ContentType()
+                            .and_then(|ct| ct.split(';').next()?.split_once('/'))
+                            .map(|(_, subtype)| subtype.trim());
+                        if suffix.is_some() && suffix == subtype {
+                            res.headers.set().ContentType(media_type);
+                        }
+                    }
+
+                    for name in &this.vary {
+                        res.headers.set().Vary(append(*name));
+                    }
+
+                    res
+                })
+            }, #[cfg(feature="openapi")] openapi_operation)
+        }
+    }
+
+    /// Routes of all the `Ohkami`s are merged, and for each route, the handler
+    /// is picked in the same way as `Versioned` handlers. When the default `Ohkami`
+    /// doesn't have the route, its fallback ( or `404 Not Found` with its fangs )
+    /// is the default.
+    ///
+    /// Unmatched requests are handled in the same way: by the fallback handlers
+    /// ( like `Dir`'s `spa_fallback` ) or `404 Not Found` of the picked `Ohkami`.
+    ///
+    /// Variants are matched by the route literals, so write the same route
+    /// ( including the names of path params ) in each `Ohkami`.
+    impl From<Versioned<Ohkami>> for Ohkami {
+        fn from(versioned: Versioned<Ohkami>) -> Self {
+            const METHODS: [Method; 5] = [Method::GET, Method::PUT, Method::POST, Method::PATCH, Method::DELETE];
+
+            let Versioned { default, variants, metas:_ } = versioned;
+            let default  = default.into_router();
+            let variants = variants.into_iter()
+                .map(|(version, ohkami)| (version, ohkami.into_router()))
+                .collect::<Vec<_>>();
+
+            type Routes = Vec<(RouteSegments, Vec<(Method, Versioned<Handler>)>)>;
+            fn entry<'r>(routes: &'r mut Routes, default: &Router, route: RouteSegments, method: Method) -> &'r mut Versioned<Handler> {
+                let i = routes.iter().position(|(r, _)| *r == route).unwrap_or_else(|| {
+                    routes.push((route.clone(), Vec::new()));
+                    routes.len() - 1
+                });
+                let handlers = &mut routes[i].1;
+                let j = handlers.iter().position(|(m, _)| *m == method).unwrap_or_else(|| {
+                    handlers.push((method, Versioned {
+                        default:  default.fallback_handler(method, &route),
+                        variants: Vec::new(),
+                        metas:    vec![HandlerMeta {
+                            name:     "ohkami::Response::NotFound",
+                            n_params: 0,
+                            fangs:    Vec::new(),
+                        }],
+                    }));
+                    handlers.len() - 1
+                });
+                &mut handlers[j].1
+            }
+
+            let mut routes = Routes::new();
+            for (route, method, handler, meta) in default.handlers() {
+                let versioned = entry(&mut routes, &default, route, method);
+                versioned.default  = handler;
+                versioned.metas[0] = meta;
+            }
+            for (version, router) in &variants {
+                for (route, method, handler, meta) in router.handlers() {
+                    let versioned = entry(&mut routes, &default, route, method);
+                    versioned.variants.push((*version, handler));
+                    versioned.metas.push(meta);
+                }
+            }
+
+            let fallback = |method: Method, route: &RouteSegments| Versioned {
+                default:  default.fallback_handler(method, route),
+                variants: variants.iter()
+                    .map(|(version, router)| (*version, router.fallback_handler(method, route)))
+                    .collect(),
+                metas:    Vec::new(),
+            }.into_handler();
+
+            let mut ohkami = Ohkami::new(());
+            for (route, handlers) in routes {
+                let mut set = HandlerSet::from_segments(route);
+                for (method, versioned) in handlers {
+                    set = match method {
+                        Method::GET    => set.GET(versioned),
+                        Method::PUT    => set.PUT(versioned),
+                        Method::POST   => set.POST(versioned),
+                        Method::PATCH  => set.PATCH(versioned),
+                        Method::DELETE => set.DELETE(versioned),
+                        _ => set
+                    };
+                }
+                ohkami.router.register_handlers(set);
+            }
+
+            let root = RouteSegments::from_literal("/");
+            for method in METHODS {
+                ohkami.router.register_not_found(method, fallback(method, &root));
+            }
+            let mut fallback_routes = Vec::<RouteSegments>::new();
+            for route in std::iter::once(&default).chain(variants.iter().map(|(_, router)| router))
+                .flat_map(|router| router.fallback_routes())
+            {
+                if route != root && !fallback_routes.contains(&route) {
+                    fallback_routes.push(route);
+                }
+            }
+            for route in fallback_routes {
+                let handler = fallback(Method::GET, &route);
+                ohkami.router.register_fallback(route, handler);
+            }
+
+            ohkami
+        }
+    }
+};
+
+#[cfg(all(test, feature="__rt_native__", feature="DEBUG"))]
+mod test {
+    use crate::prelude::*;
+    use crate::testing::*;
+    use super::{Versioned, Version};
+
+    #[derive(serde::Serialize)]
+    struct User { id: u32, name: &'static str }
+    #[cfg(feature="openapi")]
+    impl crate::openapi::Schema for User {
+        fn schema() -> impl Into<crate::openapi::schema::SchemaRef> {
+            crate::openapi::object()
+                .property("id", crate::openapi::integer())
+                .property("name", crate::openapi::string())
+        }
+    }
+
+    #[test] fn versioned_handlers() {
+        async fn get_user_v1(id: u32) -> String {
+            format!("user {id}")
+        }
+        async fn get_user_v2(id: u32) -> JSON<User> {
+            JSON(User { id, name: "ohkami" })
+        }
+
+        let t = Ohkami::new((
+            "/users/:id"
+                .GET(Versioned::new(get_user_v1)
+                    .when(Version::accept("application/vnd.acme.v2+json"), get_user_v2)
+                    .when(Version::header("Api-Version", "2"), get_user_v2)
+                ),
+        )).test();
+
+        crate::__rt__::testing::block_on(async {
+            {
+                let req = TestRequest::GET("/users/42");
+                let res = t.oneshot(req).await;
+                assert_eq!(res.status(), Status::OK);
+                assert_eq!(res.text(), Some("user 42"));
+                assert_eq!(res.header("Vary"), Some("Accept, Api-Version"));
+            }
+            {
+                let req = TestRequest::GET("/users/42")
+                    .header("Accept", "text/html, application/vnd.acme.v2+json;q=0.9");
+                let res = t.oneshot(req).await;
+                assert_eq!(res.status(), Status::OK);
+                assert_eq!(res.header("Content-Type"), Some("application/vnd.acme.v2+json"));
+                assert_eq!(res.content("application/vnd.acme.v2+json"), Some(&br#"{"id":42,"name":"ohkami"}"#[..]));
+            }
+            {
+                let req = TestRequest::GET("/users/42")
+                    .header("Accept", "application/vnd.acme.v2+json;q=0");
+                let res = t.oneshot(req).await;
+                assert_eq!(res.text(), Some("user 42"));
+            }
+            {
+                let req = TestRequest::GET("/users/42")
+                    .header("Api-Version", "2");
+                let res = t.oneshot(req).await;
+                assert_eq!(res.header("Content-Type"), Some("application/json"));
+                assert_eq!(res.json::<serde_json::Value>().unwrap().unwrap(), serde_json::json!({"id":42,"name":"ohkami"}));
+            }
+        });
+    }
+
+    #[test] fn versioned_ohkamis() {
+        #[derive(Clone)] struct V2Only;
+        impl FangAction for V2Only {
+            async fn back<'a>(&'a self, res: &'a mut Response) {
+                res.headers.set().x("X-V2", "true");
+            }
+        }
+
+        let v1 = Ohkami::new((
+            "/users".GET(|| async {"list users v1"}),
+            "/users/:id".GET(|id: u32| async move {format!("get user {id} v1")}),
+        ));
+        let v2 = Ohkami::new((V2Only,
+            "/users".GET(|| async {"list users v2"}).POST(|| async {"create user v2"}),
+        ));
+
+        let o = Ohkami::new((
+            "/api".By(Versioned::by(v1)
+                .when(Version::header("Api-Version", "2"), v2)
+            ),
+        ));
+
+        let routes = o.routes();
+        assert_eq!(
+            routes.iter().map(|r| (r.method, &*r.path)).collect::<Vec<_>>(),
+            [(Method::GET, "/api/users"), (Method::POST, "/api/users"), (Method::GET, "/api/users/:id")]
+        );
+
+        let t = o.test();
+        crate::__rt__::testing::block_on(async {
+            {
+                let req = TestRequest::GET("/api/users");
+                let res = t.oneshot(req).await;
+                assert_eq!(res.text(), Some("list users v1"));
+                assert_eq!(res.header("X-V2"), None);
+                assert_eq!(res.header("Vary"), Some("Api-Version"));
+            }
+            {
+                let req = TestRequest::GET("/api/users")
+                    .header("Api-Version", "2");
+                let res = t.oneshot(req).await;
+                assert_eq!(res.text(), Some("list users v2"));
+                assert_eq!(res.header("X-V2"), Some("true"));
+            }
+            {
+                let req = TestRequest::POST("/api/users");
+                let res = t.oneshot(req).await;
+                assert_eq!(res.status(), Status::NotFound);
+            }
+            {
+                let req = TestRequest::POST("/api/users")
+                    .header("Api-Version", "2");
+                let res = t.oneshot(req).await;
+                assert_eq!(res.text(), Some("create user v2"));
+            }
+            {
+                let req = TestRequest::GET("/api/users/7")
+                    .header("Api-Version", "2");
+                let res = t.oneshot(req).await;
+                assert_eq!(res.text(), Some("get user 7 v1"));
+            }
+        });
+    }
+
+    #[test] fn versioned_ohkamis_fallbacks() {
+        #[derive(Clone)] struct V2Only;
+        impl FangAction for V2Only {
+            async fn back<'a>(&'a self, res: &'a mut Response) {
+                res.headers.set().x("X-V2", "true");
+            }
+        }
+
+        let dir = std::env::temp_dir().join(format!("ohkami-versioning-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("index.html"), "<p>app v2</p>").unwrap();
+        let dir: &'static str = dir.into_os_string().into_string().unwrap().leak();
+
+        let v1 = Ohkami::new((
+            "/users".GET(|| async {"list users v1"}),
+        ));
+        let v2 = Ohkami::new((V2Only,
+            "/users".GET(|| async {"list users v2"}),
+            "/app".Dir(dir).spa_fallback("index.html"),
+        ));
+
+        let t = Ohkami::new((
+            "/api".By(Versioned::by(v1)
+                .when(Version::header("Api-Version", "2"), v2)
+            ),
+        )).test();
+
+        crate::__rt__::testing::block_on(async {
+            {
+                let req = TestRequest::GET("/api/app/settings")
+                    .header("Api-Version", "2");
+                let res = t.oneshot(req).await;
+                assert_eq!(res.html(), Some("<p>app v2</p>"));
+                assert_eq!(res.header("X-V2"), Some("true"));
+            }
+            {
+                let req = TestRequest::GET("/api/app/settings");
+                let res = t.oneshot(req).await;
+                assert_eq!(res.status(), Status::NotFound);
+                assert_eq!(res.header("Vary"), Some("Api-Version"));
+            }
+            {
+                let req = TestRequest::DELETE("/api/unknown")
+                    .header("Api-Version", "2");
+                let res = t.oneshot(req).await;
+                assert_eq!(res.status(), Status::NotFound);
+                assert_eq!(res.header("X-V2"), Some("true"));
+            }
+            {
+                let req = TestRequest::GET("/api/unknown");
+                let res = t.oneshot(req).await;
+                assert_eq!(res.status(), Status::NotFound);
+                assert_eq!(res.header("X-V2"), None);
+            }
+        });
+    }
+
+    #[cfg(feature="openapi")]
+    #[test] fn versioned_openapi() {
+        async fn get_user_v1(id: u32) -> String {
+            format!("user {id}")
+        }
+        async fn get_user_v2(id: u32) -> JSON<User> {
+            JSON(User { id, name: "ohkami" })
+        }
+
+        let o = Ohkami::new((
+            "/users/:id"
+                .GET(Versioned::new(get_user_v1)
+                    .when(Version::accept("application/vnd.acme.v2+json"), get_user_v2)
+                    .when(Version::header("Api-Version", "2"), get_user_v2)
+                ),
+        ));
+        let doc = String::from_utf8(o.__openapi_document_bytes__(crate::openapi::OpenAPI {
+            title: "", version: "", servers: &[]
+        })).unwrap();
+        let doc: serde_json::Value = serde_json::from_str(&doc).unwrap();
+
+        let operation = &doc["paths"]["/users/{id}"]["get"];
+        assert_eq!(operation["operationId"], "get_user_v1");
+        let content = operation["responses"]["200"]["content"].as_object().unwrap();
+        assert!(content.contains_key("text/plain"));
+        assert!(content.contains_key("application/vnd.acme.v2+json"));
+        assert!(content.contains_key("application/json"));
+        assert!(operation["parameters"].as_array().unwrap().iter().any(|p| p["in"] == "header" && p["name"] == "Api-Version"));
+    }
+}
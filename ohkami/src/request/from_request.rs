@@ -1,5 +1,6 @@
 use std::borrow::Cow;
-use crate::{util::ErrorMessage, IntoResponse, Request, Response};
+use crate::{IntoResponse, Request, Response, Status};
+use super::Rejection;
 
 #[cfg(feature="openapi")]
 use crate::openapi;
@@ -108,16 +109,8 @@ pub trait FromParam<'p>: Sized {
 
     #[inline(always)]
     fn from_raw_param(raw_param: &'p [u8]) -> Result<Self, Response> {
-        Self::from_param(
-            ohkami_lib::percent_decode_utf8(raw_param)
-                .map_err(|_e| {
-                    #[cfg(debug_assertions)] crate::warning!(
-                        "Failed to decode percent encoded param `{}`: {_e}",
-                        raw_param.escape_ascii()
-                    );
-                    Response::InternalServerError()
-                })?
-        ).map_err(IntoResponse::into_response)
+        Self::from_param(decode_param::<Self>(raw_param).map_err(IntoResponse::into_response)?)
+            .map_err(IntoResponse::into_response)
     }
 
     #[cfg(feature="openapi")]
@@ -125,6 +118,18 @@ pub trait FromParam<'p>: Sized {
         openapi::Parameter::in_path(openapi::string())
     }
 }
+
+#[inline(always)]
+pub(crate) fn decode_param<P>(raw_param: &[u8]) -> Result<Cow<'_, str>, Rejection> {
+    ohkami_lib::percent_decode_utf8(raw_param)
+        .map_err(|e| {
+            #[cfg(debug_assertions)] crate::warning!(
+                "Failed to decode percent encoded param `{}`: {e}",
+                raw_param.escape_ascii()
+            );
+            Rejection::new(Status::BadRequest, super::extractor_name::<P>(), "Invalid percent encoded path param")
+        })
+}
 const _: () = {
     impl<'p> FromParam<'p> for String {
         type Error = std::convert::Infallible;
@@ -146,7 +151,7 @@ const _: () = {
         }
     }
     impl<'p> FromParam<'p> for &'p str {
-        type Error = ErrorMessage;
+        type Error = Rejection;
 
         #[inline(always)]
         fn from_param(param: Cow<'p, str>) -> Result<Self, Self::Error> {
@@ -154,12 +159,12 @@ const _: () = {
                 Cow::Borrowed(s) => Ok(s),
                 Cow::Owned(_) => Err({
                     #[cold] #[inline(never)]
-                    fn unexpected(param: &str) -> ErrorMessage {                        
+                    fn unexpected(param: &str) -> Rejection {                        
                         crate::warning!("\
                             `&str` can't handle percent encoded parameters. \
                             Use `Cow<'_, str>` or `String` to handle them. \
                         ");
-                        ErrorMessage(format!(    
+                        Rejection::new(Status::InternalServerError, "&str", format!(
                             "Unexpected path params `{param}`: percent encoded"
                         ))
                     } unexpected(&param)
@@ -172,14 +177,14 @@ const _: () = {
         ($( $unsigned_int:ty ),*) => {
             $(
                 impl<'p> FromParam<'p> for $unsigned_int {
-                    type Error = ErrorMessage;
+                    type Error = Rejection;
 
                     fn from_param(param: Cow<'p, str>) -> Result<Self, Self::Error> {
                         ::byte_reader::Reader::new(param.as_bytes())
                             .read_uint()
                             .map(|i| Self::try_from(i).ok())
                             .flatten()
-                            .ok_or_else(|| ErrorMessage(format!("Unexpected path param")))
+                            .ok_or_else(|| Rejection::new(Status::BadRequest, stringify!($unsigned_int), "Unexpected path param"))
                     }
 
                     #[cfg(feature="openapi")]
@@ -195,14 +200,14 @@ const _: () = {
         ($( $signed_int:ty ),*) => {
             $(
                 impl<'p> FromParam<'p> for $signed_int {
-                    type Error = ErrorMessage;
+                    type Error = Rejection;
 
                     fn from_param(param: Cow<'p, str>) -> Result<Self, Self::Error> {
                         ::byte_reader::Reader::new(param.as_bytes())
                             .read_int()
                             .map(|i| Self::try_from(i).ok())
                             .flatten()
-                            .ok_or_else(|| ErrorMessage(format!("Unexpected path param")))
+                            .ok_or_else(|| Rejection::new(Status::BadRequest, stringify!($signed_int), "Unexpected path param"))
                     }
 
                     #[cfg(feature="openapi")]
@@ -225,15 +230,12 @@ pub trait FromBody<'req>: Sized {
     fn openapi_requestbody() -> impl Into<openapi::schema::SchemaRef>;
 }
 impl<'req, B: FromBody<'req>> FromRequest<'req> for B {
-    type Error = Response;
+    type Error = Rejection;
     fn from_request(req: &'req Request) -> Option<Result<Self, Self::Error>> {
-        #[cold] #[inline(never)]
-        fn reject(msg: impl std::fmt::Display) -> Response {
-            Response::BadRequest().with_text(msg.to_string())
-        }
-
         if req.headers.ContentType()?.starts_with(B::MIME_TYPE) {
-            Some(B::from_body(req.payload()?).map_err(reject))
+            Some(B::from_body(req.payload()?).map_err(|e| Rejection::new(
+                Status::BadRequest, super::extractor_name::<B>(), e
+            )))
         } else {
             None
         }
@@ -18,6 +18,10 @@ use context::Context;
 mod from_request; 
 pub use from_request::*;
 
+mod rejection;
+pub use rejection::Rejection;
+pub(crate) use rejection::{RejectionHook, extractor_name, handle_rejection};
+
 #[cfg(test)] mod _test_parse;
 #[cfg(test)] mod _test_extract;
 #[cfg(test)] mod _test_headers;
@@ -232,7 +236,7 @@ impl Request {
                 std::io::ErrorKind::ConnectionReset => Ok(None),
                 _ => Err((|err| {
                     crate::warning!("Failed to read stream: {err}");
-                    Response::InternalServerError()
+                    crate::typed::reject(crate::Status::InternalServerError, "")
                 })(e))
             },
             _ => ()
@@ -281,7 +285,7 @@ impl Request {
         };
         match content_length {
             0 => (),
-            PAYLOAD_LIMIT.. => return Err((|| Response::PayloadTooLarge())()),
+            PAYLOAD_LIMIT.. => return Err(crate::typed::reject(crate::Status::PayloadTooLarge, "")),
             _ => self.payload = Some(Request::read_payload(
                 stream,
                 r.remaining(),
@@ -416,14 +420,14 @@ impl Request {
         mut req: ::worker::Request,
         env:     ::worker::Env,
         ctx:     ::worker::Context,
-    ) -> Result<(), crate::Response> {use crate::Response;
+    ) -> Result<(), crate::Response> {
         self.context.load((ctx, env));
 
         self.method = Method::from_worker(req.method())
-            .ok_or_else(|| Response::NotImplemented().with_text("ohkami doesn't support `CONNECT`, `TRACE` method"))?;
+            .ok_or_else(|| crate::typed::reject(crate::Status::NotImplemented, "ohkami doesn't support `CONNECT`, `TRACE` method"))?;
 
         self.__url__.write(req.url()
-            .map_err(|_| Response::BadRequest().with_text("Invalid request URL"))?
+            .map_err(|_| crate::typed::reject(crate::Status::BadRequest, "Invalid request URL"))?
         );
         #[cfg(feature="DEBUG")] worker::console_debug!("Load __url__: {:?}", self.__url__);
 
@@ -437,7 +441,7 @@ impl Request {
         self.headers.take_over(req.headers());
 
         self.payload = Some(CowSlice::Own(req.bytes().await
-            .map_err(|_| Response::InternalServerError().with_text("Failed to read request payload"))?
+            .map_err(|_| crate::typed::reject(crate::Status::InternalServerError, "Failed to read request payload"))?
             .into()
         ));
 
@@ -17,6 +17,12 @@ impl QueryParams {
         Self(Slice::from_bytes(bytes))
     }
 
+    /// raw bytes of the query, not percent-decoded
+    #[cfg(feature="__rt_native__")]
+    #[inline(always)] pub(crate) fn raw(&self) -> &[u8] {
+        unsafe {self.0.as_bytes()}
+    }
+
     #[inline(always)] pub fn parse<'q, T: serde::Deserialize<'q>>(
         &'q self
     ) -> Result<T, impl serde::de::Error> {
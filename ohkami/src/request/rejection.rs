@@ -0,0 +1,147 @@
+use crate::{IntoResponse, Request, Response, Status};
+use crate::fang::SendSyncOnNative;
+use std::sync::Arc;
+
+#[cfg(feature="openapi")]
+use crate::openapi;
+
+
+/// Structured information about a request that a built-in extractor
+/// ( `JSON<T>`, `Query<T>`, path params, ... ) failed to extract.
+///
+/// By default a `Rejection` is responded as the same plain text (or
+/// [`Problem`](crate::typed::Problem)) as other built-in rejections,
+/// and [`OnRejection`](crate::fang::OnRejection) hooks it to build
+/// your own error response.
+///
+/// Custom `FromRequest` / `FromParam` impls can use `Rejection` as their
+/// `Error` by [`Rejection::new`] to go through the same hook.
+///
+/// ## Migration
+///
+/// The `Error` types of the built-in extractors are now `Rejection`:
+///
+/// - `FromBody` types ( `JSON<T>`, `URLEncoded<T>`, ... ) and `Query<T>`: was `Response`
+/// - `&str` and integer path params: was an internal error message type
+///
+/// Code naming these types, like `<JSON<T> as FromRequest>::Error`, needs
+/// to be updated. `Rejection` is `IntoResponse` and responds the same as
+/// before, so code just converting them into `Response` keeps working.
+#[derive(Debug, Clone)]
+pub struct Rejection {
+    status:    Status,
+    extractor: &'static str,
+    field:     Option<String>,
+    message:   String,
+}
+
+impl Rejection {
+    /// `field` is picked from `message` when it's a serde error like
+    /// ``missing field `name` ``. Set it explicitly by [`with_field`](Self::with_field)
+    /// if `message` doesn't tell it.
+    #[cold] #[inline(never)]
+    pub fn new(status: Status, extractor: &'static str, message: impl std::fmt::Display) -> Self {
+        let message = message.to_string();
+        let field = field_of(&message).map(String::from);
+        Self { status, extractor, field, message }
+    }
+
+    pub fn with_field(mut self, field: impl Into<String>) -> Self {
+        self.field = Some(field.into());
+        self
+    }
+
+    /// Status of the default response, `400 Bad Request` in most cases.
+    pub fn status(&self) -> Status {
+        self.status
+    }
+
+    /// Name of the extractor type like `"JSON"`, `"Query"`, `"u64"`.
+    pub fn extractor(&self) -> &'static str {
+        self.extractor
+    }
+
+    /// Name of the field the error is about, when the message tells it
+    /// (e.g. ``missing field `name` ``).
+    ///
+    /// This is best-effort: it's picked from the message text, so it's `None`
+    /// for errors like type mismatches whose message doesn't name the field,
+    /// and only the last segment for nested fields.
+    pub fn field(&self) -> Option<&str> {
+        self.field.as_deref()
+    }
+
+    /// Error message from the extractor, typically a serde error.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl IntoResponse for Rejection {
+    fn into_response(self) -> Response {
+        crate::typed::reject(self.status, self.message)
+    }
+
+    fn __rejection__(self) -> Result<Rejection, Self> {
+        Ok(self)
+    }
+
+    #[cfg(feature="openapi")]
+    fn openapi_responses() -> openapi::Responses {
+        openapi::Responses::new([(400, openapi::Response::when("Bad Request"))])
+    }
+}
+
+/// `std::any::type_name` without the module path and generics:
+/// `ohkami::format::JSON<app::User>` -> `JSON`
+pub(crate) fn extractor_name<T>() -> &'static str {
+    let type_name = std::any::type_name::<T>();
+    let type_path = type_name.split_once('<').map_or(type_name, |(path, _)| path);
+    type_path.rsplit_once("::").map_or(type_path, |(_, ident)| ident)
+}
+
+/// Picks `name` from serde's ``missing field `name` ``, ``unknown field `name`, expected ...``
+/// or ``duplicate field `name` ``.
+fn field_of(message: &str) -> Option<&str> {
+    ["missing field `", "unknown field `", "duplicate field `"].into_iter().find_map(|prefix| {
+        let (_, rest) = message.split_once(prefix)?;
+        let (field, _) = rest.split_once('`')?;
+        Some(field)
+    })
+}
+
+pub(crate) trait RejectionHandler: Fn(Rejection) -> Response + SendSyncOnNative + 'static {}
+impl<F: Fn(Rejection) -> Response + SendSyncOnNative + 'static> RejectionHandler for F {}
+
+#[derive(Clone)]
+pub(crate) struct RejectionHook(pub(crate) Arc<dyn RejectionHandler>);
+
+/// Responds an extraction error, passing it to the `OnRejection` hook
+/// of the request if it's a `Rejection` and the hook exists.
+#[cold] #[inline(never)]
+pub(crate) fn handle_rejection(req: &Request, e: impl IntoResponse) -> Response {
+    match e.__rejection__() {
+        Ok(rejection) => match req.context.get::<RejectionHook>() {
+            Some(RejectionHook(hook)) => hook(rejection),
+            None => rejection.into_response(),
+        },
+        Err(e) => e.into_response(),
+    }
+}
+
+
+#[cfg(test)]
+#[test] fn test_rejection_info() {
+    let r = Rejection::new(Status::BadRequest, "JSON", "missing field `name` at line 1 column 2");
+    assert_eq!(r.field(), Some("name"));
+
+    let r = Rejection::new(Status::BadRequest, "Query", "unknown field `limt`, expected `limit` or `offset`");
+    assert_eq!(r.field(), Some("limt"));
+
+    let r = Rejection::new(Status::BadRequest, "JSON", "invalid type: string \"a\", expected u8 at line 1 column 12");
+    assert_eq!(r.field(), None);
+    assert_eq!(r.with_field("age").field(), Some("age"));
+
+    assert_eq!(extractor_name::<crate::format::JSON<Vec<String>>>(), "JSON");
+    assert_eq!(extractor_name::<u64>(), "u64");
+}
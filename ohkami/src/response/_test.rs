@@ -211,3 +211,132 @@ fn test_stream_response() {
         \r\n\
     ");
 }
+
+#[test]
+fn test_body_stream_response() {
+    use ohkami_lib::stream;
+
+    let mut res = Response::OK()
+        .with_body_stream(stream::queue(|mut q| async move {
+            q.push(Ok::<_, std::io::Error>(&b"id,name\n"[..]));
+            q.push(Ok(&b""[..]));
+            q.push(Ok(&b"1,ohkami\n"[..]));
+        }))
+        .with_headers(|h| h
+            .ContentType("text/csv")
+            .Server("ohkami")
+        );
+    assert_response_bytes_eq!(res, "\
+        HTTP/1.1 200 OK\r\n\
+        Date: {NOW}\r\n\
+        Content-Type: text/csv\r\n\
+        Transfer-Encoding: chunked\r\n\
+        Server: ohkami\r\n\
+        \r\n\
+        8\r\n\
+        id,name\n\
+        \r\n\
+        9\r\n\
+        1,ohkami\n\
+        \r\n\
+        0\r\n\
+        \r\n\
+    ");
+
+    let mut res = Response::OK()
+        .with_sized_body_stream(17, stream::queue(|mut q| async move {
+            q.push(Ok::<_, std::io::Error>(b"id,name\n".to_vec()));
+            q.push(Ok(b"1,ohkami\n".to_vec()));
+        }));
+    assert_response_bytes_eq!(res, "\
+        HTTP/1.1 200 OK\r\n\
+        Date: {NOW}\r\n\
+        Content-Length: 17\r\n\
+        Content-Type: application/octet-stream\r\n\
+        \r\n\
+        id,name\n\
+        1,ohkami\n\
+    ");
+
+    /* longer than `Content-Length`: truncated and the connection is closed */
+    let res = Response::OK()
+        .with_sized_body_stream(10, stream::queue(|mut q| async move {
+            q.push(Ok::<_, std::io::Error>(b"id,name\n".to_vec()));
+            q.push(Ok(b"1,ohkami\n".to_vec()));
+        }));
+    let mut res_bytes = Vec::new();
+    crate::__rt__::testing::block_on(async {
+        assert!(res.send(&mut res_bytes).await.is_err());
+    });
+    assert!(res_bytes.ends_with(b"\r\n\r\nid,name\n1,"));
+
+    /* shorter than `Content-Length`: the connection is closed */
+    let res = Response::OK()
+        .with_sized_body_stream(20, stream::queue(|mut q| async move {
+            q.push(Ok::<_, std::io::Error>(b"id,name\n".to_vec()));
+        }));
+    let mut res_bytes = Vec::new();
+    crate::__rt__::testing::block_on(async {
+        let err = res.send(&mut res_bytes).await.err().unwrap();
+        assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+    });
+    assert!(res_bytes.ends_with(b"\r\n\r\nid,name\n"));
+
+    let res = Response::OK()
+        .with_body_stream(stream::queue(|mut q| async move {
+            q.push(Ok(&b"partial"[..]));
+            q.push(Err(std::io::Error::other("failed to read")));
+            q.push(Ok(&b"never sent"[..]));
+        }));
+    let mut res_bytes = Vec::new();
+    crate::__rt__::testing::block_on(async {
+        assert!(res.send(&mut res_bytes).await.is_err());
+    });
+    assert!(res_bytes.ends_with(b"7\r\npartial\r\n"));
+}
+
+#[test]
+fn test_trailers_response() {
+    use crate::Trailers;
+    use ohkami_lib::stream;
+
+    let trailers = Trailers::new();
+    let mut res = Response::OK()
+        .with_headers(|h| h.Trailer("X-Checksum, Grpc-Status"))
+        .with_trailers(trailers.clone())
+        .with_body_stream(stream::queue(|mut q| async move {
+            q.push(Ok::<_, std::io::Error>(&b"hello"[..]));
+            trailers.set("X-Checksum", "532");
+            trailers.set("Grpc-Status", "1");
+            trailers.set("grpc-status", "0");
+        }));
+    assert_response_bytes_eq!(res, "\
+        HTTP/1.1 200 OK\r\n\
+        Date: {NOW}\r\n\
+        Trailer: X-Checksum, Grpc-Status\r\n\
+        Content-Type: application/octet-stream\r\n\
+        Transfer-Encoding: chunked\r\n\
+        \r\n\
+        5\r\n\
+        hello\r\n\
+        0\r\n\
+        X-Checksum: 532\r\n\
+        Grpc-Status: 0\r\n\
+        \r\n\
+    ");
+
+    /* not sent for a non-chunked body */
+    let trailers = Trailers::new();
+    trailers.set("X-Checksum", "532");
+    let mut res = Response::OK()
+        .with_text("hello")
+        .with_trailers(trailers);
+    assert_response_bytes_eq!(res, "\
+        HTTP/1.1 200 OK\r\n\
+        Date: {NOW}\r\n\
+        Content-Length: 5\r\n\
+        Content-Type: text/plain; charset=UTF-8\r\n\
+        \r\n\
+        hello\
+    ");
+}
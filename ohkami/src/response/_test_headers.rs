@@ -106,7 +106,7 @@ macro_rules! headers_dump {
     h.set().SetCookie("id", "42", |d|d.Path("/").SameSiteLax().Secure());
     assert_eq!(h.SetCookie().collect::<Vec<_>>(), [
         SetCookie {
-            Cookie:   ("id", "42".into()),
+            Cookie:   ("id".into(), "42".into()),
             Expires:  None,
             MaxAge:   None,
             Domain:   None,
@@ -117,13 +117,17 @@ macro_rules! headers_dump {
         }
     ]);
 
+    let mut h = ResponseHeaders::new();
+    h.set().SetCookie("a;b", "1", |d|d.Path("/"));
+    assert_eq!(h.SetCookie().count(), 0);
+
     let mut h = ResponseHeaders::new();
     h.set()
         .SetCookie("id", "10", |d|d.Path("/").SameSiteLax().Secure())
         .SetCookie("id", "42", |d|d.MaxAge(1280).HttpOnly().Path("/where").SameSiteLax().Secure());
     assert_eq!(h.SetCookie().collect::<Vec<_>>(), [
         SetCookie {
-            Cookie:   ("id", "10".into()),
+            Cookie:   ("id".into(), "10".into()),
             Expires:  None,
             MaxAge:   None,
             Domain:   None,
@@ -133,7 +137,7 @@ macro_rules! headers_dump {
             SameSite: Some(SameSitePolicy::Lax),
         },
         SetCookie {
-            Cookie:   ("id", "42".into()),
+            Cookie:   ("id".into(), "42".into()),
             Expires:  None,
             MaxAge:   Some(1280),
             Domain:   None,
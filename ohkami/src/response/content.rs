@@ -1,6 +1,6 @@
 use ohkami_lib::CowSlice;
 
-#[cfg(feature="sse")]
+#[cfg(any(feature="sse", feature="__rt__"))]
 use ohkami_lib::Stream;
 
 #[cfg(not(feature="rt_lambda"/* currently */))]
@@ -8,6 +8,13 @@ use ohkami_lib::Stream;
 use crate::ws::Session;
 
 
+#[cfg(feature="__rt__")]
+/// Chunks of a response body, see `Response::with_body_stream`
+pub type BodyStream = std::pin::Pin<Box<dyn Stream<Item = Result<
+    std::borrow::Cow<'static, [u8]>,
+    Box<dyn std::error::Error + Send + Sync>
+>> + Send>>;
+
 pub enum Content {
     None,
 
@@ -16,6 +23,12 @@ pub enum Content {
     #[cfg(feature="sse")]
     Stream(std::pin::Pin<Box<dyn Stream<Item = String> + Send>>),
 
+    #[cfg(feature="__rt__")]
+    BodyStream(BodyStream),
+
+    #[cfg(feature="__rt_native__")]
+    File(super::NamedFile),
+
     #[cfg(not(feature="rt_lambda"/* currently */))]
     #[cfg(all(feature="ws", feature="__rt__"))]
     WebSocket(Session),
@@ -49,6 +62,12 @@ const _: () = {
                 #[cfg(feature="sse")]
                 Self::Stream(_)      => f.write_str("{stream}"),
 
+                #[cfg(feature="__rt__")]
+                Self::BodyStream(_)  => f.write_str("{body stream}"),
+
+                #[cfg(feature="__rt_native__")]
+                Self::File(_)        => f.write_str("{file}"),
+
                 #[cfg(not(feature="rt_lambda"/* currently */))]
                 #[cfg(all(feature="ws", feature="__rt__"))]
                 Self::WebSocket(_)   => f.write_str("{websocket}"),
@@ -96,6 +115,14 @@ impl Content {
                 stream.map(Result::<_, Infallible>::Ok)
             }),
 
+            Self::BodyStream(stream) => ::worker::Response::from_stream({
+                use ohkami_lib::StreamExt;
+                stream.map(|chunk| chunk
+                    .map(std::borrow::Cow::into_owned)
+                    .map_err(|e| ::worker::Error::RustError(e.to_string()))
+                )
+            }),
+
             #[cfg(feature="ws")]
             Self::WebSocket(ws)  => ::worker::Response::from_websocket(ws),
             
@@ -0,0 +1,490 @@
+use super::{Response, Content};
+use crate::ohkami::dir::{Validators, Selection, Multipart};
+use crate::{IntoResponse, Request, Method, Status};
+use crate::__rt__::{AsyncWrite, unblock};
+use crate::util::{stream, Stream, StreamExt};
+use std::{io, borrow::Cow, ops::Range, path::Path, sync::Arc};
+
+#[cfg(feature="openapi")]
+use crate::openapi;
+
+
+/// A file on disk as a response, without reading the whole of it into memory.
+///
+/// - `Content-Type` by the file extension ( or sniffing the content for unknown ones )
+/// - `Content-Length`, `Last-Modified`, `ETag`, `Accept-Ranges`
+/// - `Content-Disposition` by `.attachment()` / `.inline()`
+/// - `If-None-Match` / `If-Modified-Since` for `304 Not Modified`
+/// - `Range` / `If-Range` for `206 Partial Content` or `416 Range Not Satisfiable`
+///   on `GET` and `HEAD`
+///
+/// On Linux with `rt_tokio`, the content is sent by `sendfile(2)` ( zero-copy ).
+/// Other runtimes read and write it in chunks. File system operations are
+/// done off the executor ( like `spawn_blocking` ).
+///
+/// <br>
+///
+/// *example.rs*
+/// ```no_run
+/// use ohkami::prelude::*;
+/// use ohkami::NamedFile;
+///
+/// async fn download_report() -> Result<NamedFile, Response> {
+///     NamedFile::open("./reports/2024.pdf").await
+///         .map(NamedFile::attachment)
+///         .map_err(|_| Response::NotFound())
+/// }
+///
+/// #[tokio::main]
+/// async fn main() {
+///     Ohkami::new(
+///         "/report".GET(download_report)
+///     ).howl("localhost:5050").await
+/// }
+/// ```
+pub struct NamedFile {
+    pub(crate) file:       Arc<std::fs::File>,
+    /// range of the content to send, the whole of it by default
+    pub(crate) range:      Range<u64>,
+    pub(crate) len:        u64,
+    pub(crate) mime:       &'static str,
+    pub(crate) validators: Validators,
+    filename:              Option<String>,
+    disposition:           Option<&'static str>,
+}
+
+impl NamedFile {
+    pub async fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref().to_owned();
+        unblock(move || Self::open_blocking(&path)).await
+    }
+
+    fn open_blocking(path: &Path) -> io::Result<Self> {
+        use std::io::Read;
+
+        let mut file = std::fs::File::open(path)?;
+        let metadata = file.metadata()?;
+        if !metadata.is_file() {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                format!("`{}` is not a file", path.display())
+            ))
+        }
+
+        let filename = path.file_name()
+            .and_then(|name| name.to_str())
+            .map(String::from);
+
+        let mime = match filename.as_deref()
+            .and_then(|name| name.rsplit_once('.'))
+            .filter(|(stem, _)| !stem.is_empty()/* not like `.gitignore` */)
+            .and_then(|(_, ext)| ohkami_lib::mime::get_by_extension(&ext.to_ascii_lowercase()))
+        {
+            Some(mime) => mime,
+            None => {
+                let mut head = Vec::with_capacity(512);
+                (&mut file).take(512).read_to_end(&mut head)?;
+                ohkami_lib::mime::sniff(&head).unwrap_or("application/octet-stream")
+            }
+        };
+
+        Ok(Self {
+            file:        Arc::new(file),
+            range:       0..metadata.len(),
+            len:         metadata.len(),
+            mime,
+            validators:  Validators::of(&metadata),
+            filename,
+            disposition: None,
+        })
+    }
+
+    /// Override `Content-Type` of the response
+    pub fn with_mime(mut self, mime: &'static str) -> Self {
+        self.mime = mime;
+        self
+    }
+
+    /// Override the file name in `Content-Disposition`
+    pub fn with_filename(mut self, filename: impl Into<String>) -> Self {
+        self.filename = Some(filename.into());
+        self
+    }
+
+    /// `Content-Disposition: attachment` to let the client download the file
+    pub fn attachment(mut self) -> Self {
+        self.disposition = Some("attachment");
+        self
+    }
+
+    /// `Content-Disposition: inline` to let the client display the file
+    pub fn inline(mut self) -> Self {
+        self.disposition = Some("inline");
+        self
+    }
+
+    fn content_disposition(&self) -> Option<String> {
+        let disposition = self.disposition?;
+        Some(match self.filename.as_deref() {
+            None => disposition.to_string(),
+            Some(filename) if filename.bytes().all(|b| b.is_ascii_graphic() || b == b' ') => {
+                format!("{disposition}; filename=\"{}\"",
+                    filename.replace('\\', "\\\\").replace('"', "\\\""))
+            }
+            Some(filename) => {
+                /* ASCII fallback for clients not supporting `filename*` ( RFC 6266 ) */
+                let fallback = filename.chars()
+                    .map(|c| if c.is_ascii_graphic() && !matches!(c, '"' | '\\') || c == ' ' {c} else {'_'})
+                    .collect::<String>();
+                format!("{disposition}; filename=\"{fallback}\"; filename*=UTF-8''{}",
+                    crate::util::percent_encode(filename))
+            }
+        })
+    }
+
+}
+
+/// Read `range` of `file` in chunks of bounded size, off the executor
+pub(crate) fn read_chunks(
+    file:  Arc<std::fs::File>,
+    range: Range<u64>,
+) -> impl Stream<Item = io::Result<Vec<u8>>> + Send + 'static {
+    const CHUNK_SIZE: u64 = 64 * 1024;
+
+    stream::queue(move |mut q| async move {
+        let mut offset = range.start;
+        while offset < range.end {
+            let size = CHUNK_SIZE.min(range.end - offset) as usize;
+            let file = Arc::clone(&file);
+            match unblock(move || {
+                let mut buf = vec![0; size];
+                read_exact_at(&file, &mut buf, offset)?;
+                Ok(buf)
+            }).await {
+                Ok(chunk) => q.add(chunk),
+                Err(e) => return q.push(Err(e)),
+            }
+            offset += size as u64;
+        }
+    })
+}
+
+/// Read exactly `buf.len()` bytes at `offset` of `file`,
+/// not moving the cursor shared by the clones of `Arc<File>` on unix
+fn read_exact_at(file: &std::fs::File, buf: &mut [u8], offset: u64) -> io::Result<()> {
+    #[cfg(unix)]
+    return std::os::unix::fs::FileExt::read_exact_at(file, buf, offset);
+
+    #[cfg(not(unix))]
+    return {
+        use std::io::{Read, Seek, SeekFrom};
+
+        let mut file = file;
+        file.seek(SeekFrom::Start(offset))?;
+        file.read_exact(buf)
+    };
+}
+
+impl IntoResponse for NamedFile {
+    fn into_response(self) -> Response {
+        let mut res = Response::OK();
+        res.headers.set()
+            .ContentType(self.mime)
+            .ContentLength(self.len.to_string())
+            .ContentDisposition(self.content_disposition().map(Cow::Owned));
+        self.validators.set_to(&mut res);
+        res.content = Content::File(self);
+        res
+    }
+
+    #[cfg(feature="openapi")]
+    fn openapi_responses() -> openapi::Responses {
+        openapi::Responses::new([
+            (200, openapi::Response::when("OK")
+                .content("application/octet-stream", openapi::string().format("binary"))),
+            (206, openapi::Response::when("Partial Content")
+                .content("application/octet-stream", openapi::string().format("binary"))),
+            (304, openapi::Response::when("Not Modified")),
+            (416, openapi::Response::when("Range Not Satisfiable")),
+        ])
+    }
+}
+
+impl Response {
+    /// Apply conditional and range requests of `req` to a `NamedFile` response
+    ///
+    /// should be called, like, just after router's handling
+    pub(crate) fn select_file_range(&mut self, req: &Request) {
+        if !matches!(req.method, Method::GET | Method::HEAD) || self.status != Status::OK {
+            return
+        }
+        let Content::File(file) = &self.content else {
+            return
+        };
+
+        let len = file.len;
+        match Selection::of(req, len, &file.validators) {
+            Selection::Full => (),
+
+            Selection::NotModified => {
+                self.status = Status::NotModified;
+                self.drop_content();
+                self.headers.set().ContentDisposition(None);
+            }
+
+            Selection::Unsatisfiable => {
+                self.status = Status::RangeNotSatisfiable;
+                self.drop_content();
+                self.headers.set()
+                    .ContentDisposition(None)
+                    .ContentRange(format!("bytes */{len}"));
+            }
+
+            Selection::Partial(ranges) if ranges.len() == 1 => {
+                let range = ranges.into_iter().next().unwrap();
+                self.status = Status::PartialContent;
+                self.headers.set()
+                    .ContentLength((range.end - range.start).to_string())
+                    .ContentRange(crate::header::range::content_range(&range, len));
+                if let Content::File(file) = &mut self.content {
+                    file.range = range;
+                }
+            }
+
+            Selection::Partial(ranges) => {
+                let multipart = Multipart::new(file.mime, len, ranges);
+                let file = Arc::clone(&file.file);
+
+                self.status = Status::PartialContent;
+                self.headers.set().ContentType(multipart.content_type());
+                self.set_sized_body_stream(multipart.content_length(), stream::queue(|mut q| async move {
+                    for (head, range) in multipart.parts {
+                        q.add(head.into_bytes());
+                        let mut chunks = Box::pin(read_chunks(Arc::clone(&file), range));
+                        while let Some(chunk) = chunks.next().await {
+                            let failed = chunk.is_err();
+                            q.push(chunk);
+                            if failed {return}
+                        }
+                        q.add(b"\r\n".to_vec());
+                    }
+                    q.add(multipart.closing.into_bytes());
+                }));
+            }
+        }
+    }
+}
+
+/// Write a range of a file to the connection, by `sendfile(2)` where possible
+pub(crate) trait SendFile: AsyncWrite + Unpin {
+    async fn send_file(&mut self, file: &Arc<std::fs::File>, range: Range<u64>) -> io::Result<()> {
+        let mut chunks = Box::pin(read_chunks(Arc::clone(file), range));
+        while let Some(chunk) = chunks.next().await {
+            self.write_all(&chunk?).await?;
+        }
+        self.flush().await
+    }
+}
+
+#[cfg(not(all(feature="rt_tokio", target_os="linux")))]
+impl SendFile for crate::__rt__::TcpStream {}
+
+#[cfg(all(feature="rt_tokio", target_os="linux"))]
+impl SendFile for tokio::net::TcpStream {
+    async fn send_file(&mut self, file: &Arc<std::fs::File>, range: Range<u64>) -> io::Result<()> {
+        use std::os::fd::AsRawFd;
+
+        /* `sendfile` counts are limited to this on Linux */
+        const MAX_COUNT: u64 = 0x7fff_f000;
+
+        self.flush().await?;
+
+        let (socket, file) = (self.as_raw_fd(), file.as_raw_fd());
+        let mut offset = range.start as libc::off_t;
+        while (offset as u64) < range.end {
+            self.writable().await?;
+            let count = (range.end - offset as u64).min(MAX_COUNT) as usize;
+            match self.try_io(tokio::io::Interest::WRITABLE, || {
+                // SAFETY: both `socket` and `file` are open while `self` and `file` are borrowed
+                match unsafe {libc::sendfile(socket, file, &mut offset, count)} {
+                    -1 => Err(io::Error::last_os_error()),
+                    n  => Ok(n as usize),
+                }
+            }) {
+                Ok(0) => return Err(io::ErrorKind::UnexpectedEof.into()),
+                Ok(_) => (),
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => continue,
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+impl SendFile for Vec<u8> {}
+
+#[cfg(all(test, feature="__rt_native__", feature="DEBUG"))]
+mod test {
+    use super::NamedFile;
+    use crate::prelude::*;
+    use crate::testing::*;
+
+    fn temp_file(name: &str, content: &[u8]) -> &'static str {
+        let dir = std::env::temp_dir().join(format!("ohkami-file-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(name), content).unwrap();
+        dir.join(name).into_os_string().into_string().unwrap().leak()
+    }
+
+    #[test] fn named_file() {
+        let hello = temp_file("hello.txt", b"Hello, world!");
+        let blob = temp_file("blob", b"\x89PNG\r\n\x1a\n...");
+        let report = temp_file("report.csv", b"a,b\n1,2\n");
+
+        let t = Ohkami::new((
+            "/hello".GET(move || async move {NamedFile::open(hello).await.unwrap()}),
+            "/blob".GET(move || async move {NamedFile::open(blob).await.unwrap()}),
+            "/report".GET(move || async move {NamedFile::open(report).await.unwrap().attachment()}),
+            "/resume".GET(move || async move {
+                NamedFile::open(report).await.unwrap().with_filename("résumé.csv").inline()
+            }),
+        )).test();
+
+        crate::__rt__::testing::block_on(async {
+            let req = TestRequest::GET("/hello");
+            let res = t.oneshot(req).await.collect_body().await;
+            assert_eq!(res.status().code(), 200);
+            assert_eq!(res.text(), Some("Hello, world!"));
+            assert_eq!(res.header("Content-Type"), Some("text/plain"));
+            assert_eq!(res.header("Content-Length"), Some("13"));
+            assert_eq!(res.header("Accept-Ranges"), Some("bytes"));
+            assert_eq!(res.header("Content-Disposition"), None);
+            assert!(res.header("Last-Modified").is_some());
+            let etag = res.header("ETag").unwrap().to_string();
+
+            let req = TestRequest::GET("/hello")
+                .header("If-None-Match", etag.clone());
+            let res = t.oneshot(req).await;
+            assert_eq!(res.status().code(), 304);
+            assert_eq!(res.text(), None);
+            assert_eq!(res.header("Content-Length"), None);
+            assert_eq!(res.header("ETag"), Some(&*etag));
+
+            let req = TestRequest::GET("/hello")
+                .header("Range", "bytes=7-11");
+            let res = t.oneshot(req).await.collect_body().await;
+            assert_eq!(res.status().code(), 206);
+            assert_eq!(res.text(), Some("world"));
+            assert_eq!(res.header("Content-Range"), Some("bytes 7-11/13"));
+            assert_eq!(res.header("Content-Length"), Some("5"));
+
+            let req = TestRequest::GET("/hello")
+                .header("Range", "bytes=0-4")
+                .header("If-Range", r#""stale""#);
+            let res = t.oneshot(req).await.collect_body().await;
+            assert_eq!(res.status().code(), 200);
+            assert_eq!(res.text(), Some("Hello, world!"));
+
+            let req = TestRequest::GET("/hello")
+                .header("Range", "bytes=0-4, 7-11");
+            let res = t.oneshot(req).await.collect_body().await;
+            assert_eq!(res.status().code(), 206);
+            let content_type = res.header("Content-Type").unwrap();
+            assert!(content_type.starts_with("multipart/byteranges; boundary="));
+            let body = std::str::from_utf8(res.content(content_type).unwrap()).unwrap();
+            assert!(body.contains("Content-Range: bytes 0-4/13\r\n\r\nHello\r\n"));
+            assert!(body.contains("Content-Range: bytes 7-11/13\r\n\r\nworld\r\n"));
+
+            let req = TestRequest::GET("/hello")
+                .header("Range", "bytes=100-");
+            let res = t.oneshot(req).await;
+            assert_eq!(res.status().code(), 416);
+            assert_eq!(res.header("Content-Range"), Some("bytes */13"));
+            assert_eq!(res.text(), None);
+
+            let req = TestRequest::GET("/blob");
+            let res = t.oneshot(req).await;
+            assert_eq!(res.header("Content-Type"), Some("image/png"));
+
+            let req = TestRequest::GET("/report");
+            let res = t.oneshot(req).await;
+            assert_eq!(res.header("Content-Disposition"), Some(r#"attachment; filename="report.csv""#));
+
+            let req = TestRequest::GET("/resume");
+            let res = t.oneshot(req).await;
+            assert_eq!(res.header("Content-Disposition"), Some(
+                r#"inline; filename="r_sum_.csv"; filename*=UTF-8''r%C3%A9sum%C3%A9%2Ecsv"#
+            ));
+        });
+    }
+
+    #[test] fn named_file_send() {
+        let hello = temp_file("send.txt", b"Hello, world!");
+
+        crate::__rt__::testing::block_on(async {
+            let mut res = NamedFile::open(hello).await.unwrap().into_response();
+            res.headers.set().Date(None);
+            let etag = res.headers.ETag().unwrap().to_string();
+            let last_modified = res.headers.LastModified().unwrap().to_string();
+
+            let mut bytes = Vec::new();
+            res.send(&mut bytes).await.unwrap();
+            assert_eq!(std::str::from_utf8(&bytes).unwrap(), format!("\
+                HTTP/1.1 200 OK\r\n\
+                Content-Length: 13\r\n\
+                Content-Type: text/plain\r\n\
+                ETag: {etag}\r\n\
+                Accept-Ranges: bytes\r\n\
+                Last-Modified: {last_modified}\r\n\
+                \r\n\
+                Hello, world!\
+            "));
+        });
+    }
+
+    #[test] fn named_file_send_in_chunks() {
+        let content = (0..200_000).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+        let large = temp_file("large.bin", &content);
+
+        crate::__rt__::testing::block_on(async {
+            let mut file = NamedFile::open(large).await.unwrap();
+            file.range = 1000..150_000;
+
+            let mut bytes = Vec::new();
+            super::SendFile::send_file(&mut bytes, &file.file, file.range.clone()).await.unwrap();
+            assert_eq!(bytes, &content[1000..150_000]);
+        });
+    }
+
+    #[cfg(all(feature="rt_tokio", target_os="linux"))]
+    #[test] fn named_file_sendfile() {
+        use tokio::io::AsyncReadExt;
+        use tokio::net::{TcpListener, TcpStream};
+
+        let content = (0..300_000).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+        let large = temp_file("sendfile.bin", &content);
+
+        crate::__rt__::testing::block_on(async {
+            let file = NamedFile::open(large).await.unwrap();
+
+            for range in [0..300_000, 1000..250_000] {
+                let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+                let mut client = TcpStream::connect(listener.local_addr().unwrap()).await.unwrap();
+                let (mut server, _) = listener.accept().await.unwrap();
+
+                let receive = async {
+                    let mut bytes = Vec::new();
+                    client.read_to_end(&mut bytes).await.unwrap();
+                    bytes
+                };
+                let send = async {
+                    super::SendFile::send_file(&mut server, &file.file, range.clone()).await.unwrap();
+                    drop(server);
+                };
+                let (bytes, ()) = tokio::join!(receive, send);
+                assert_eq!(bytes, &content[range.start as usize..range.end as usize]);
+            }
+        });
+    }
+}
@@ -188,7 +188,7 @@ macro_rules! Header {
             }
         }
     };
-} Header! {47;
+} Header! {48;
     AcceptRanges:                    b"Accept-Ranges",
     AccessControlAllowCredentials:   b"Access-Control-Allow-Credentials",
     AccessControlAllowHeaders:       b"Access-Control-Allow-Headers",
@@ -204,7 +204,7 @@ macro_rules! Header {
     CDNCacheControl:                 b"CDN-Cache-Control",
     Connection:                      b"Connection",
     ContentDisposition:              b"Content-Disposition",
-    ContentEncoding:                 b"Content-Ecoding",
+    ContentEncoding:                 b"Content-Encoding",
     ContentLanguage:                 b"Content-Language",
     ContentLength:                   b"Content-Length",
     ContentLocation:                 b"Content-Location",
@@ -217,6 +217,7 @@ macro_rules! Header {
     Date:                            b"Date",
     ETag:                            b"ETag",
     Expires:                         b"Expires",
+    LastModified:                    b"Last-Modified",
     Link:                            b"Link",
     Location:                        b"Location",
     ProxyAuthenticate:               b"Proxy-Authenticate",
@@ -264,6 +265,8 @@ const _: () = {
         ///   `Set-Cookie` headers.
         /// - Cookie value (second argument) is percent-encoded when the
         ///   response is sended.
+        /// - When Cookie name (first argument) is not a valid one,
+        ///   a warning is logged and the header is not added.
         /// 
         /// ---
         /// *example.rs*
@@ -279,21 +282,31 @@ const _: () = {
         /// ```
         #[inline]
         pub fn SetCookie(self,
-            name:  &'static str,
+            name:  impl Into<Cow<'static, str>>,
             value: impl Into<Cow<'static, str>>,
             directives: impl FnOnce(SetCookieBuilder)->SetCookieBuilder
         ) -> Self {
-            let setcookie: Cow<'static, str> = directives(SetCookieBuilder::new(name, value)).build().into();
-            self.0.size += "Set-Cookie: ".len() + setcookie.len() + "\r\n".len();
-            match self.0.setcookie.as_mut() {
-                None             => self.0.setcookie = Some(Box::new(vec![setcookie])),
-                Some(setcookies) => setcookies.push(setcookie),
+            match directives(SetCookieBuilder::new(name, value)).build() {
+                Ok(setcookie) => self.0.push_setcookie(setcookie),
+                Err(e) => crate::warning!("[SetCookie] skipped: {e}"),
             }
             self
         }
     }
 };
 
+impl Headers {
+    /// Add a built `Set-Cookie` value
+    pub(crate) fn push_setcookie(&mut self, setcookie: String) {
+        let setcookie: Cow<'static, str> = Cow::Owned(setcookie);
+        self.size += "Set-Cookie: ".len() + setcookie.len() + "\r\n".len();
+        match self.setcookie.as_mut() {
+            None             => self.setcookie = Some(Box::new(vec![setcookie])),
+            Some(setcookies) => setcookies.push(setcookie),
+        }
+    }
+}
+
 impl Headers {
     #[inline(always)]
     pub(crate) fn insert(&mut self, name: Header, value: Cow<'static, str>) {
@@ -41,6 +41,12 @@ use std::borrow::Cow;
 pub trait IntoResponse {
     fn into_response(self) -> Response;
 
+    #[doc(hidden)]
+    /// structured extraction error to be passed to `OnRejection` hook
+    fn __rejection__(self) -> Result<crate::Rejection, Self> where Self: Sized {
+        Err(self)
+    }
+
     #[cfg(feature="openapi")]
     fn openapi_responses() -> openapi::Responses;
 }
@@ -70,7 +76,7 @@ impl<B: IntoBody> IntoResponse for B {
                         Self::CONTENT_TYPE
                     )
                 }
-                Response::InternalServerError()
+                crate::typed::reject(crate::Status::InternalServerError, "")
             }
         }
     }
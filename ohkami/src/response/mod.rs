@@ -3,15 +3,27 @@ pub use status::Status;
 
 mod headers;
 pub use headers::{Headers as ResponseHeaders, SetHeaders};
+pub(crate) use headers::{Header, HeaderAction};
 #[cfg(feature="DEBUG")]
 pub use headers::Header as ResponseHeader;
 
 mod content;
 pub use content::Content;
+#[cfg(feature="__rt__")]
+#[cfg(any(feature="gzip", feature="br", feature="zstd"))]
+pub(crate) use content::BodyStream;
 
 mod into_response;
 pub use into_response::{IntoResponse, IntoBody};
 
+mod trailers;
+pub use trailers::Trailers;
+
+#[cfg(feature="__rt_native__")]
+pub(crate) mod file;
+#[cfg(feature="__rt_native__")]
+pub use file::NamedFile;
+
 #[cfg(test)] mod _test;
 #[cfg(test)] mod _test_headers;
 
@@ -19,9 +31,11 @@ use std::borrow::Cow;
 use ohkami_lib::{CowSlice, Slice};
 
 #[cfg(feature="__rt_native__")]
-use crate::__rt__::AsyncWrite;
+use file::SendFile;
 #[cfg(feature="sse")]
-use crate::{sse, util::{Stream, StreamExt}};
+use crate::sse;
+#[cfg(any(feature="sse", feature="__rt__"))]
+use crate::util::{Stream, StreamExt};
 
 
 /// # HTTP Response
@@ -109,6 +123,8 @@ pub struct Response {
     pub headers: ResponseHeaders,
 
     pub(crate) content: Content,
+
+    pub(crate) trailers: Option<Trailers>,
 }
 
 impl Response {
@@ -118,6 +134,7 @@ impl Response {
             status,
             headers: ResponseHeaders::new(),
             content: Content::None,
+            trailers: None,
         }
     }
 
@@ -172,6 +189,18 @@ impl Response {
         self
     }
 
+    /// Send the fields of `trailers` after the chunked body, see [`Trailers`]
+    pub fn set_trailers(&mut self, trailers: Trailers) {
+        self.trailers = Some(trailers);
+    }
+    pub fn with_trailers(mut self, trailers: Trailers) -> Self {
+        self.set_trailers(trailers);
+        self
+    }
+    pub fn trailers(&self) -> Option<&Trailers> {
+        self.trailers.as_ref()
+    }
+
     #[inline]
     pub fn set_payload(&mut self,
         content_type: &'static str,
@@ -292,6 +321,105 @@ impl Response {
     }
 }
 
+#[cfg(feature="__rt__")]
+impl Response {
+    /// Set a stream of arbitrary bytes as the body, sent with
+    /// `Transfer-Encoding: chunked` as the items are yielded.
+    /// `Content-Type` defaults to `application/octet-stream`.
+    ///
+    /// When the stream yields an `Err`, the response is aborted
+    /// ( the connection is closed without completing the body ).
+    ///
+    /// ```
+    /// use ohkami::prelude::*;
+    /// use ohkami::util::stream;
+    ///
+    /// async fn export_csv() -> Response {
+    ///     Response::OK()
+    ///         .with_headers(|h| h.ContentType("text/csv"))
+    ///         .with_body_stream(stream::queue(|mut q| async move {
+    ///             q.push(Ok::<_, std::io::Error>("id,name\n".as_bytes()));
+    ///             for (id, name) in [(1, "ohkami"), (2, "ohkami2")] {
+    ///                 q.push(Ok(format!("{id},{name}\n").into_bytes().leak()));
+    ///             }
+    ///         }))
+    /// }
+    /// ```
+    pub fn with_body_stream<B, E>(
+        mut self,
+        stream: impl Stream<Item = Result<B, E>> + Send + 'static
+    ) -> Self
+    where
+        B: Into<Cow<'static, [u8]>>,
+        E: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        self.set_body_stream(stream);
+        self
+    }
+
+    pub fn set_body_stream<B, E>(
+        &mut self,
+        stream: impl Stream<Item = Result<B, E>> + Send + 'static
+    )
+    where
+        B: Into<Cow<'static, [u8]>>,
+        E: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        self.set_body_stream_raw(None, stream);
+        self.headers.set().TransferEncoding("chunked");
+    }
+
+    /// Set a stream of bytes whose total length is known to be `content_length`
+    /// as the body, sent with `Content-Length` instead of chunked encoding.
+    ///
+    /// The stream must yield exactly `content_length` bytes in total. Otherwise
+    /// the connection is closed after sending at most `content_length` bytes.
+    pub fn with_sized_body_stream<B, E>(
+        mut self,
+        content_length: u64,
+        stream: impl Stream<Item = Result<B, E>> + Send + 'static
+    ) -> Self
+    where
+        B: Into<Cow<'static, [u8]>>,
+        E: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        self.set_sized_body_stream(content_length, stream);
+        self
+    }
+
+    pub fn set_sized_body_stream<B, E>(
+        &mut self,
+        content_length: u64,
+        stream: impl Stream<Item = Result<B, E>> + Send + 'static
+    )
+    where
+        B: Into<Cow<'static, [u8]>>,
+        E: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        self.set_body_stream_raw(Some(content_length), stream);
+        self.headers.set().TransferEncoding(None);
+    }
+
+    fn set_body_stream_raw<B, E>(
+        &mut self,
+        content_length: Option<u64>,
+        stream: impl Stream<Item = Result<B, E>> + Send + 'static
+    )
+    where
+        B: Into<Cow<'static, [u8]>>,
+        E: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        if self.headers.ContentType().is_none() {
+            self.headers.set().ContentType("application/octet-stream");
+        }
+        self.headers.set().ContentLength(content_length.map(|len| Cow::Owned(len.to_string())));
+        self.content = Content::BodyStream(Box::pin(stream.map(|chunk| chunk
+            .map(Into::into)
+            .map_err(Into::into)
+        )));
+    }
+}
+
 #[cfg(feature="__rt_native__")]
 pub(super) enum Upgrade {
     None,
@@ -311,7 +439,7 @@ impl Response {
     #[cfg_attr(not(feature="sse"), inline)]
     pub(crate) async fn send(
         self,
-        conn: &mut (impl AsyncWrite + Unpin)
+        conn: &mut impl SendFile
     ) -> std::io::Result<Upgrade> {
         match self.content {
             Content::None => {
@@ -357,16 +485,7 @@ impl Response {
                 conn.flush().await?;
 
                 while let Some(chunk) = stream.next().await {
-                    let mut message = Vec::with_capacity(
-                        /* capacity for a single line */
-                        "data: ".len() + chunk.len() + "\n\n".len()
-                    );
-                    for line in chunk.split('\n') {
-                        message.extend_from_slice(b"data: ");
-                        message.extend_from_slice(line.as_bytes());
-                        message.push(b'\n');
-                    }
-                    message.push(b'\n');
+                    let mut message = sse::message(&chunk);
 
                     let size_hex_bytes = ohkami_lib::num::hexized_bytes(message.len());
 
@@ -381,7 +500,87 @@ impl Response {
                     conn.write_all(&chunk).await?;
                     conn.flush().await?;
                 }
-                conn.write_all(b"0\r\n\r\n").await?;
+                conn.write_all(&last_chunk(self.trailers.as_ref())).await?;
+                conn.flush().await?;
+
+                Ok(Upgrade::None)
+            }
+
+            Content::BodyStream(mut stream) => {
+                /* bytes left to send for a sized body stream, `None` for chunked encoding */
+                let mut rest = self.headers.ContentLength().map(|len| len.parse::<u64>().unwrap_or(0));
+
+                let mut buf = Vec::<u8>::with_capacity(
+                    self.status.line().len() +
+                    self.headers.size
+                ); unsafe {
+                    crate::push_unchecked!(buf <- self.status.line());
+                    self.headers.write_unchecked_to(&mut buf);
+                }
+                conn.write_all(&buf).await?;
+                conn.flush().await?;
+
+                while let Some(chunk) = stream.next().await {
+                    let chunk = chunk.map_err(|e| {
+                        crate::warning!("[Response::send] body stream failed: {e}");
+                        std::io::Error::other(e)
+                    })?;
+                    if chunk.is_empty() {
+                        /* an empty chunk would terminate the chunked body */
+                        continue
+                    }
+
+                    match &mut rest {
+                        None => {
+                            let size_hex_bytes = ohkami_lib::num::hexized_bytes(chunk.len());
+                            let mut size_line = Vec::from(&size_hex_bytes[size_hex_bytes.iter().position(|b| *b!=b'0').unwrap()..]);
+                            size_line.extend_from_slice(b"\r\n");
+                            conn.write_all(&size_line).await?;
+                            conn.write_all(&chunk).await?;
+                            conn.write_all(b"\r\n").await?;
+                        }
+                        Some(rest) if chunk.len() as u64 > *rest => {
+                            /* truncate to `Content-Length` and close the connection,
+                               not to be taken as the next response by the client */
+                            conn.write_all(&chunk[..*rest as usize]).await?;
+                            conn.flush().await?;
+                            crate::warning!("[Response::send] body stream is longer than `Content-Length`");
+                            return Err(std::io::Error::other("body stream is longer than `Content-Length`"))
+                        }
+                        Some(rest) => {
+                            conn.write_all(&chunk).await?;
+                            *rest -= chunk.len() as u64;
+                        }
+                    }
+                    conn.flush().await?;
+                }
+                match rest {
+                    None => {
+                        conn.write_all(&last_chunk(self.trailers.as_ref())).await?;
+                        conn.flush().await?;
+                    }
+                    Some(0) => (),
+                    Some(_) => {
+                        /* close the connection to let the client know the body is incomplete */
+                        crate::warning!("[Response::send] body stream is shorter than `Content-Length`");
+                        return Err(std::io::ErrorKind::UnexpectedEof.into())
+                    }
+                }
+
+                Ok(Upgrade::None)
+            }
+
+            Content::File(file) => {
+                let mut buf = Vec::<u8>::with_capacity(
+                    self.status.line().len() +
+                    self.headers.size
+                ); unsafe {
+                    crate::push_unchecked!(buf <- self.status.line());
+                    self.headers.write_unchecked_to(&mut buf);
+                }
+                conn.write_all(&buf).await?;
+
+                conn.send_file(&file.file, file.range).await?;
                 conn.flush().await?;
 
                 Ok(Upgrade::None)
@@ -405,6 +604,17 @@ impl Response {
     }
 }
 
+/// `0\r\n`, the trailer fields and `\r\n` terminating a chunked body
+#[cfg(feature="__rt_native__")]
+fn last_chunk(trailers: Option<&Trailers>) -> Vec<u8> {
+    let mut buf = Vec::from(b"0\r\n");
+    if let Some(trailers) = trailers {
+        trailers.write_to(&mut buf);
+    }
+    buf.extend_from_slice(b"\r\n");
+    buf
+}
+
 const _: () = {
     impl std::fmt::Debug for Response {
         fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -412,6 +622,7 @@ const _: () = {
                 .field("status",  &self.status)
                 .field("headers", &self.headers)
                 .field("content", &self.content)
+                .field("trailers", &self.trailers)
                 .finish()
         }
     }
@@ -472,15 +683,15 @@ const _: () = {
     use crate::x_lambda::LambdaResponse;
     use ohkami_lib::Stream;
     use ::lambda_runtime::FunctionResponse;
-    use std::{pin::Pin, convert::Infallible};
+    use std::pin::Pin;
 
     impl Into<FunctionResponse<
         LambdaResponse,
-        Pin<Box<dyn Stream<Item = Result<String, Infallible>> + Send>>
+        Pin<Box<dyn Stream<Item = Result<Vec<u8>, ::lambda_runtime::Error>> + Send>>
     >> for Response {
         fn into(self) -> FunctionResponse<
             LambdaResponse,
-            Pin<Box<dyn Stream<Item = Result<String, Infallible>> + Send>>
+            Pin<Box<dyn Stream<Item = Result<Vec<u8>, ::lambda_runtime::Error>> + Send>>
         > {
             let mut headers = self.headers;
 
@@ -524,26 +735,52 @@ const _: () = {
                 
                 #[cfg(feature="sse")]
                 Content::Stream(stream) => {
-                    FunctionResponse::StreamingResponse(::lambda_runtime::StreamResponse {
-                        stream: Box::pin(stream.map(Result::<_, Infallible>::Ok)),
-                        metadata_prelude: ::lambda_runtime::MetadataPrelude {
-                            // `StatusCode` of `http` crate
-                            status_code: unsafe {
-                                TryFrom::<u16>::try_from(self.status.code()).unwrap_unchecked()
-                            },
-                            // `HeaderMap` of `http` crate
-                            headers: FromIterator/*::<HeaderName, HeaderValue>*/::from_iter(
-                                headers.into_iter()
-                                    .map(|(n, v): (&'static str, Cow<'static, str>)| (
-                                        TryFrom::<&str>::try_from(n).unwrap(),
-                                        TryFrom::<String>::try_from(v.into_owned()).unwrap()
-                                    ))
-                            ),
-                            cookies: cookies.unwrap_or_else(Vec::new)
-                        }
-                    })
+                    streaming_response(
+                        Box::pin(stream.map(|s| Ok(s.into_bytes()))),
+                        self.status,
+                        headers,
+                        cookies,
+                    )
+                }
+
+                Content::BodyStream(stream) => {
+                    streaming_response(
+                        Box::pin(stream.map(|chunk| chunk.map(Cow::into_owned))),
+                        self.status,
+                        headers,
+                        cookies,
+                    )
                 }
             }
         }
     }
+
+    fn streaming_response(
+        stream:  Pin<Box<dyn Stream<Item = Result<Vec<u8>, ::lambda_runtime::Error>> + Send>>,
+        status:  Status,
+        headers: ResponseHeaders,
+        cookies: Option<Vec<String>>,
+    ) -> FunctionResponse<
+        LambdaResponse,
+        Pin<Box<dyn Stream<Item = Result<Vec<u8>, ::lambda_runtime::Error>> + Send>>
+    > {
+        FunctionResponse::StreamingResponse(::lambda_runtime::StreamResponse {
+            stream,
+            metadata_prelude: ::lambda_runtime::MetadataPrelude {
+                // `StatusCode` of `http` crate
+                status_code: unsafe {
+                    TryFrom::<u16>::try_from(status.code()).unwrap_unchecked()
+                },
+                // `HeaderMap` of `http` crate
+                headers: FromIterator/*::<HeaderName, HeaderValue>*/::from_iter(
+                    headers.into_iter()
+                        .map(|(n, v): (&'static str, Cow<'static, str>)| (
+                            TryFrom::<&str>::try_from(n).unwrap(),
+                            TryFrom::<String>::try_from(v.into_owned()).unwrap()
+                        ))
+                ),
+                cookies: cookies.unwrap_or_else(Vec::new)
+            }
+        })
+    }
 };
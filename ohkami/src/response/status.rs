@@ -54,6 +54,7 @@ macro_rules! status {
                         status:  Status::$name,
                         headers: ResponseHeaders::new(),
                         content: Content::None,
+                        trailers: None,
                     }
                 }
             )*
@@ -0,0 +1,84 @@
+use std::borrow::Cow;
+use std::sync::{Arc, Mutex};
+
+
+/// # Trailer fields of a response
+///
+/// Header fields sent after a chunked body ( `Response::with_body_stream`,
+/// `Response::with_stream` ), for values known only when the body ends
+/// like checksums or gRPC-web-style status.
+///
+/// This is a shared handle: give a clone to `Response::with_trailers` and
+/// set values from the stream producing the body. Values set by the time
+/// the stream ends are sent. They're ignored for non-chunked bodies or
+/// on runtimes without HTTP/1.1 chunked encoding ( Workers, Lambda ).
+///
+/// ```
+/// use ohkami::prelude::*;
+/// use ohkami::Trailers;
+/// use ohkami::util::stream;
+///
+/// async fn download() -> Response {
+///     let trailers = Trailers::new();
+///     Response::OK()
+///         .with_headers(|h| h.Trailer("X-Checksum"))
+///         .with_trailers(trailers.clone())
+///         .with_body_stream(stream::queue(|mut q| async move {
+///             let mut sum = 0u32;
+///             for chunk in [&b"hello, "[..], &b"trailers"[..]] {
+///                 sum = chunk.iter().fold(sum, |s, b| s.wrapping_add(*b as u32));
+///                 q.push(Ok::<_, std::io::Error>(chunk));
+///             }
+///             trailers.set("X-Checksum", sum.to_string());
+///         }))
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct Trailers(
+    Arc<Mutex<Vec<Field>>>
+);
+
+type Field = (Cow<'static, str>, Cow<'static, str>);
+
+impl Trailers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set `name: value`, replacing the existing value of `name`
+    pub fn set(&self, name: impl Into<Cow<'static, str>>, value: impl Into<Cow<'static, str>>) {
+        let (name, value) = (name.into(), value.into());
+        let mut fields = self.0.lock().unwrap();
+        match fields.iter_mut().find(|(n, _)| n.eq_ignore_ascii_case(&name)) {
+            Some((_, v)) => *v = value,
+            None => fields.push((name, value)),
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<String> {
+        self.0.lock().unwrap().iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.to_string())
+    }
+
+    pub fn remove(&self, name: &str) {
+        self.0.lock().unwrap().retain(|(n, _)| !n.eq_ignore_ascii_case(name));
+    }
+
+    /// `name: value\r\n` for each field
+    #[cfg(feature="__rt_native__")]
+    pub(crate) fn write_to(&self, buf: &mut Vec<u8>) {
+        for (name, value) in &*self.0.lock().unwrap() {
+            buf.extend_from_slice(name.as_bytes());
+            buf.extend_from_slice(b": ");
+            buf.extend_from_slice(value.as_bytes());
+            buf.extend_from_slice(b"\r\n");
+        }
+    }
+}
+
+impl std::fmt::Debug for Trailers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.0.lock().unwrap().iter().map(|(n, v)| (n, v))).finish()
+    }
+}
@@ -2,7 +2,7 @@ use super::util::ID;
 use super::segments::{RouteSegment, RouteSegments, RouteSegmentsIterator};
 use crate::Method;
 use crate::fang::{BoxedFPC, Fangs, handler::Handler};
-use crate::ohkami::routing::{ByAnother, HandlerSet, HandlerMeta};
+use crate::ohkami::routing::{ByAnother, HandlerSet, HandlerMeta, RouteInfo};
 use ohkami_lib::map::TupleMap;
 use std::{sync::Arc, borrow::Cow, collections::HashMap};
 
@@ -23,6 +23,8 @@ pub struct Router {
 pub(super) struct Node {
     pub(super) pattern:  Option<Pattern>,
     pub(super) handler:  Option<Handler>,
+    /// handler for unmatched paths at or under this node, instead of the default Not Found
+    pub(super) fallback: Option<Handler>,
     pub(super) fangses:  FangsList,
     pub(super) children: Vec<Node>
 }
@@ -54,12 +56,29 @@ impl FangsList {
         }
     }
 
+    /// yield from most inner fangs
+    fn iter(&self) -> impl DoubleEndedIterator<Item = &Arc<dyn Fangs>> {
+        self.0.iter()
+            .map(|(_, fangs)| fangs)
+    }
+
     /// yield from most inner fangs
     fn into_iter(self) -> impl Iterator<Item = Arc<dyn Fangs>> {
         self.0.into_iter()
             .map(|(_, fangs)| fangs)
     }
 
+    /// `h` with these fangs applied
+    fn apply_to(&self, h: Handler) -> Handler {
+        #[cfg(not(feature="openapi"))]
+        return Handler { proc: self.clone().into_proc_with(h) };
+        #[cfg(feature="openapi")]
+        return {
+            let (proc, openapi_operation) = self.clone().into_proc_with(h);
+            Handler { proc, openapi_operation }
+        };
+    }
+
     pub(super) fn into_proc_with(self, h: Handler) -> IntoProcWith {
         let mut iter = self.into_iter();
 
@@ -111,7 +130,7 @@ impl Router {
     }
 
     pub(crate) fn register_handlers(&mut self, handlers: HandlerSet) {
-        let HandlerSet { route, GET, PUT, POST, PATCH, DELETE } = handlers;
+        let HandlerSet { route, GET, PUT, POST, PATCH, DELETE, fangs } = handlers;
 
         let methods = {
             macro_rules! allow_methods {
@@ -130,7 +149,16 @@ impl Router {
 
         macro_rules! register {
             ($( $method:ident ),*) => {$(
-                if let Some((handler, meta)) = $method {
+                if let Some((mut handler, mut meta)) = $method {
+                    for fangs in fangs.iter().rev() {
+                        handler = Handler {
+                            proc: fangs.build(handler.proc),
+                            #[cfg(feature="openapi")]
+                            openapi_operation: fangs.openapi_map_operation(handler.openapi_operation)
+                        };
+                    }
+                    meta.fangs.splice(0..0, fangs.iter().flat_map(|fangs| fangs.type_names()));
+
                     self.routes.entry(route.clone())
                         .and_modify(|it| {it.insert(Method::$method, meta.clone());})
                         .or_insert_with(|| TupleMap::from_iter([(Method::$method, meta)]));
@@ -152,6 +180,27 @@ impl Router {
         ).expect("Failed to register handler");
     }
 
+    /// Register `handler` for `GET` ( and so `HEAD` ) requests to unmatched paths
+    /// at or under `route`. Registered handlers are preferred to this.
+    pub(crate) fn register_fallback(&mut self, route: RouteSegments, handler: Handler) {
+        self.GET.register_fallback(
+            route.into_iter(),
+            handler
+        ).expect("Failed to register fallback handler");
+    }
+
+    /// Register `handler` for `method` requests to any unmatched paths,
+    /// instead of the default `Not Found`.
+    pub(crate) fn register_not_found(&mut self, method: Method, handler: Handler) {
+        match method {
+            Method::PUT    => &mut self.PUT,
+            Method::POST   => &mut self.POST,
+            Method::PATCH  => &mut self.PATCH,
+            Method::DELETE => &mut self.DELETE,
+            _              => &mut self.GET,
+        }.set_fallback(handler).expect("Failed to register not found handler");
+    }
+
     pub(crate) fn merge_another(&mut self, another: ByAnother) {
         let ByAnother { route, ohkami } = another;
         let another_routes = ohkami.into_router();
@@ -204,6 +253,119 @@ impl Router {
         } apply_to! { GET, PUT, POST, PATCH, DELETE, OPTIONS }
     }
 
+    /// `outer`: fangs to be applied to this router later, like `Ohkami`'s own fangs
+    pub(crate) fn routes(&self, outer: Option<&dyn Fangs>) -> Vec<RouteInfo> {
+        let mut routes = Vec::new();
+        for (route, handlers_meta) in &self.routes {
+            for (method, handler_meta) in handlers_meta.iter() {
+                let node = match method {
+                    Method::GET    => &self.GET,
+                    Method::PUT    => &self.PUT,
+                    Method::POST   => &self.POST,
+                    Method::PATCH  => &self.PATCH,
+                    Method::DELETE => &self.DELETE,
+                    _ => continue
+                }.find(route.clone().into_iter());
+                let Some(node) = node else {continue};
+
+                let fangs = outer.into_iter()
+                    .chain(node.fangses.iter().rev().map(|fangs| &**fangs))
+                    .flat_map(Fangs::type_names)
+                    .chain(handler_meta.fangs.iter().copied())
+                    .collect();
+
+                #[cfg(feature="openapi")]
+                let operation_id = node.handler.as_ref().and_then(|h| {
+                    let operation = node.fangses.iter().map(|fangs| &**fangs)
+                        .chain(outer)
+                        .fold(h.openapi_operation.clone(), |operation, fangs| {
+                            fangs.openapi_map_operation(operation)
+                        });
+                    operation.get_operationId()
+                });
+
+                routes.push(RouteInfo {
+                    path: route.literal().to_owned(),
+                    method: *method,
+                    handler: handler_meta.name,
+                    fangs,
+                    #[cfg(feature="openapi")]
+                    operation_id,
+                });
+            }
+        }
+        routes.sort_by(|a, b| Ord::cmp(
+            &(&a.path, a.method.as_str()),
+            &(&b.path, b.method.as_str())
+        ));
+        routes
+    }
+
+    /// All the registered handlers, each with the fangs applied to it.
+    pub(crate) fn handlers(&self) -> Vec<(RouteSegments, Method, Handler, HandlerMeta)> {
+        let mut handlers = Vec::new();
+        for (route, handlers_meta) in &self.routes {
+            for (method, handler_meta) in handlers_meta.iter() {
+                let node = match method {
+                    Method::GET    => &self.GET,
+                    Method::PUT    => &self.PUT,
+                    Method::POST   => &self.POST,
+                    Method::PATCH  => &self.PATCH,
+                    Method::DELETE => &self.DELETE,
+                    _ => continue
+                }.find(route.clone().into_iter());
+                let Some(Node { handler: Some(handler), fangses, .. }) = node else {continue};
+
+                let mut meta = handler_meta.clone();
+                meta.fangs.splice(0..0, fangses.iter().rev().flat_map(|fangs| fangs.type_names()));
+
+                handlers.push((route.clone(), *method, fangses.apply_to(handler.clone()), meta));
+            }
+        }
+        handlers
+    }
+
+    /// Routes where fallback handlers are registered by `register_fallback`.
+    pub(crate) fn fallback_routes(&self) -> Vec<RouteSegments> {
+        fn collect(node: &Node, literal: String, routes: &mut Vec<RouteSegments>) {
+            if node.fallback.is_some() {
+                routes.push(RouteSegments::from_literal(
+                    if literal.is_empty() {String::from("/")} else {literal.clone()}
+                ));
+            }
+            for child in &node.children {
+                let (Some(Pattern::Static(segment)) | Some(Pattern::Param(segment))) = &child.pattern else {continue};
+                collect(child, literal.clone() + segment, routes);
+            }
+        }
+
+        let mut routes = Vec::new();
+        collect(&self.GET, String::new(), &mut routes);
+        routes
+    }
+
+    /// The handler for `method` requests to `route` not matching any handler:
+    /// the nearest fallback handler or `Not Found`, with the fangs applied.
+    pub(crate) fn fallback_handler(&self, method: Method, route: &RouteSegments) -> Handler {
+        let mut node = match method {
+            Method::PUT    => &self.PUT,
+            Method::POST   => &self.POST,
+            Method::PATCH  => &self.PATCH,
+            Method::DELETE => &self.DELETE,
+            _              => &self.GET,
+        };
+        let mut fallback = node.fallback.as_ref();
+        for segment in route.clone() {
+            let pattern = Pattern::from(segment);
+            let Some(child) = node.children.iter()
+                .find(|child| child.pattern.as_ref().unwrap().matches(&pattern))
+                else {break};
+            node = child;
+            fallback = node.fallback.as_ref().or(fallback);
+        }
+        node.fangses.apply_to(fallback.cloned().unwrap_or_else(Handler::default_not_found))
+    }
+
     pub(crate) fn finalize(mut self) -> (
         super::r#final::Router,
         HashMap<RouteSegments, TupleMap<Method, HandlerMeta>>
@@ -234,6 +396,7 @@ impl Node {
         Self {
             pattern:  None,
             handler:  None,
+            fallback: None,
             fangses:  FangsList::new(),
             children: vec![],
         }
@@ -242,11 +405,24 @@ impl Node {
         Self {
             pattern:  Some(pattern),
             handler:  None,
+            fallback: None,
             fangses:  FangsList::new(),
             children: vec![],
         }
     }
 
+    fn find(&self, mut route: RouteSegmentsIterator) -> Option<&Node> {
+        match route.next() {
+            None => Some(self),
+            Some(segment) => {
+                let pattern = Pattern::from(segment);
+                self.children.iter()
+                    .find(|child| child.pattern.as_ref().unwrap().matches(&pattern))?
+                    .find(route)
+            }
+        }
+    }
+
     fn machable_child_mut(&mut self, pattern: Pattern) -> Option<&mut Node> {
         for child in &mut self.children {
             if child.pattern.as_ref().unwrap().matches(&pattern) {
@@ -282,6 +458,27 @@ impl Node {
         }
     }
 
+    fn register_fallback(
+        &mut self,
+        mut route: RouteSegmentsIterator,
+        handler:   Handler,
+    ) -> Result<(), String> {
+        match route.next() {
+            None => self.set_fallback(handler),
+            Some(segment) => {
+                let pattern = Pattern::from(segment);
+                match self.machable_child_mut(pattern.clone()) {
+                    Some(child) => child.register_fallback(route, handler),
+                    None => {
+                        let mut child = Node::new(pattern);
+                        child.register_fallback(route, handler)?;
+                        self.append_child(child)
+                    }
+                }
+            }
+        }
+    }
+
     fn append_child(&mut self, new_child: Node) -> Result<(), String> {
         match new_child.pattern.as_ref().expect("Invalid child node: Child node must have pattern") {
             Pattern::Param(_) => {
@@ -317,6 +514,14 @@ impl Node {
         Ok(())
     }
 
+    fn set_fallback(&mut self, new_fallback: Handler) -> Result<(), String> {
+        if self.fallback.is_some() {
+            return Err(String::from("Conflicting fallback handler registering"))
+        }
+        self.fallback = Some(new_fallback);
+        Ok(())
+    }
+
     fn merge_node(
         &mut self,
         mut route_to_merge_root: RouteSegmentsIterator,
@@ -345,6 +550,7 @@ impl Node {
             pattern:  None, /* another_root must be a root node and has pattern `None` */
             fangses:  another_root_fangses,
             handler:  another_root_handler,
+            fallback: another_root_fallback,
             children: another_root_children,
         } = another_root else {
             panic!("Unexpectedly called `Node::merge_here` where `another_root` is not root node")
@@ -355,6 +561,9 @@ impl Node {
         if let Some(h) = another_root_handler {
             self.set_handler(h, allow_override_handler)?;
         }
+        if let Some(h) = another_root_fallback {
+            self.set_fallback(h)?;
+        }
 
         for ac in another_root_children {
             self.append_child(ac)?
@@ -457,6 +666,7 @@ const _: (/* Debugs */) = {
             f.debug_struct("")
                 .field("pattern",  &DebugSimpleOption(&self.pattern))
                 .field("handler",  &DebugSimpleOption(&self.handler))
+                .field("fallback", &DebugSimpleOption(&self.fallback))
                 .field("fangs",    &self.fangses)
                 .field("children", &self.children)
                 .finish()
@@ -46,6 +46,9 @@ impl Router {
                 Method::HEAD => {
                     let mut res = self.GET.search(&mut req.path).call_bite(req).await;
 
+                    #[cfg(feature="__rt_native__")]
+                    res.select_file_range(req);
+
                     /* not `res.drop_content()` to keep `Content-Type`, `Content-Length` */
                     res.content = Content::None;
 
@@ -55,6 +58,9 @@ impl Router {
             }).search(&mut req.path).call_bite(req).await
         };
 
+        #[cfg(feature="__rt_native__")]
+        res.select_file_range(req);
+
         res.complete();
 
         res
@@ -233,7 +239,9 @@ impl Pattern {
         match self {
             Pattern::Static(s) => {
                 let size = s.len();
-                if bytes.len() >= size && *s == unsafe {bytes.get_unchecked(..size)} {
+                if bytes.len() >= size && *s == unsafe {bytes.get_unchecked(..size)}
+                /* matches only at a segment boundary: `/abc` doesn't take through `/abcd` */
+                && bytes.get(size).is_none_or(|b| *b == b'/') {
                     Some(unsafe {bytes.get_unchecked(size..)})
                 } else {
                     None
@@ -270,18 +278,27 @@ const _: (/* conversions */) = {
     }
     
     impl From<base::Node> for Node {
-        fn from(mut base: base::Node) -> Self {
+        fn from(base: base::Node) -> Self {
+            Self::from_base(base, None)
+        }
+    }
+
+    impl Node {
+        /// `inherited_fallback`: the fallback handler of the nearest ancestor that has one
+        fn from_base(mut base: base::Node, inherited_fallback: Option<&Handler>) -> Self {
             /* skip compression on edge runtimes */
             #[cfg(feature="__rt_native__")]
             /* compress: merge single-child static pattern and compress routing tree */
             while base.children.len() == 1
                && base.handler.is_none()
+               && base.fallback.is_none()
                && base.pattern.as_ref().is_none_or(|p| p.is_static())
                && base.children[0].pattern.as_ref().unwrap(/* not root */).is_static()
             {
                 let child = base.children.pop().unwrap(/* base.children.len() == 1 */);
                 base.children = child.children;
                 base.handler = child.handler;
+                base.fallback = child.fallback;
                 base.fangses.append(child.fangses);
                 base.pattern = Some(match base.pattern {
                     None    => child.pattern.unwrap(/* not root */),
@@ -301,15 +318,21 @@ const _: (/* conversions */) = {
 
             #[cfg(feature="openapi")] let has_handler = base.handler.is_some();
 
-            let proc = base.fangses.clone().into_proc_with(base.handler.unwrap_or(Handler::default_not_found()));
-            #[cfg(feature="openapi")] let (proc, openapi_operation) = (proc.0, has_handler.then_some(proc.1));
+            let fallback = base.fallback.as_ref().or(inherited_fallback);
+
+            let children = base.children.into_iter()
+                .map(|child| Node::from_base(child, fallback))
+                .collect::<Vec<_>>().leak();
 
-            let catch = base.fangses.into_proc_with(Handler::default_not_found());
+            let catch = base.fangses.clone().into_proc_with(fallback.cloned().unwrap_or(Handler::default_not_found()));
             #[cfg(feature="openapi")] let catch = catch.0;
 
+            let proc = base.fangses.into_proc_with(base.handler.or(fallback.cloned()).unwrap_or(Handler::default_not_found()));
+            #[cfg(feature="openapi")] let (proc, openapi_operation) = (proc.0, has_handler.then_some(proc.1));
+
             Node {
                 pattern:  base.pattern.map(Pattern::from).unwrap_or(Pattern::Static(b"")),
-                children: base.children.into_iter().map(Node::from).collect::<Vec<_>>().leak(),
+                children,
 
                 proc,
                 catch,
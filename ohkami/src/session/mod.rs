@@ -38,7 +38,7 @@ impl Session {
             } else {
                 crate::warning!("[Panicked]");
             }
-            crate::Response::InternalServerError()
+            crate::typed::reject(crate::Status::InternalServerError, "")
         }
 
         #[cold]
@@ -63,6 +63,22 @@ const _: () = {
     }
 };
 
+/// An event of `data` in the `text/event-stream` format
+#[cfg(any(feature="__rt_native__", feature="gzip", feature="br", feature="zstd"))]
+pub(crate) fn message(data: &str) -> Vec<u8> {
+    let mut message = Vec::with_capacity(
+        /* capacity for a single line */
+        "data: ".len() + data.len() + "\n\n".len()
+    );
+    for line in data.split('\n') {
+        message.extend_from_slice(b"data: ");
+        message.extend_from_slice(line.as_bytes());
+        message.push(b'\n');
+    }
+    message.push(b'\n');
+    message
+}
+
 impl<T: Data> crate::IntoResponse for DataStream<T> {
     #[inline]
     fn into_response(self) -> crate::Response {
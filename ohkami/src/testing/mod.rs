@@ -30,6 +30,7 @@
 //! ```
 
 use crate::{Response, Request, Ohkami, Status, Method};
+use crate::response::Content;
 use crate::router::r#final::Router;
 
 use std::borrow::Cow;
@@ -184,6 +185,45 @@ impl TestResponse {
         Self(response)
     }
 }
+impl TestResponse {
+    /// Receive whole the body stream or file like a client,
+    /// to check it by `text`, `html`, `json` or `content`
+    pub async fn collect_body(mut self) -> Self {
+        if let Content::BodyStream(stream) = &mut self.0.content {
+            use crate::util::StreamExt;
+
+            let mut body = Vec::new();
+            while let Some(chunk) = stream.next().await {
+                match chunk {
+                    Ok(chunk) => body.extend_from_slice(&chunk),
+                    Err(e) => {
+                        crate::warning!("[TestResponse::collect_body] body stream failed: {e}");
+                        break
+                    }
+                }
+            }
+            self.0.content = Content::Payload(body.into());
+        }
+        #[cfg(feature="__rt_native__")]
+        if let Content::File(file) = &self.0.content {
+            use crate::util::StreamExt;
+
+            let mut chunks = Box::pin(crate::response::file::read_chunks(file.file.clone(), file.range.clone()));
+            let mut body = Vec::new();
+            while let Some(chunk) = chunks.next().await {
+                match chunk {
+                    Ok(chunk) => body.extend_from_slice(&chunk),
+                    Err(e) => {
+                        crate::warning!("[TestResponse::collect_body] failed to read file: {e}");
+                        break
+                    }
+                }
+            }
+            self.0.content = Content::Payload(body.into());
+        }
+        self
+    }
+}
 impl TestResponse {
     pub fn status(&self) -> Status {
         self.0.status
@@ -195,6 +235,9 @@ impl TestResponse {
     pub fn headers(&self) -> impl Iterator<Item = (&str, &str)> {
         self.0.headers.iter()
     }
+    pub fn trailer(&self, name: &str) -> Option<String> {
+        self.0.trailers.as_ref()?.get(name)
+    }
 
     pub fn text(&self) -> Option<&str> {
         if self.0.headers.ContentType()?.starts_with("text/plain") {
@@ -214,4 +257,9 @@ impl TestResponse {
             Some(serde_json::from_slice(body))
         } else {None}
     }
+    pub fn content(&self, content_type: &str) -> Option<&[u8]> {
+        if self.0.headers.ContentType()?.starts_with(content_type) {
+            self.0.content.as_bytes()
+        } else {None}
+    }
 }
@@ -1,4 +1,6 @@
-use crate::{Request, FromRequest};
+use crate::{Request, FromRequest, IntoResponse, Response};
+use crate::response::ResponseHeaders;
+use std::borrow::Cow;
 
 #[cfg(not(feature="openapi"))]
 mod bound {
@@ -212,3 +214,377 @@ impl<'req, Fields: crate::format::bound::Incoming<'req>> FromRequest<'req> for C
         )
     }
 }
+
+/// `Accept` header value parsed into media ranges and their weights
+/// ( <https://httpwg.org/specs/rfc9110.html#field.accept> ).
+///
+/// Media type parameters other than `q` are ignored in matching.
+///
+/// ## Example
+///
+/// ```
+/// use ohkami::typed::header::MediaRanges;
+///
+/// let accept = MediaRanges::parse("text/*;q=0.5, application/json");
+/// assert_eq!(accept.quality("text/csv"), 500);
+/// assert_eq!(accept.quality("image/png"), 0);
+/// assert_eq!(accept.preferred(["text/csv", "application/json"]), Some("application/json"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct MediaRanges<'req>(Vec<(&'req str, &'req str, u16)>);
+
+impl<'req> MediaRanges<'req> {
+    pub fn parse(raw: &'req str) -> Self {
+        Self(raw.split(',').filter_map(|range| {
+            let mut parts = range.split(';');
+            let (type_, subtype) = parts.next()?.trim().split_once('/')?;
+            let q = parts
+                .find_map(|p| p.trim().strip_prefix("q=").or_else(|| p.trim().strip_prefix("Q=")))
+                .map(crate::header::encoding::parse_qvalue)
+                .unwrap_or(Some(1000))?;
+            Some((type_.trim(), subtype.trim(), q))
+        }).collect())
+    }
+
+    /// Weight of `media_type` in thousandths, taken from the most specific
+    /// matching range. `0` means not acceptable.
+    ///
+    /// An empty `Accept` accepts anything.
+    pub fn quality(&self, media_type: &str) -> u16 {
+        if self.0.is_empty() {
+            return 1000
+        }
+
+        let media_type = media_type.split_once(';').map_or(media_type, |(m, _)| m).trim();
+        let Some((type_, subtype)) = media_type.split_once('/') else {
+            return 0
+        };
+
+        self.0.iter()
+            .filter_map(|&(t, s, q)| match (t, s) {
+                ("*", "*") => Some((0, q)),
+                (t, "*") if t.eq_ignore_ascii_case(type_) => Some((1, q)),
+                (t, s) if t.eq_ignore_ascii_case(type_) && s.eq_ignore_ascii_case(subtype) => Some((2, q)),
+                _ => None
+            })
+            .max_by_key(|&(specificity, _)| specificity)
+            .map_or(0, |(_, q)| q)
+    }
+
+    pub fn accepts(&self, media_type: &str) -> bool {
+        self.quality(media_type) > 0
+    }
+
+    /// Select the most preferred one of `candidates`, the earlier on a tie.
+    pub fn preferred<'m>(&self, candidates: impl IntoIterator<Item = &'m str>) -> Option<&'m str> {
+        let mut selected: Option<(&'m str, u16)> = None;
+        for candidate in candidates {
+            let q = self.quality(candidate);
+            if q == 0 || selected.is_some_and(|(_, max)| max >= q) {
+                continue
+            }
+            selected = Some((candidate, q));
+        }
+        selected.map(|(media_type, _)| media_type)
+    }
+}
+
+impl<'req> FromHeader<'req> for MediaRanges<'req> {
+    type Error = std::convert::Infallible;
+    fn from_header(raw: &'req str) -> Result<Self, Self::Error> {
+        Ok(Self::parse(raw))
+    }
+}
+
+#[cfg(feature="openapi")]
+impl crate::openapi::Schema for MediaRanges<'_> {
+    fn schema() -> impl Into<crate::openapi::schema::SchemaRef> {
+        crate::openapi::string()
+    }
+}
+
+#[cfg(test)]
+#[test] fn test_media_ranges() {
+    let accept = MediaRanges::parse("text/html, application/xhtml+xml, application/xml;q=0.9, */*;q=0.8");
+    assert_eq!(accept.quality("text/html"), 1000);
+    assert_eq!(accept.quality("text/html; charset=UTF-8"), 1000);
+    assert_eq!(accept.quality("application/xml"), 900);
+    assert_eq!(accept.quality("application/json"), 800);
+    assert_eq!(accept.preferred(["application/json", "application/xml"]), Some("application/xml"));
+
+    let accept = MediaRanges::parse("application/json;q=0, text/*;q=0.3, text/csv");
+    assert!(!accept.accepts("application/json"));
+    assert_eq!(accept.quality("text/plain"), 300);
+    assert_eq!(accept.preferred(["application/json", "text/plain", "text/csv"]), Some("text/csv"));
+    assert_eq!(accept.preferred(["application/json", "image/png"]), None);
+
+    let accept = MediaRanges::parse("");
+    assert!(accept.accepts("application/msgpack"));
+}
+
+
+/// Serialized into a response header value
+pub trait IntoHeader {
+    fn into_header(self) -> Cow<'static, str>;
+
+    #[cfg(feature="openapi")]
+    fn openapi_schema() -> crate::openapi::schema::SchemaRef {
+        crate::openapi::string().into()
+    }
+}
+const _: () = {
+    impl IntoHeader for &'static str {
+        fn into_header(self) -> Cow<'static, str> {
+            Cow::Borrowed(self)
+        }
+    }
+
+    impl IntoHeader for String {
+        fn into_header(self) -> Cow<'static, str> {
+            Cow::Owned(self)
+        }
+    }
+
+    impl IntoHeader for Cow<'static, str> {
+        fn into_header(self) -> Cow<'static, str> {
+            self
+        }
+    }
+
+    macro_rules! integers {
+        ($( $int:ty ),*) => {$(
+            impl IntoHeader for $int {
+                fn into_header(self) -> Cow<'static, str> {
+                    Cow::Owned(self.to_string())
+                }
+
+                #[cfg(feature="openapi")]
+                fn openapi_schema() -> crate::openapi::schema::SchemaRef {
+                    crate::openapi::integer().into()
+                }
+            }
+        )*};
+    } integers! { u8, u16, u32, u64, usize }
+
+    /// HTTP-date ( IMF-fixdate ) like `Sun, 06 Nov 1994 08:49:37 GMT`
+    impl IntoHeader for std::time::SystemTime {
+        fn into_header(self) -> Cow<'static, str> {
+            let unix_timestamp = self.duration_since(std::time::UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs());
+            Cow::Owned(ohkami_lib::time::imf_fixdate(unix_timestamp))
+        }
+    }
+
+    /// delta-seconds like `120`
+    impl IntoHeader for std::time::Duration {
+        fn into_header(self) -> Cow<'static, str> {
+            Cow::Owned(self.as_secs().to_string())
+        }
+
+        #[cfg(feature="openapi")]
+        fn openapi_schema() -> crate::openapi::schema::SchemaRef {
+            crate::openapi::integer().into()
+        }
+    }
+};
+
+/// Typed response header(s) set by [`WithHeaders`]
+///
+/// Implemented by typed headers like `Location<Value>` ( `Value: IntoHeader` )
+/// and tuples of them.
+pub trait ResponseHeader {
+    fn set_to(self, headers: &mut ResponseHeaders);
+
+    #[cfg(feature="openapi")]
+    fn openapi_headers() -> Vec<(&'static str, crate::openapi::schema::SchemaRef)>;
+}
+macro_rules! tuple_response_headers {
+    ($( ( $($H:ident),+ ) )*) => {$(
+        #[allow(non_snake_case)]
+        impl<$($H: ResponseHeader),+> ResponseHeader for ($($H,)+) {
+            fn set_to(self, headers: &mut ResponseHeaders) {
+                let ($($H,)+) = self;
+                $( $H.set_to(headers); )+
+            }
+
+            #[cfg(feature="openapi")]
+            fn openapi_headers() -> Vec<(&'static str, crate::openapi::schema::SchemaRef)> {
+                [$($H::openapi_headers()),+].concat()
+            }
+        }
+    )*};
+} tuple_response_headers! {
+    (H1)
+    (H1, H2)
+    (H1, H2, H3)
+    (H1, H2, H3, H4)
+    (H1, H2, H3, H4, H5)
+    (H1, H2, H3, H4, H5, H6)
+    (H1, H2, H3, H4, H5, H6, H7)
+    (H1, H2, H3, H4, H5, H6, H7, H8)
+}
+
+macro_rules! typed_response_header {
+    (
+        shared { $( $Shared:ident : $shared_key:literal ),* $(,)? }
+        $( $Name:ident : $key:literal ),* $(,)?
+    ) => {
+        $(
+            impl<Value: IntoHeader> ResponseHeader for $Shared<Value> {
+                fn set_to(self, headers: &mut ResponseHeaders) {
+                    headers.set().$Shared(self.0.into_header());
+                }
+
+                #[cfg(feature="openapi")]
+                fn openapi_headers() -> Vec<(&'static str, crate::openapi::schema::SchemaRef)> {
+                    vec![($shared_key, Value::openapi_schema())]
+                }
+            }
+        )*
+        $(
+            /// Set the response header value of a type implementing
+            /// [`IntoHeader`](crate::typed::header::IntoHeader) trait
+            /// in [`WithHeaders`](crate::typed::WithHeaders) .
+            pub struct $Name<Value>(pub Value);
+
+            impl<Value: IntoHeader> ResponseHeader for $Name<Value> {
+                fn set_to(self, headers: &mut ResponseHeaders) {
+                    headers.set().$Name(self.0.into_header());
+                }
+
+                #[cfg(feature="openapi")]
+                fn openapi_headers() -> Vec<(&'static str, crate::openapi::schema::SchemaRef)> {
+                    vec![($key, Value::openapi_schema())]
+                }
+            }
+
+            impl<Value: std::fmt::Debug> std::fmt::Debug for $Name<Value> {
+                fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                    f.write_str(stringify!($Name))?;
+                    f.write_str("(")?;
+                    write!(f, "{:?}", self.0)?;
+                    f.write_str(")")?;
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+typed_response_header! {
+    shared {
+        CacheControl:       "Cache-Control",
+        ContentDisposition: "Content-Disposition",
+        ContentLanguage:    "Content-Language",
+        ContentLocation:    "Content-Location",
+        Link:               "Link",
+    }
+    AcceptRanges:            "Accept-Ranges",
+    Age:                     "Age",
+    Allow:                   "Allow",
+    AltSvc:                  "Alt-Svc",
+    CacheStatus:             "Cache-Status",
+    CDNCacheControl:         "CDN-Cache-Control",
+    ContentRange:            "Content-Range",
+    ContentSecurityPolicy:   "Content-Security-Policy",
+    ETag:                    "ETag",
+    Expires:                 "Expires",
+    LastModified:            "Last-Modified",
+    Location:                "Location",
+    ProxyAuthenticate:       "Proxy-Authenticate",
+    ReferrerPolicy:          "Referrer-Policy",
+    Refresh:                 "Refresh",
+    RetryAfter:              "Retry-After",
+    Server:                  "Server",
+    StrictTransportSecurity: "Strict-Transport-Security",
+    Vary:                    "Vary",
+    WWWAuthenticate:         "WWW-Authenticate",
+    XContentTypeOptions:     "X-Content-Type-Options",
+    XFrameOptions:           "X-Frame-Options",
+}
+
+/// Response of `B` with typed headers `H`, which are also documented
+/// in OpenAPI when `openapi` feature is activated.
+///
+/// The headers are set only on success ( 2xx or 3xx ) responses,
+/// not on errors of `B` like `Result::Err`.
+///
+/// ## Example
+///
+/// ```
+/// use ohkami::prelude::*;
+/// use ohkami::typed::{WithHeaders, status::Created, header::{Location, LastModified}};
+/// use std::time::SystemTime;
+///
+/// async fn create_article(
+/// ) -> WithHeaders<(Location<String>, LastModified<SystemTime>), Created<String>> {
+///     let id = 42;
+///     WithHeaders(
+///         (Location(format!("/articles/{id}")), LastModified(SystemTime::now())),
+///         Created(String::from("created"))
+///     )
+/// }
+/// ```
+pub struct WithHeaders<H: ResponseHeader, B: IntoResponse>(pub H, pub B);
+
+impl<H: ResponseHeader, B: IntoResponse> IntoResponse for WithHeaders<H, B> {
+    #[inline]
+    fn into_response(self) -> Response {
+        let mut res = self.1.into_response();
+        if (200..400).contains(&res.status.code()) {
+            self.0.set_to(&mut res.headers);
+        }
+        res
+    }
+
+    #[cfg(feature="openapi")]
+    fn openapi_responses() -> crate::openapi::Responses {
+        H::openapi_headers().into_iter().fold(B::openapi_responses(), |responses, (name, schema)|
+            responses.header(name, crate::openapi::response::ResponseHeader::of(schema))
+        )
+    }
+}
+
+#[cfg(test)]
+#[test] fn test_with_headers() {
+    use crate::typed::status::{Created, NotFound};
+    use std::time::{Duration, UNIX_EPOCH};
+
+    let res = WithHeaders(
+        (
+            Location("/articles/42"),
+            LastModified(UNIX_EPOCH + Duration::from_secs(784111777)),
+            CacheControl("no-cache"),
+            RetryAfter(Duration::from_secs(120)),
+        ),
+        Created("created")
+    ).into_response();
+
+    assert_eq!(res.status, crate::Status::Created);
+    assert_eq!(res.headers.Location(), Some("/articles/42"));
+    assert_eq!(res.headers.LastModified(), Some("Sun, 06 Nov 1994 08:49:37 GMT"));
+    assert_eq!(res.headers.CacheControl(), Some("no-cache"));
+    assert_eq!(res.headers.RetryAfter(), Some("120"));
+    assert_eq!(res.payload(), Some(&b"created"[..]));
+
+    let res = WithHeaders(
+        (Location("/articles/42"), ETag("\"42\"")),
+        Err::<Created<&str>, _>(NotFound("not found"))
+    ).into_response();
+    assert_eq!(res.status, crate::Status::NotFound);
+    assert_eq!(res.headers.Location(), None);
+    assert_eq!(res.headers.ETag(), None);
+
+    #[cfg(feature="openapi")] {
+        let responses = <WithHeaders<(Location<&str>, RetryAfter<Duration>), Created<&str>>>::openapi_responses();
+        let responses = serde_json::to_value(&responses).unwrap();
+        assert_eq!(responses["201"]["headers"]["Location"]["schema"]["type"], "string");
+        assert_eq!(responses["201"]["headers"]["Retry-After"]["schema"]["type"], "integer");
+        assert_eq!(responses["201"]["headers"]["Location"]["required"], true);
+
+        let responses = <WithHeaders<Location<&str>, Result<Created<&str>, NotFound<&str>>>>::openapi_responses();
+        let responses = serde_json::to_value(&responses).unwrap();
+        assert_eq!(responses["201"]["headers"]["Location"]["required"], true);
+        assert!(responses["404"].is_object());
+        assert!(responses["404"]["headers"].is_null());
+    }
+}
@@ -1,2 +1,7 @@
 pub mod status;
 pub mod header;
+pub use header::WithHeaders;
+
+mod problem;
+pub use problem::Problem;
+pub(crate) use problem::reject;
@@ -0,0 +1,296 @@
+use crate::{IntoResponse, Response, Status};
+use std::borrow::Cow;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+#[cfg(feature="openapi")]
+use crate::openapi;
+
+
+/// # Problem Details for HTTP APIs
+/// 
+/// An error response in `application/problem+json`
+/// ( [RFC 9457](https://www.rfc-editor.org/rfc/rfc9457) ).
+/// 
+/// `title` defaults to the reason phrase of `status` when `type` is not set
+/// ( means `about:blank` ).
+/// 
+/// <br>
+/// 
+/// *example.rs*
+/// ```
+/// use ohkami::prelude::*;
+/// use ohkami::typed::Problem;
+/// 
+/// async fn withdraw(amount: u64) -> Result<Status, Problem> {
+///     let balance = 30;
+///     if amount > balance {
+///         return Err(Problem::new(Status::Forbidden)
+///             .with_type("https://example.com/probs/out-of-credit")
+///             .with_title("You do not have enough credit.")
+///             .with_detail(format!("Your current balance is {balance}, but that costs {amount}."))
+///             .with_instance("/account/12345/msgs/abc")
+///             .with_extension("balance", balance))
+///     }
+///     Ok(Status::NoContent)
+/// }
+/// ```
+/// 
+/// <br>
+/// 
+/// Built-in rejections ( of `FromRequest`, `FromBody` and builtin fangs ) respond
+/// `Problem`s too after `Problem::use_for_rejections(true)`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Problem {
+    pub type_:      Option<Cow<'static, str>>,
+    pub title:      Option<Cow<'static, str>>,
+    pub status:     Status,
+    pub detail:     Option<Cow<'static, str>>,
+    pub instance:   Option<Cow<'static, str>>,
+    pub extensions: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(not(test))]
+static USE_FOR_REJECTIONS: AtomicBool = AtomicBool::new(false);
+#[cfg(test)]
+thread_local! {
+    /* per thread in tests, not to affect the other tests running in parallel */
+    static USE_FOR_REJECTIONS: AtomicBool = const {AtomicBool::new(false)};
+}
+
+impl Problem {
+    pub fn new(status: Status) -> Self {
+        Self {
+            type_:      None,
+            title:      None,
+            status,
+            detail:     None,
+            instance:   None,
+            extensions: serde_json::Map::new(),
+        }
+    }
+
+    /// URI reference identifying the problem type
+    pub fn with_type(mut self, type_: impl Into<Cow<'static, str>>) -> Self {
+        self.type_ = Some(type_.into());
+        self
+    }
+
+    /// Short, human-readable summary of the problem type
+    pub fn with_title(mut self, title: impl Into<Cow<'static, str>>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Human-readable explanation specific to this occurrence of the problem
+    pub fn with_detail(mut self, detail: impl Into<Cow<'static, str>>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+
+    /// URI reference identifying this occurrence of the problem
+    pub fn with_instance(mut self, instance: impl Into<Cow<'static, str>>) -> Self {
+        self.instance = Some(instance.into());
+        self
+    }
+
+    /// Additional member of the problem details object.
+    /// 
+    /// Members of the standard names ( `type`, `title`, `status`, `detail`, `instance` )
+    /// are ignored.
+    pub fn with_extension(mut self, key: impl Into<String>, value: impl serde::Serialize) -> Self {
+        let key = key.into();
+        if matches!(&*key, "type" | "title" | "status" | "detail" | "instance") {
+            #[cfg(debug_assertions)] crate::warning!(
+                "[Problem] `{key}` is a standard member and can't be an extension"
+            );
+            return self
+        }
+        match serde_json::to_value(value) {
+            Ok(value) => {self.extensions.insert(key, value);}
+            Err(_e) => {
+                #[cfg(debug_assertions)] crate::warning!(
+                    "[Problem] failed to serialize extension `{key}`: {_e}"
+                );
+            }
+        }
+        self
+    }
+
+    /// Make built-in rejections ( of `FromRequest`, `FromBody` and builtin fangs )
+    /// `Problem`s instead of plain text responses.
+    /// 
+    /// This is a global switch; call it before `howl`.
+    pub fn use_for_rejections(yes: bool) {
+        #[cfg(not(test))]
+        USE_FOR_REJECTIONS.store(yes, Ordering::Relaxed);
+        #[cfg(test)]
+        USE_FOR_REJECTIONS.with(|it| it.store(yes, Ordering::Relaxed));
+    }
+}
+
+/// Response of a built-in rejection with `status` and `detail` ( empty for no detail ):
+/// `Problem` after `Problem::use_for_rejections(true)`, or a plain text response
+#[cold] #[inline(never)]
+pub(crate) fn reject(status: Status, detail: impl Into<Cow<'static, str>>) -> Response {
+    #[cfg(not(test))]
+    let as_problem = USE_FOR_REJECTIONS.load(Ordering::Relaxed);
+    #[cfg(test)]
+    let as_problem = USE_FOR_REJECTIONS.with(|it| it.load(Ordering::Relaxed));
+
+    rejection(as_problem, status, detail.into())
+}
+fn rejection(as_problem: bool, status: Status, detail: Cow<'static, str>) -> Response {
+    if as_problem {
+        let mut problem = Problem::new(status);
+        if !detail.is_empty() {
+            problem.detail = Some(detail);
+        }
+        problem.into_response()
+    } else if detail.is_empty() {
+        Response::new(status)
+    } else {
+        Response::new(status).with_text(detail)
+    }
+}
+
+const _: () = {
+    impl serde::Serialize for Problem {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            use serde::ser::SerializeMap;
+
+            let title = self.title.as_deref().or_else(|| self.type_.is_none().then(|| {
+                /* `status.as_str()` is like `404 Not Found` */
+                let status = self.status.as_str();
+                &status[status.find(' ').map_or(0, |i| i + 1)..]
+            }));
+
+            let mut map = serializer.serialize_map(None)?;
+            if let Some(type_) = &self.type_ {
+                map.serialize_entry("type", type_)?;
+            }
+            if let Some(title) = title {
+                map.serialize_entry("title", title)?;
+            }
+            map.serialize_entry("status", &self.status.code())?;
+            if let Some(detail) = &self.detail {
+                map.serialize_entry("detail", detail)?;
+            }
+            if let Some(instance) = &self.instance {
+                map.serialize_entry("instance", instance)?;
+            }
+            for (key, value) in &self.extensions {
+                map.serialize_entry(key, value)?;
+            }
+            map.end()
+        }
+    }
+
+    impl IntoResponse for Problem {
+        fn into_response(self) -> Response {
+            match serde_json::to_vec(&self) {
+                Ok(json) => Response::new(self.status)
+                    .with_payload("application/problem+json", json),
+                Err(_e) => {
+                    #[cfg(debug_assertions)] crate::warning!(
+                        "[Problem] failed to serialize: {_e}"
+                    );
+                    Response::new(self.status)
+                }
+            }
+        }
+
+        #[cfg(feature="openapi")]
+        fn openapi_responses() -> openapi::Responses {
+            openapi::Responses::new([]).or_default(
+                openapi::Response::when("Problem Details")
+                    .content("application/problem+json", <Self as openapi::Schema>::schema())
+            )
+        }
+    }
+
+    #[cfg(feature="openapi")]
+    impl openapi::Schema for Problem {
+        fn schema() -> impl Into<openapi::schema::SchemaRef> {
+            openapi::component("Problem", openapi::object()
+                .optional("type", openapi::string().format("uri-reference"))
+                .optional("title", openapi::string())
+                .property("status", openapi::integer())
+                .optional("detail", openapi::string())
+                .optional("instance", openapi::string().format("uri-reference"))
+            )
+        }
+    }
+};
+
+
+#[cfg(test)]
+#[test] fn test_problem_json() {
+    let problem = Problem::new(Status::Forbidden)
+        .with_type("https://example.com/probs/out-of-credit")
+        .with_title("You do not have enough credit.")
+        .with_detail("Your current balance is 30, but that costs 50.")
+        .with_instance("/account/12345/msgs/abc")
+        .with_extension("balance", 30)
+        .with_extension("status", 200);
+    assert_eq!(serde_json::to_string(&problem).unwrap(), r#"{"type":"https://example.com/probs/out-of-credit","title":"You do not have enough credit.","status":403,"detail":"Your current balance is 30, but that costs 50.","instance":"/account/12345/msgs/abc","balance":30}"#);
+
+    let problem = Problem::new(Status::NotFound);
+    assert_eq!(serde_json::to_string(&problem).unwrap(), r#"{"title":"Not Found","status":404}"#);
+
+    let problem = Problem::new(Status::BadRequest).with_type("https://example.com/probs/bad");
+    assert_eq!(serde_json::to_string(&problem).unwrap(), r#"{"type":"https://example.com/probs/bad","status":400}"#);
+
+    let res = Problem::new(Status::NotFound).into_response();
+    assert_eq!(res.status, Status::NotFound);
+    assert_eq!(res.headers.ContentType(), Some("application/problem+json"));
+}
+
+#[cfg(test)]
+#[test] fn test_rejection() {
+    let res = rejection(false, Status::BadRequest, "invalid json".into());
+    assert_eq!(res.headers.ContentType(), Some("text/plain; charset=UTF-8"));
+    assert_eq!(res.payload(), Some(&b"invalid json"[..]));
+
+    let res = rejection(false, Status::NotFound, "".into());
+    assert_eq!(res.payload(), None);
+
+    let res = rejection(true, Status::BadRequest, "invalid json".into());
+    assert_eq!(res.status, Status::BadRequest);
+    assert_eq!(res.headers.ContentType(), Some("application/problem+json"));
+    assert_eq!(res.payload(), Some(&br#"{"title":"Bad Request","status":400,"detail":"invalid json"}"#[..]));
+
+    let res = rejection(true, Status::NotFound, "".into());
+    assert_eq!(res.payload(), Some(&br#"{"title":"Not Found","status":404}"#[..]));
+}
+
+#[cfg(all(test, feature="__rt_native__", feature="DEBUG"))]
+#[test] fn test_use_for_rejections() {
+    use crate::prelude::*;
+    use crate::testing::*;
+
+    let dir = std::env::temp_dir().join(format!("ohkami-problem-test-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("index.html"), "<p>top</p>").unwrap();
+    let dir: &'static str = dir.into_os_string().into_string().unwrap().leak();
+
+    let t = Ohkami::new((
+        "/static".Dir(dir).dev(true),
+        "/api".GET(|| async {"Hello"}),
+    )).test();
+
+    Problem::use_for_rejections(true);
+    crate::__rt__::testing::block_on(async {
+        let req = TestRequest::GET("/static/missing.html");
+        let res = t.oneshot(req).await;
+        assert_eq!(res.status(), Status::NotFound);
+        assert_eq!(res.content("application/problem+json"), Some(&br#"{"title":"Not Found","status":404}"#[..]));
+
+        let req = TestRequest::OPTIONS("/api")
+            .header("Access-Control-Request-Method", "DELETE");
+        let res = t.oneshot(req).await;
+        assert_eq!(res.status(), Status::BadRequest);
+        assert_eq!(res.content("application/problem+json"), Some(&br#"{"title":"Bad Request","status":400}"#[..]));
+        assert_eq!(res.header("Access-Control-Allow-Methods"), Some("GET, HEAD, OPTIONS"));
+    });
+    Problem::use_for_rejections(false);
+}
@@ -159,6 +159,7 @@ macro_rules! generate_redirects {
                         status: Status::$status,
                         headers: self.headers,
                         content: Content::None,
+                        trailers: None,
                     }
                 }
 
@@ -44,6 +44,8 @@ pub use crate::fang::bound::{SendOnNative, SendSyncOnNative};
 
 pub use ohkami_lib::{percent_decode, percent_decode_utf8, percent_encode};
 
+pub use ohkami_lib::mime;
+
 #[inline]
 pub fn base64_decode(input: impl AsRef<[u8]>) -> Result<Vec<u8>, base64::DecodeError> {
     use ::base64::engine::{Engine as _, general_purpose::STANDARD};
@@ -72,7 +74,7 @@ pub fn base64_url_encode(input: impl AsRef<[u8]>) -> String {
     URL_SAFE_NO_PAD.encode(input)
 }
 
-#[cfg(feature="sse")]
+#[cfg(any(feature="sse", feature="__rt__"))]
 pub use ohkami_lib::stream::{self, Stream, StreamExt};
 
 #[cfg(not(feature="rt_worker"))]
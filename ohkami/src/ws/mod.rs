@@ -44,13 +44,13 @@ impl<'req> crate::FromRequest<'req> for WebSocketContext<'req> {
     #[inline]
     fn from_request(req: &'req crate::Request) -> Option<Result<Self, Self::Error>> {
         if !matches!(req.headers.Connection()?, "Upgrade" | "upgrade") {
-            return Some(Err((|| crate::Response::BadRequest().with_text("upgrade request must have `Connection: Upgrade`"))()))
+            return Some(Err(crate::typed::reject(crate::Status::BadRequest, "upgrade request must have `Connection: Upgrade`")))
         }
         if !(req.headers.Upgrade()?.eq_ignore_ascii_case("websocket")) {
-            return Some(Err((|| crate::Response::BadRequest().with_text("upgrade request must have `Upgrade: websocket`"))()))
+            return Some(Err(crate::typed::reject(crate::Status::BadRequest, "upgrade request must have `Upgrade: websocket`")))
         }
         if !(req.headers.SecWebSocketVersion()? == "13") {
-            return Some(Err((|| crate::Response::BadRequest().with_text("upgrade request must have `Sec-WebSocket-Version: 13`"))()))
+            return Some(Err(crate::typed::reject(crate::Status::BadRequest, "upgrade request must have `Sec-WebSocket-Version: 13`")))
         }
 
         req.headers.SecWebSocketKey().map(|sec_websocket_key|
@@ -6,7 +6,7 @@ pub mod mime;
 pub mod num;
 
 pub mod time;
-pub use time::imf_fixdate;
+pub use time::{imf_fixdate, parse_imf_fixdate};
 
 mod slice;
 pub use slice::{Slice, CowSlice};
@@ -15,10 +15,18 @@ mod percent_encoding;
 pub use percent_encoding::{percent_encode, percent_decode, percent_decode_utf8};
 
 pub mod serde_utf8;
+pub mod serde_csv;
 pub mod serde_cookie;
 pub mod serde_multipart;
 pub mod serde_urlencoded;
 
+#[cfg(any(feature="msgpack", feature="cbor"))]
+mod serde_binary;
+#[cfg(feature="msgpack")]
+pub mod serde_msgpack;
+#[cfg(feature="cbor")]
+pub mod serde_cbor;
+
 #[cfg(feature="stream")]
 pub mod stream;
 #[cfg(feature="stream")]
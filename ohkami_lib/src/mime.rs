@@ -1,28 +1,327 @@
-/// Based on mimes lined up in <https://github.com/hyperium/mime/blob/master/mime-parse/src/constants.rs>
-/// 
+/// Based on the IANA media types registry ( <https://www.iana.org/assignments/media-types> )
+/// and the de facto extension mappings of [mime-db](https://github.com/jshttp/mime-db)
+///
+/// `extension` is expected to be **lowercase** and without leading `.`.
+///
 /// In current version, this **ONLY** support **UTF-8** as charset of `text/〜` files !
 pub const fn get_by_extension(extension: &str) -> Option<&'static str> {
     match extension.as_bytes() {
-        b"txt"   => Some("text/plain"),
-        b"html"  => Some("text/html"),
-        b"css"   => Some("text/css"),
-        b"js"    => Some("text/javascript"),
-        b"xml"   => Some("text/xml"),
-        b"csv"   => Some("text/csv"),
-        b"tsv"   => Some("text/tab-separated-values"),
-        b"vcard" => Some("text/vcard"),
-
-        b"jpeg"  => Some("image/jpeg"),
-        b"gif"   => Some("image/gif"),
-        b"png"   => Some("image/png"),
-        b"svg"   => Some("image/svg+xml"),
-
-        b"woff"  => Some("font/woff"),
-        b"woff2" => Some("font/woff2"),
-
-        b"json"  => Some("application/json"),
-        b"pdf"   => Some("application/pdf"),
+        /*===== text =====*/
+        b"txt" | b"text" | b"conf" | b"def" | b"list" | b"log" | b"in" | b"ini"
+                        => Some("text/plain"),
+        b"html" | b"htm" | b"shtml"
+                        => Some("text/html"),
+        b"css"          => Some("text/css"),
+        b"js" | b"mjs" | b"cjs"
+                        => Some("text/javascript"),
+        b"xml"          => Some("text/xml"),
+        b"csv"          => Some("text/csv"),
+        b"tsv"          => Some("text/tab-separated-values"),
+        b"vcard" | b"vcf"
+                        => Some("text/vcard"),
+        b"ics" | b"ifb" => Some("text/calendar"),
+        b"md" | b"markdown"
+                        => Some("text/markdown"),
+        b"rtx"          => Some("text/richtext"),
+        b"sgml" | b"sgm"
+                        => Some("text/sgml"),
+        b"vtt"          => Some("text/vtt"),
+        b"yaml" | b"yml"
+                        => Some("text/yaml"),
+        b"jsx"          => Some("text/jsx"),
+        b"mdx"          => Some("text/mdx"),
+        b"coffee"       => Some("text/coffeescript"),
+        b"less"         => Some("text/less"),
+        b"scss"         => Some("text/x-scss"),
+        b"sass"         => Some("text/x-sass"),
+        b"styl"         => Some("text/stylus"),
+        b"n3"           => Some("text/n3"),
+        b"ttl"          => Some("text/turtle"),
+        b"c" | b"h"     => Some("text/x-c"),
+        b"java"         => Some("text/x-java-source"),
+        b"py"           => Some("text/x-python"),
+        b"sh"           => Some("application/x-sh"),
+        b"toml"         => Some("application/toml"),
+
+        /*===== image =====*/
+        b"jpeg" | b"jpg" | b"jpe"
+                        => Some("image/jpeg"),
+        b"gif"          => Some("image/gif"),
+        b"png"          => Some("image/png"),
+        b"apng"         => Some("image/apng"),
+        b"svg" | b"svgz"
+                        => Some("image/svg+xml"),
+        b"webp"         => Some("image/webp"),
+        b"avif"         => Some("image/avif"),
+        b"heic"         => Some("image/heic"),
+        b"heif"         => Some("image/heif"),
+        b"jxl"          => Some("image/jxl"),
+        b"jp2" | b"jpg2"
+                        => Some("image/jp2"),
+        b"bmp"          => Some("image/bmp"),
+        b"ico" | b"cur" => Some("image/x-icon"),
+        b"tif" | b"tiff"
+                        => Some("image/tiff"),
+        b"psd"          => Some("image/vnd.adobe.photoshop"),
+
+        /*===== audio =====*/
+        b"mp3"          => Some("audio/mpeg"),
+        b"m4a"          => Some("audio/mp4"),
+        b"aac"          => Some("audio/aac"),
+        b"oga" | b"ogg" | b"opus" | b"spx"
+                        => Some("audio/ogg"),
+        b"wav"          => Some("audio/wav"),
+        b"weba"         => Some("audio/webm"),
+        b"flac"         => Some("audio/flac"),
+        b"mid" | b"midi" | b"kar" | b"rmi"
+                        => Some("audio/midi"),
+        b"aif" | b"aiff" | b"aifc"
+                        => Some("audio/x-aiff"),
+
+        /*===== video =====*/
+        b"mp4" | b"mp4v" | b"mpg4" | b"m4v"
+                        => Some("video/mp4"),
+        b"webm"         => Some("video/webm"),
+        b"ogv"          => Some("video/ogg"),
+        b"mpeg" | b"mpg" | b"mpe"
+                        => Some("video/mpeg"),
+        b"mov" | b"qt"  => Some("video/quicktime"),
+        b"avi"          => Some("video/x-msvideo"),
+        b"mkv"          => Some("video/x-matroska"),
+        b"flv"          => Some("video/x-flv"),
+        b"wmv"          => Some("video/x-ms-wmv"),
+        b"3gp"          => Some("video/3gpp"),
+        b"3g2"          => Some("video/3gpp2"),
+        b"ts" | b"m2ts" | b"mts"
+                        => Some("video/mp2t"),
+
+        /*===== font =====*/
+        b"woff"         => Some("font/woff"),
+        b"woff2"        => Some("font/woff2"),
+        b"ttf"          => Some("font/ttf"),
+        b"otf"          => Some("font/otf"),
+        b"ttc"          => Some("font/collection"),
+        b"eot"          => Some("application/vnd.ms-fontobject"),
+
+        /*===== model =====*/
+        b"gltf"         => Some("model/gltf+json"),
+        b"glb"          => Some("model/gltf-binary"),
+        b"obj"          => Some("model/obj"),
+        b"stl"          => Some("model/stl"),
+
+        /*===== application =====*/
+        b"json" | b"map"
+                        => Some("application/json"),
+        b"jsonld"       => Some("application/ld+json"),
+        b"webmanifest"  => Some("application/manifest+json"),
+        b"geojson"      => Some("application/geo+json"),
+        b"pdf"          => Some("application/pdf"),
+        b"wasm"         => Some("application/wasm"),
+        b"xhtml" | b"xht"
+                        => Some("application/xhtml+xml"),
+        b"rss"          => Some("application/rss+xml"),
+        b"atom"         => Some("application/atom+xml"),
+        b"xsl" | b"xslt"
+                        => Some("application/xslt+xml"),
+        b"dtd"          => Some("application/xml-dtd"),
+        b"rdf"          => Some("application/rdf+xml"),
+        b"wsdl"         => Some("application/wsdl+xml"),
+        b"graphql" | b"gql"
+                        => Some("application/graphql"),
+        b"sql"          => Some("application/sql"),
+        b"rtf"          => Some("application/rtf"),
+        b"ps" | b"ai" | b"eps"
+                        => Some("application/postscript"),
+        b"epub"         => Some("application/epub+zip"),
+        b"doc" | b"dot" => Some("application/msword"),
+        b"docx"         => Some("application/vnd.openxmlformats-officedocument.wordprocessingml.document"),
+        b"xls"          => Some("application/vnd.ms-excel"),
+        b"xlsx"         => Some("application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"),
+        b"ppt"          => Some("application/vnd.ms-powerpoint"),
+        b"pptx"         => Some("application/vnd.openxmlformats-officedocument.presentationml.presentation"),
+        b"odt"          => Some("application/vnd.oasis.opendocument.text"),
+        b"ods"          => Some("application/vnd.oasis.opendocument.spreadsheet"),
+        b"odp"          => Some("application/vnd.oasis.opendocument.presentation"),
+        b"apk"          => Some("application/vnd.android.package-archive"),
+        b"kml"          => Some("application/vnd.google-earth.kml+xml"),
+        b"kmz"          => Some("application/vnd.google-earth.kmz"),
+        b"xul"          => Some("application/vnd.mozilla.xul+xml"),
+        b"jar" | b"war" | b"ear"
+                        => Some("application/java-archive"),
+        b"class"        => Some("application/java-vm"),
+        b"ser"          => Some("application/java-serialized-object"),
+        b"swf"          => Some("application/x-shockwave-flash"),
+        b"crt" | b"der" | b"cer"
+                        => Some("application/pkix-cert"),
+        b"pem"          => Some("application/x-pem-file"),
+        b"p12" | b"pfx" => Some("application/x-pkcs12"),
+        b"cbor"         => Some("application/cbor"),
+        b"msgpack"      => Some("application/msgpack"),
+        b"proto"        => Some("application/x-protobuf"),
+        b"ndjson" | b"jsonl"
+                        => Some("application/x-ndjson"),
+        b"bin" | b"exe" | b"dll" | b"so" | b"dmg" | b"iso" | b"img" | b"msi" | b"deb" | b"dump"
+                        => Some("application/octet-stream"),
+
+        /*===== archive =====*/
+        b"zip"          => Some("application/zip"),
+        b"gz" | b"tgz"  => Some("application/gzip"),
+        b"br"           => Some("application/x-brotli"),
+        b"zst"          => Some("application/zstd"),
+        b"bz"           => Some("application/x-bzip"),
+        b"bz2" | b"boz" => Some("application/x-bzip2"),
+        b"xz"           => Some("application/x-xz"),
+        b"7z"           => Some("application/x-7z-compressed"),
+        b"rar"          => Some("application/vnd.rar"),
+        b"tar"          => Some("application/x-tar"),
+        b"lz"           => Some("application/x-lzip"),
+        b"cpio"         => Some("application/x-cpio"),
 
         _ => None
     }
 }
+
+/// Guess MIME type from the leading bytes of a content
+/// ( based on [MIME Sniffing Standard](https://mimesniff.spec.whatwg.org) ),
+/// falling back to `text/plain` when it seems to be a UTF-8 text.
+pub fn sniff(content: &[u8]) -> Option<&'static str> {
+    const SIGNATURES: &[(&[u8], &str)] = &[
+        (b"\x89PNG\r\n\x1A\n",     "image/png"),
+        (b"\xFF\xD8\xFF",          "image/jpeg"),
+        (b"GIF87a",                "image/gif"),
+        (b"GIF89a",                "image/gif"),
+        (b"\x00\x00\x01\x00",      "image/x-icon"),
+        (b"\x00\x00\x02\x00",      "image/x-icon"),
+        (b"%PDF-",                 "application/pdf"),
+        (b"%!PS-Adobe-",           "application/postscript"),
+        (b"\x00asm",               "application/wasm"),
+        (b"PK\x03\x04",            "application/zip"),
+        (b"\x1F\x8B\x08",          "application/gzip"),
+        (b"\x28\xB5\x2F\xFD",      "application/zstd"),
+        (b"BZh",                   "application/x-bzip2"),
+        (b"\xFD7zXZ\x00",          "application/x-xz"),
+        (b"7z\xBC\xAF\x27\x1C",    "application/x-7z-compressed"),
+        (b"Rar!\x1A\x07",          "application/vnd.rar"),
+        (b"wOFF",                  "font/woff"),
+        (b"wOF2",                  "font/woff2"),
+        (b"OTTO",                  "font/otf"),
+        (b"\x00\x01\x00\x00",      "font/ttf"),
+        (b"ttcf",                  "font/collection"),
+        (b"ID3",                   "audio/mpeg"),
+        (b"OggS\x00",              "audio/ogg"),
+        (b"fLaC",                  "audio/flac"),
+        (b"MThd\x00\x00\x00\x06",  "audio/midi"),
+        (b"\x1A\x45\xDF\xA3",      "video/webm"),
+    ];
+
+    if let Some((_, mime)) = SIGNATURES.iter().find(|(sig, _)| content.starts_with(sig)) {
+        return Some(mime)
+    }
+
+    /* `BM` and reserved zeros */
+    if content.len() >= 14 && content.starts_with(b"BM") && content[6..10] == [0; 4] {
+        return Some("image/bmp")
+    }
+
+    /* RIFF containers */
+    if content.len() >= 12 && content.starts_with(b"RIFF") {
+        match &content[8..12] {
+            b"WEBP" => return Some("image/webp"),
+            b"WAVE" => return Some("audio/wav"),
+            b"AVI " => return Some("video/x-msvideo"),
+            _ => ()
+        }
+    }
+
+    /* ISO base media file format */
+    if content.len() >= 12 && &content[4..8] == b"ftyp" {
+        return Some(match &content[8..12] {
+            b"avif" | b"avis"                   => "image/avif",
+            b"heic" | b"heix" | b"mif1" | b"msf1" => "image/heic",
+            b"M4A " | b"M4B "                   => "audio/mp4",
+            b"qt  "                             => "video/quicktime",
+            _                                   => "video/mp4",
+        })
+    }
+
+    /* MP3 without ID3 tag ( frame sync ) */
+    if content.len() >= 2 && content[0] == 0xFF && (content[1] & 0xE0) == 0xE0 {
+        return Some("audio/mpeg")
+    }
+
+    let text = match std::str::from_utf8(content) {
+        Ok(text) => text,
+        /* the content may be cut in the middle of a multi-byte character */
+        Err(e) if e.error_len().is_none() => std::str::from_utf8(&content[..e.valid_up_to()]).unwrap(),
+        Err(_) => return None,
+    };
+    if text.chars().any(|c| c.is_control() && !c.is_ascii_whitespace()) {
+        return None
+    }
+
+    let head = text.trim_start_matches('\u{FEFF}').trim_start();
+    let starts_with_ignore_case = |prefix: &str| head.len() >= prefix.len()
+        && head.as_bytes()[..prefix.len()].eq_ignore_ascii_case(prefix.as_bytes());
+
+    Some(if starts_with_ignore_case("<!DOCTYPE html") || starts_with_ignore_case("<html") {
+        "text/html"
+    } else if starts_with_ignore_case("<svg") {
+        "image/svg+xml"
+    } else if starts_with_ignore_case("<?xml") {
+        if head.contains("<svg") {"image/svg+xml"} else {"text/xml"}
+    } else {
+        "text/plain"
+    })
+}
+
+/// whether a response of `mime` is worth compressing
+pub fn is_compressible(mime: &str) -> bool {
+    let mime = mime.split(';').next().unwrap().trim();
+    let Some((type_, subtype)) = mime.split_once('/') else {return false};
+
+    type_.eq_ignore_ascii_case("text")
+    || subtype.ends_with("+json")
+    || subtype.ends_with("+xml")
+    || matches!(subtype,
+        | "json"
+        | "xml"
+        | "javascript"
+        | "ecmascript"
+        | "wasm"
+        | "x-javascript"
+        | "graphql"
+    )
+}
+
+#[cfg(test)]
+#[test] fn test_sniff() {
+    assert_eq!(sniff(b"\x89PNG\r\n\x1A\n\0\0\0\rIHDR"), Some("image/png"));
+    assert_eq!(sniff(b"\xFF\xD8\xFF\xE0\0\x10JFIF"), Some("image/jpeg"));
+    assert_eq!(sniff(b"RIFF\x24\0\0\0WEBPVP8 "), Some("image/webp"));
+    assert_eq!(sniff(b"\0\0\0\x1Cftypavif\0\0\0\0"), Some("image/avif"));
+    assert_eq!(sniff(b"\0\0\0\x18ftypmp42\0\0\0\0"), Some("video/mp4"));
+    assert_eq!(sniff(b"\0asm\x01\0\0\0"), Some("application/wasm"));
+    assert_eq!(sniff(b"wOF2\0\x01\0\0"), Some("font/woff2"));
+    assert_eq!(sniff(b"  <!doctype html><html></html>"), Some("text/html"));
+    assert_eq!(sniff(b"<?xml version=\"1.0\"?><svg></svg>"), Some("image/svg+xml"));
+    assert_eq!(sniff(b"<?xml version=\"1.0\"?><feed></feed>"), Some("text/xml"));
+    assert_eq!(sniff(b"MIT License\n\nCopyright (c) 2024"), Some("text/plain"));
+    assert_eq!(sniff("こんにちは".as_bytes()), Some("text/plain"));
+    assert_eq!(sniff(&"こんにちは".as_bytes()[..7]), Some("text/plain"));
+    assert_eq!(sniff(b"BM\x36\x00\x0C\x00\x00\x00\x00\x00\x36\x00\x00\x00"), Some("image/bmp"));
+    assert_eq!(sniff(b"BMW is a car manufacturer"), Some("text/plain"));
+    assert_eq!(sniff(b"\x01\x02\x03\x04"), None);
+    assert_eq!(sniff(b"\xC0\xC1"), None);
+}
+
+#[cfg(test)]
+#[test] fn test_is_compressible() {
+    assert!(is_compressible("text/html; charset=UTF-8"));
+    assert!(is_compressible("application/json"));
+    assert!(is_compressible("application/problem+json"));
+    assert!(is_compressible("image/svg+xml"));
+    assert!(is_compressible("application/javascript"));
+    assert!(!is_compressible("image/png"));
+    assert!(!is_compressible("application/zip"));
+    assert!(!is_compressible("invalid"));
+}
@@ -0,0 +1,364 @@
+//! Serializer shared by the binary formats mapping serde's data model
+//! into the same shape: structs and maps into maps, sequences and tuples
+//! into arrays, and data-carrying enum variants into `{ variant: data }`.
+
+use serde::ser::Error as _;
+use std::marker::PhantomData;
+
+
+pub(crate) trait Encoding {
+    const NAME: &'static str;
+
+    fn nil(out: &mut Vec<u8>);
+    fn bool(out: &mut Vec<u8>, v: bool);
+    fn uint(out: &mut Vec<u8>, v: u64);
+    /// only called for negative `v`
+    fn negative_int(out: &mut Vec<u8>, v: i64);
+    fn f32(out: &mut Vec<u8>, v: f32);
+    fn f64(out: &mut Vec<u8>, v: f64);
+
+    /// header of a UTF-8 string of `len` bytes
+    fn str_header(out: &mut Vec<u8>, len: usize) -> Option<()>;
+    /// header of a binary of `len` bytes
+    fn bytes_header(out: &mut Vec<u8>, len: usize) -> Option<()>;
+    /// header of an array of `len` elements
+    fn array_header(out: &mut Vec<u8>, len: usize) -> Option<()>;
+    /// header of a map of `len` entries
+    fn map_header(out: &mut Vec<u8>, len: usize) -> Option<()>;
+}
+
+pub(crate) fn to_vec<E: Encoding>(value: &impl serde::Serialize) -> Result<Vec<u8>, Error> {
+    let mut s = BinarySerializer::<E> { output: Vec::new(), __e__: PhantomData };
+    value.serialize(&mut s)?;
+    Ok(s.output)
+}
+
+
+#[derive(Debug)]
+pub struct Error(String);
+const _: () = {
+    impl std::fmt::Display for Error {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str(&self.0)
+        }
+    }
+    impl std::error::Error for Error {}
+
+    impl serde::ser::Error for Error {
+        fn custom<T>(msg:T) -> Self where T:std::fmt::Display {
+            Self(msg.to_string())
+        }
+    }
+};
+
+#[cold] #[inline(never)]
+fn too_long<E: Encoding>(len: usize) -> Error {
+    Error(format!("{} can't encode {len} items or bytes", E::NAME))
+}
+
+
+struct BinarySerializer<E: Encoding> {
+    output: Vec<u8>,
+    __e__:  PhantomData<fn() -> E>,
+}
+
+impl<E: Encoding> BinarySerializer<E> {
+    fn str(&mut self, v: &str) -> Result<(), Error> {
+        E::str_header(&mut self.output, v.len()).ok_or_else(|| too_long::<E>(v.len()))?;
+        self.output.extend_from_slice(v.as_bytes());
+        Ok(())
+    }
+
+    /// Start `{ variant: ... }` of a data-carrying enum variant
+    fn variant(&mut self, variant: &'static str) -> Result<(), Error> {
+        E::map_header(&mut self.output, 1).ok_or_else(|| too_long::<E>(1))?;
+        self.str(variant)
+    }
+
+    fn compound(&mut self, kind: Kind) -> Compound<'_, E> {
+        Compound { start: self.output.len(), len: 0, kind, ser: self }
+    }
+}
+
+enum Kind { Array, Map }
+
+/// Array or map whose header is inserted at `start` on `end`,
+/// as the length may be unknown until then
+struct Compound<'s, E: Encoding> {
+    ser:   &'s mut BinarySerializer<E>,
+    start: usize,
+    len:   usize,
+    kind:  Kind,
+}
+
+impl<E: Encoding> Compound<'_, E> {
+    fn element(&mut self, value: &(impl serde::Serialize + ?Sized)) -> Result<(), Error> {
+        self.len += 1;
+        value.serialize(&mut *self.ser)
+    }
+
+    fn entry(&mut self, key: &'static str, value: &(impl serde::Serialize + ?Sized)) -> Result<(), Error> {
+        self.len += 1;
+        self.ser.str(key)?;
+        value.serialize(&mut *self.ser)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        let mut header = Vec::new();
+        match self.kind {
+            Kind::Array => E::array_header(&mut header, self.len),
+            Kind::Map   => E::map_header(&mut header, self.len),
+        }.ok_or_else(|| too_long::<E>(self.len))?;
+        self.ser.output.splice(self.start..self.start, header);
+        Ok(())
+    }
+}
+
+const _: () = {
+    impl<E: Encoding> serde::ser::SerializeSeq for Compound<'_, E> {
+        type Ok    = ();
+        type Error = Error;
+
+        fn serialize_element<T: ?Sized + serde::Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+            self.element(value)
+        }
+        fn end(self) -> Result<Self::Ok, Self::Error> {
+            self.end()
+        }
+    }
+    impl<E: Encoding> serde::ser::SerializeTuple for Compound<'_, E> {
+        type Ok    = ();
+        type Error = Error;
+
+        fn serialize_element<T: ?Sized + serde::Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+            self.element(value)
+        }
+        fn end(self) -> Result<Self::Ok, Self::Error> {
+            self.end()
+        }
+    }
+    impl<E: Encoding> serde::ser::SerializeTupleStruct for Compound<'_, E> {
+        type Ok    = ();
+        type Error = Error;
+
+        fn serialize_field<T: ?Sized + serde::Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+            self.element(value)
+        }
+        fn end(self) -> Result<Self::Ok, Self::Error> {
+            self.end()
+        }
+    }
+    impl<E: Encoding> serde::ser::SerializeTupleVariant for Compound<'_, E> {
+        type Ok    = ();
+        type Error = Error;
+
+        fn serialize_field<T: ?Sized + serde::Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+            self.element(value)
+        }
+        fn end(self) -> Result<Self::Ok, Self::Error> {
+            self.end()
+        }
+    }
+    impl<E: Encoding> serde::ser::SerializeMap for Compound<'_, E> {
+        type Ok    = ();
+        type Error = Error;
+
+        fn serialize_key<T: ?Sized + serde::Serialize>(&mut self, key: &T) -> Result<(), Self::Error> {
+            self.element(key)
+        }
+        fn serialize_value<T: ?Sized + serde::Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+            value.serialize(&mut *self.ser)
+        }
+        fn end(self) -> Result<Self::Ok, Self::Error> {
+            self.end()
+        }
+    }
+    impl<E: Encoding> serde::ser::SerializeStruct for Compound<'_, E> {
+        type Ok    = ();
+        type Error = Error;
+
+        fn serialize_field<T: ?Sized + serde::Serialize>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error> {
+            self.entry(key, value)
+        }
+        fn end(self) -> Result<Self::Ok, Self::Error> {
+            self.end()
+        }
+    }
+    impl<E: Encoding> serde::ser::SerializeStructVariant for Compound<'_, E> {
+        type Ok    = ();
+        type Error = Error;
+
+        fn serialize_field<T: ?Sized + serde::Serialize>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error> {
+            self.entry(key, value)
+        }
+        fn end(self) -> Result<Self::Ok, Self::Error> {
+            self.end()
+        }
+    }
+};
+
+impl<'s, E: Encoding> serde::Serializer for &'s mut BinarySerializer<E> {
+    type Ok    = ();
+    type Error = Error;
+
+    type SerializeSeq           = Compound<'s, E>;
+    type SerializeTuple         = Compound<'s, E>;
+    type SerializeTupleStruct   = Compound<'s, E>;
+    type SerializeTupleVariant  = Compound<'s, E>;
+    type SerializeMap           = Compound<'s, E>;
+    type SerializeStruct        = Compound<'s, E>;
+    type SerializeStructVariant = Compound<'s, E>;
+
+    fn serialize_bool(self, v: bool) -> Result<Self::Ok, Self::Error> {
+        E::bool(&mut self.output, v);
+        Ok(())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Self::Ok, Self::Error> {
+        self.serialize_i64(v as i64)
+    }
+    fn serialize_i16(self, v: i16) -> Result<Self::Ok, Self::Error> {
+        self.serialize_i64(v as i64)
+    }
+    fn serialize_i32(self, v: i32) -> Result<Self::Ok, Self::Error> {
+        self.serialize_i64(v as i64)
+    }
+    fn serialize_i64(self, v: i64) -> Result<Self::Ok, Self::Error> {
+        if v < 0 {
+            E::negative_int(&mut self.output, v);
+        } else {
+            E::uint(&mut self.output, v as u64);
+        }
+        Ok(())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Self::Ok, Self::Error> {
+        self.serialize_u64(v as u64)
+    }
+    fn serialize_u16(self, v: u16) -> Result<Self::Ok, Self::Error> {
+        self.serialize_u64(v as u64)
+    }
+    fn serialize_u32(self, v: u32) -> Result<Self::Ok, Self::Error> {
+        self.serialize_u64(v as u64)
+    }
+    fn serialize_u64(self, v: u64) -> Result<Self::Ok, Self::Error> {
+        E::uint(&mut self.output, v);
+        Ok(())
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Self::Ok, Self::Error> {
+        E::f32(&mut self.output, v);
+        Ok(())
+    }
+    fn serialize_f64(self, v: f64) -> Result<Self::Ok, Self::Error> {
+        E::f64(&mut self.output, v);
+        Ok(())
+    }
+
+    fn serialize_char(self, v: char) -> Result<Self::Ok, Self::Error> {
+        self.str(v.encode_utf8(&mut [0; 4]))
+    }
+    fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
+        self.str(v)
+    }
+    fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
+        E::bytes_header(&mut self.output, v.len()).ok_or_else(|| too_long::<E>(v.len()))?;
+        self.output.extend_from_slice(v);
+        Ok(())
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        E::nil(&mut self.output);
+        Ok(())
+    }
+    fn serialize_some<T: ?Sized + serde::Serialize>(self, value: &T) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        self.serialize_none()
+    }
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+        self.serialize_none()
+    }
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.str(variant)
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + serde::Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+    fn serialize_newtype_variant<T: ?Sized + serde::Serialize>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.variant(variant)?;
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Ok(self.compound(Kind::Array))
+    }
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        Ok(self.compound(Kind::Array))
+    }
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        Ok(self.compound(Kind::Array))
+    }
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        self.variant(variant)?;
+        Ok(self.compound(Kind::Array))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Ok(self.compound(Kind::Map))
+    }
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Ok(self.compound(Kind::Map))
+    }
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        self.variant(variant)?;
+        Ok(self.compound(Kind::Map))
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<Self::Ok, Self::Error> {
+        i64::try_from(v).map_err(|_| Error::custom(format!("{} can't encode {v} as an integer", E::NAME)))
+            .and_then(|v| self.serialize_i64(v))
+    }
+    fn serialize_u128(self, v: u128) -> Result<Self::Ok, Self::Error> {
+        u64::try_from(v).map_err(|_| Error::custom(format!("{} can't encode {v} as an integer", E::NAME)))
+            .and_then(|v| self.serialize_u64(v))
+    }
+}
@@ -0,0 +1,73 @@
+use crate::serde_binary::{self, Encoding};
+
+#[cfg(test)]
+mod _test;
+
+pub use serde_binary::Error;
+
+
+/// Serialize `value` into CBOR ( RFC 8949 ), with structs as maps keyed
+/// by the field names and definite lengths.
+#[inline]
+pub fn to_vec(value: &impl serde::Serialize) -> Result<Vec<u8>, Error> {
+    serde_binary::to_vec::<Cbor>(value)
+}
+
+
+struct Cbor;
+
+impl Cbor {
+    /// initial byte of `major` type and the argument `n`
+    fn head(out: &mut Vec<u8>, major: u8, n: u64) {
+        let major = major << 5;
+        match n {
+            0..=23 => out.push(major | n as u8),
+            _ if n <= u8::MAX  as u64 => out.extend([major | 24, n as u8]),
+            _ if n <= u16::MAX as u64 => {out.push(major | 25); out.extend((n as u16).to_be_bytes())}
+            _ if n <= u32::MAX as u64 => {out.push(major | 26); out.extend((n as u32).to_be_bytes())}
+            _ => {out.push(major | 27); out.extend(n.to_be_bytes())}
+        }
+    }
+}
+
+impl Encoding for Cbor {
+    const NAME: &'static str = "CBOR";
+
+    fn nil(out: &mut Vec<u8>) {
+        out.push(0xf6)
+    }
+    fn bool(out: &mut Vec<u8>, v: bool) {
+        out.push(if v {0xf5} else {0xf4})
+    }
+    fn uint(out: &mut Vec<u8>, v: u64) {
+        Self::head(out, 0, v)
+    }
+    fn negative_int(out: &mut Vec<u8>, v: i64) {
+        Self::head(out, 1, !v as u64)
+    }
+    fn f32(out: &mut Vec<u8>, v: f32) {
+        out.push(0xfa);
+        out.extend(v.to_be_bytes())
+    }
+    fn f64(out: &mut Vec<u8>, v: f64) {
+        out.push(0xfb);
+        out.extend(v.to_be_bytes())
+    }
+
+    fn str_header(out: &mut Vec<u8>, len: usize) -> Option<()> {
+        Self::head(out, 3, len as u64);
+        Some(())
+    }
+    fn bytes_header(out: &mut Vec<u8>, len: usize) -> Option<()> {
+        Self::head(out, 2, len as u64);
+        Some(())
+    }
+    fn array_header(out: &mut Vec<u8>, len: usize) -> Option<()> {
+        Self::head(out, 4, len as u64);
+        Some(())
+    }
+    fn map_header(out: &mut Vec<u8>, len: usize) -> Option<()> {
+        Self::head(out, 5, len as u64);
+        Some(())
+    }
+}
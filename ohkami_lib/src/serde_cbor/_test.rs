@@ -0,0 +1,64 @@
+use crate::serde_cbor;
+use serde::Serialize;
+
+
+/* https://www.rfc-editor.org/rfc/rfc8949.html#appendix-A */
+
+#[test] fn serialize_scalars() {
+    for (value, expected) in [
+        (0,             &[0x00][..]),
+        (23,            &[0x17]),
+        (24,            &[0x18, 0x18]),
+        (100,           &[0x18, 0x64]),
+        (1000,          &[0x19, 0x03, 0xe8]),
+        (1000000,       &[0x1a, 0x00, 0x0f, 0x42, 0x40]),
+        (1000000000000, &[0x1b, 0x00, 0x00, 0x00, 0xe8, 0xd4, 0xa5, 0x10, 0x00]),
+        (-1,            &[0x20]),
+        (-10,           &[0x29]),
+        (-100,          &[0x38, 0x63]),
+        (-1000,         &[0x39, 0x03, 0xe7]),
+        (i64::MIN,      &[0x3b, 0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]),
+    ] {
+        assert_eq!(serde_cbor::to_vec(&value).unwrap(), expected, "{value}");
+    }
+    assert_eq!(serde_cbor::to_vec(&u64::MAX).unwrap(), [0x1b, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]);
+
+    assert_eq!(serde_cbor::to_vec(&false).unwrap(), [0xf4]);
+    assert_eq!(serde_cbor::to_vec(&true).unwrap(), [0xf5]);
+    assert_eq!(serde_cbor::to_vec(&None::<u8>).unwrap(), [0xf6]);
+    assert_eq!(serde_cbor::to_vec(&1.5).unwrap(), [0xfb, 0x3f, 0xf8, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(serde_cbor::to_vec(&100000.0f32).unwrap(), [0xfa, 0x47, 0xc3, 0x50, 0x00]);
+
+    assert_eq!(serde_cbor::to_vec(&"").unwrap(), [0x60]);
+    assert_eq!(serde_cbor::to_vec(&"IETF").unwrap(), [0x64, 0x49, 0x45, 0x54, 0x46]);
+    assert_eq!(serde_cbor::to_vec(&'\u{00fc}').unwrap(), [0x62, 0xc3, 0xbc]);
+}
+
+#[test] fn serialize_compounds() {
+    assert_eq!(serde_cbor::to_vec(&Vec::<u8>::new()).unwrap(), [0x80]);
+    assert_eq!(serde_cbor::to_vec(&[1, 2, 3]).unwrap(), [0x83, 0x01, 0x02, 0x03]);
+    assert_eq!(serde_cbor::to_vec(&(1, (2, 3), [4, 5])).unwrap(), [0x83, 0x01, 0x82, 0x02, 0x03, 0x82, 0x04, 0x05]);
+    assert_eq!(serde_cbor::to_vec(&(1..=25).collect::<Vec<u8>>()).unwrap()[..3], [0x98, 0x19, 0x01]);
+
+    #[derive(Serialize)]
+    struct AB {
+        a: u8,
+        b: [u8; 2],
+    }
+    assert_eq!(serde_cbor::to_vec(&AB { a: 1, b: [2, 3] }).unwrap(), [0xa2, 0x61, 0x61, 0x01, 0x61, 0x62, 0x82, 0x02, 0x03]);
+
+    let map = std::collections::BTreeMap::from([(1, 2), (3, 4)]);
+    assert_eq!(serde_cbor::to_vec(&map).unwrap(), [0xa2, 0x01, 0x02, 0x03, 0x04]);
+}
+
+#[test] fn serialize_enums() {
+    #[derive(Serialize)]
+    enum E {
+        A,
+        B(u8),
+        C { x: u8 },
+    }
+    assert_eq!(serde_cbor::to_vec(&E::A).unwrap(), [0x61, b'A']);
+    assert_eq!(serde_cbor::to_vec(&E::B(1)).unwrap(), [0xa1, 0x61, b'B', 0x01]);
+    assert_eq!(serde_cbor::to_vec(&E::C { x: 1 }).unwrap(), [0xa1, 0x61, b'C', 0xa1, 0x61, b'x', 0x01]);
+}
@@ -1,8 +1,37 @@
+mod ser;
 mod de;
 
 #[cfg(test)]
 mod _test;
 
+/// Serialize a map or map-like struct into pairs of Cookie name and value,
+/// omitting `None` fields. The values are **not** percent-encoded.
+pub fn to_pairs(value: &impl serde::Serialize) -> Result<Vec<(String, String)>, Error> {
+    let mut s = ser::CookieSerializer::new();
+    value.serialize(&mut s)?;
+    Ok(s.pairs)
+}
+
+/// Serialize a map or map-like struct into `Cookie` header value
+/// like `name=ohkami; age=4`, percent-encoding the values.
+pub fn to_string(value: &impl serde::Serialize) -> Result<String, Error> {
+    let mut cookies = String::new();
+    for (i, (name, value)) in to_pairs(value)?.into_iter().enumerate() {
+        if i > 0 {cookies.push_str("; ")}
+        cookies.push_str(&name);
+        cookies.push('=');
+        cookies.push_str(&crate::percent_encode(&value));
+    }
+    Ok(cookies)
+}
+
+/// Whether `name` is a valid Cookie name, the same check as
+/// `to_pairs` and `from_str` do.
+#[inline]
+pub fn is_valid_name(name: &str) -> bool {
+    de::valid::name(name.as_bytes()).is_ok()
+}
+
 #[inline(always)]
 pub fn from_str<'de, D: serde::Deserialize<'de>>(input: &'de str) -> Result<D, Error> {
     let mut d = de::CookieDeserializer::new(input);
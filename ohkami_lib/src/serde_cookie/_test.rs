@@ -98,3 +98,34 @@ fn nonascii_encoded_cookies() {
         }
     );
 }
+
+#[test]
+fn serialize_cookies() {
+    assert_eq!(
+        serde_cookie::to_pairs(&UserInfo {
+            name: Cow::Borrowed("狼"),
+            age: Some(Age(4)),
+            gender: None,
+        }).unwrap(),
+        [
+            (String::from("name"), String::from("狼")),
+            (String::from("age"),  String::from("4")),
+        ]
+    );
+
+    let user = UserInfo {
+        name: Cow::Borrowed("狼"),
+        age: None,
+        gender: Some(Gender::Other),
+    };
+    let cookies = serde_cookie::to_string(&user).unwrap();
+    assert_eq!(cookies, "name=%E7%8B%BC; gender=other");
+    assert_eq!(serde_cookie::from_str::<UserInfo>(&cookies).unwrap(), user);
+
+    let map = std::collections::BTreeMap::from([("a", 1), ("b", 2)]);
+    assert_eq!(serde_cookie::to_string(&map).unwrap(), "a=1; b=2");
+
+    assert!(serde_cookie::to_pairs(&std::collections::BTreeMap::from([("a b", 1)])).is_err());
+    assert!(serde_cookie::to_pairs(&42).is_err());
+    assert!(serde_cookie::to_pairs(&vec![1, 2]).is_err());
+}
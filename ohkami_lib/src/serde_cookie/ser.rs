@@ -0,0 +1,245 @@
+use serde::ser::Impossible;
+
+
+/// Serializes a map or map-like struct into pairs of Cookie name and value,
+/// omitting `None` fields
+pub(crate) struct CookieSerializer {
+    pub(crate) pairs: Vec<(String, String)>,
+    key: Option<String>,
+}
+impl CookieSerializer {
+    pub(crate) const fn new() -> Self {
+        Self { pairs: Vec::new(), key: None }
+    }
+
+    fn push(&mut self, name: &str, value: Option<String>) -> Result<(), super::Error> {
+        super::de::valid::name(name.as_bytes())?;
+        if let Some(value) = value {
+            self.pairs.push((name.into(), value));
+        }
+        Ok(())
+    }
+}
+
+const _: () = {
+    impl serde::ser::SerializeMap for &mut CookieSerializer {
+        type Ok    = ();
+        type Error = super::Error;
+
+        fn serialize_key<T: ?Sized>(&mut self, key: &T) -> Result<(), Self::Error>
+        where T: serde::Serialize {
+            self.key = Some(key.serialize(ValueSerializer)?
+                .ok_or_else(|| serde::ser::Error::custom("Cookie name must not be `None`"))?);
+            Ok(())
+        }
+        fn serialize_value<T: ?Sized>(&mut self, value: &T) -> Result<(), Self::Error>
+        where T: serde::Serialize {
+            let key = self.key.take()
+                .ok_or_else(|| serde::ser::Error::custom("`serialize_value` called before `serialize_key`"))?;
+            let value = value.serialize(ValueSerializer)?;
+            self.push(&key, value)
+        }
+        fn end(self) -> Result<Self::Ok, Self::Error> {
+            Ok(())
+        }
+    }
+
+    impl serde::ser::SerializeStruct for &mut CookieSerializer {
+        type Ok    = ();
+        type Error = super::Error;
+
+        fn serialize_field<T: ?Sized>(
+            &mut self,
+            key: &'static str,
+            value: &T,
+        ) -> Result<(), Self::Error>
+        where T: serde::Serialize {
+            let value = value.serialize(ValueSerializer)?;
+            self.push(key, value)
+        }
+        fn end(self) -> Result<Self::Ok, Self::Error> {
+            Ok(())
+        }
+    }
+};
+
+macro_rules! unsupported {
+    ($($method:ident ($($arg:ident: $ty:ty),*) -> $ret:ty: $what:literal;)*) => {$(
+        fn $method(self, $(_: $ty),*) -> Result<$ret, Self::Error> {
+            Err(serde::ser::Error::custom(concat!("ohkami's builtin Cookie serializer doesn't support ", $what, " !")))
+        }
+    )*};
+}
+
+impl serde::Serializer for &mut CookieSerializer {
+    type Ok    = ();
+    type Error = super::Error;
+
+    type SerializeMap           = Self;
+    type SerializeStruct        = Self;
+    type SerializeSeq           = Impossible<(), super::Error>;
+    type SerializeTuple         = Impossible<(), super::Error>;
+    type SerializeTupleStruct   = Impossible<(), super::Error>;
+    type SerializeTupleVariant  = Impossible<(), super::Error>;
+    type SerializeStructVariant = Impossible<(), super::Error>;
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Ok(self)
+    }
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct, Self::Error> {
+        Ok(self)
+    }
+    fn serialize_newtype_struct<T: ?Sized>(self, _name: &'static str, value: &T) -> Result<Self::Ok, Self::Error>
+    where T: serde::Serialize {
+        value.serialize(self)
+    }
+    fn serialize_some<T: ?Sized>(self, value: &T) -> Result<Self::Ok, Self::Error>
+    where T: serde::Serialize {
+        value.serialize(self)
+    }
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        Ok(())
+    }
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        Ok(())
+    }
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+        Ok(())
+    }
+    fn serialize_newtype_variant<T: ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where T: serde::Serialize {
+        Err(serde::ser::Error::custom("ohkami's builtin Cookie serializer doesn't support enum at top level !"))
+    }
+
+    unsupported! {
+        serialize_bool(v: bool) -> (): "primitive at top level";
+        serialize_i8(v: i8) -> (): "primitive at top level";
+        serialize_i16(v: i16) -> (): "primitive at top level";
+        serialize_i32(v: i32) -> (): "primitive at top level";
+        serialize_i64(v: i64) -> (): "primitive at top level";
+        serialize_u8(v: u8) -> (): "primitive at top level";
+        serialize_u16(v: u16) -> (): "primitive at top level";
+        serialize_u32(v: u32) -> (): "primitive at top level";
+        serialize_u64(v: u64) -> (): "primitive at top level";
+        serialize_f32(v: f32) -> (): "primitive at top level";
+        serialize_f64(v: f64) -> (): "primitive at top level";
+        serialize_char(v: char) -> (): "primitive at top level";
+        serialize_str(v: &str) -> (): "primitive at top level";
+        serialize_bytes(v: &[u8]) -> (): "raw byte data";
+        serialize_unit_variant(name: &'static str, index: u32, variant: &'static str) -> (): "enum at top level";
+        serialize_seq(len: Option<usize>) -> Self::SerializeSeq: "sequence";
+        serialize_tuple(len: usize) -> Self::SerializeTuple: "tuple";
+        serialize_tuple_struct(name: &'static str, len: usize) -> Self::SerializeTupleStruct: "tuple struct";
+        serialize_tuple_variant(name: &'static str, index: u32, variant: &'static str, len: usize) -> Self::SerializeTupleVariant: "enum with tuple variants";
+        serialize_struct_variant(name: &'static str, index: u32, variant: &'static str, len: usize) -> Self::SerializeStructVariant: "enum with struct variants";
+    }
+}
+
+/// Serializes a Cookie value, or `None` to omit the Cookie
+struct ValueSerializer;
+
+impl serde::Serializer for ValueSerializer {
+    type Ok    = Option<String>;
+    type Error = super::Error;
+
+    type SerializeMap           = Impossible<Option<String>, super::Error>;
+    type SerializeStruct        = Impossible<Option<String>, super::Error>;
+    type SerializeSeq           = Impossible<Option<String>, super::Error>;
+    type SerializeTuple         = Impossible<Option<String>, super::Error>;
+    type SerializeTupleStruct   = Impossible<Option<String>, super::Error>;
+    type SerializeTupleVariant  = Impossible<Option<String>, super::Error>;
+    type SerializeStructVariant = Impossible<Option<String>, super::Error>;
+
+    fn serialize_bool(self, v: bool) -> Result<Self::Ok, Self::Error> {
+        Ok(Some(String::from(if v {"true"} else {"false"})))
+    }
+    fn serialize_i8(self, v: i8) -> Result<Self::Ok, Self::Error> {
+        self.serialize_i64(v.into())
+    }
+    fn serialize_i16(self, v: i16) -> Result<Self::Ok, Self::Error> {
+        self.serialize_i64(v.into())
+    }
+    fn serialize_i32(self, v: i32) -> Result<Self::Ok, Self::Error> {
+        self.serialize_i64(v.into())
+    }
+    fn serialize_i64(self, v: i64) -> Result<Self::Ok, Self::Error> {
+        Ok(Some(v.to_string()))
+    }
+    fn serialize_u8(self, v: u8) -> Result<Self::Ok, Self::Error> {
+        self.serialize_u64(v.into())
+    }
+    fn serialize_u16(self, v: u16) -> Result<Self::Ok, Self::Error> {
+        self.serialize_u64(v.into())
+    }
+    fn serialize_u32(self, v: u32) -> Result<Self::Ok, Self::Error> {
+        self.serialize_u64(v.into())
+    }
+    fn serialize_u64(self, v: u64) -> Result<Self::Ok, Self::Error> {
+        Ok(Some(v.to_string()))
+    }
+    fn serialize_f32(self, v: f32) -> Result<Self::Ok, Self::Error> {
+        self.serialize_f64(v.into())
+    }
+    fn serialize_f64(self, v: f64) -> Result<Self::Ok, Self::Error> {
+        Ok(Some(v.to_string()))
+    }
+    fn serialize_char(self, v: char) -> Result<Self::Ok, Self::Error> {
+        Ok(Some(v.to_string()))
+    }
+    fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
+        Ok(Some(v.to_string()))
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        Ok(None)
+    }
+    fn serialize_some<T: ?Sized>(self, value: &T) -> Result<Self::Ok, Self::Error>
+    where T: serde::Serialize {
+        value.serialize(self)
+    }
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        Ok(Some(String::new()))
+    }
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+        self.serialize_unit()
+    }
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.serialize_str(variant)
+    }
+    fn serialize_newtype_struct<T: ?Sized>(self, _name: &'static str, value: &T) -> Result<Self::Ok, Self::Error>
+    where T: serde::Serialize {
+        value.serialize(self)
+    }
+    fn serialize_newtype_variant<T: ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where T: serde::Serialize {
+        value.serialize(self)
+    }
+
+    unsupported! {
+        serialize_bytes(v: &[u8]) -> Self::Ok: "raw byte data";
+        serialize_seq(len: Option<usize>) -> Self::SerializeSeq: "sequence as a value";
+        serialize_tuple(len: usize) -> Self::SerializeTuple: "tuple as a value";
+        serialize_tuple_struct(name: &'static str, len: usize) -> Self::SerializeTupleStruct: "tuple struct as a value";
+        serialize_tuple_variant(name: &'static str, index: u32, variant: &'static str, len: usize) -> Self::SerializeTupleVariant: "enum with tuple variants";
+        serialize_map(len: Option<usize>) -> Self::SerializeMap: "nested maps or map-like structs";
+        serialize_struct(name: &'static str, len: usize) -> Self::SerializeStruct: "nested maps or map-like structs";
+        serialize_struct_variant(name: &'static str, index: u32, variant: &'static str, len: usize) -> Self::SerializeStructVariant: "enum with struct variants";
+    }
+}
@@ -0,0 +1,35 @@
+mod ser;
+
+#[cfg(test)]
+mod _test;
+
+
+/// Serialize `value` into CSV ( RFC 4180 ) :
+/// 
+/// - a sequence ( or array ) is serialized into records, and other values into one record
+/// - a struct or map record makes the header line from its keys
+/// - every field must be a scalar, serialized the same as `serde_utf8`
+#[inline]
+pub fn to_string(value: &impl serde::Serialize) -> Result<String, Error> {
+    let mut s = ser::CSVSerializer::new();
+    value.serialize(&mut s)?;
+    s.output()
+}
+
+
+#[derive(Debug)]
+pub struct Error(String);
+const _: () = {
+    impl std::fmt::Display for Error {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str(&self.0)
+        }
+    }
+    impl std::error::Error for Error {}
+
+    impl serde::ser::Error for Error {
+        fn custom<T>(msg:T) -> Self where T:std::fmt::Display {
+            Self(msg.to_string())
+        }
+    }
+};
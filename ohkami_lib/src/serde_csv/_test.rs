@@ -0,0 +1,94 @@
+use crate::serde_csv;
+use serde::Serialize;
+
+
+#[derive(Serialize)]
+struct User {
+    name: &'static str,
+    age:  Option<u8>,
+}
+
+#[test] fn serialize_records() {
+    assert_eq!(
+        serde_csv::to_string(&[
+            User { name: "ohkami", age: Some(4) },
+            User { name: "serde",  age: None },
+        ]).unwrap(),
+        "name,age\r\nohkami,4\r\nserde,\r\n"
+    );
+
+    assert_eq!(
+        serde_csv::to_string(&Vec::<User>::new()).unwrap(),
+        ""
+    );
+
+    assert_eq!(
+        serde_csv::to_string(&User { name: "ohkami", age: Some(4) }).unwrap(),
+        "name,age\r\nohkami,4\r\n"
+    );
+
+    let mut scores = std::collections::BTreeMap::new();
+    scores.insert("alice", 90);
+    scores.insert("bob",   85);
+    assert_eq!(
+        serde_csv::to_string(&vec![scores.clone(), scores]).unwrap(),
+        "alice,bob\r\n90,85\r\n90,85\r\n"
+    );
+}
+
+#[test] fn serialize_records_without_header() {
+    assert_eq!(
+        serde_csv::to_string(&vec![("a", 1), ("b", 2)]).unwrap(),
+        "a,1\r\nb,2\r\n"
+    );
+
+    #[derive(Serialize)]
+    struct Point(i32, i32);
+    assert_eq!(
+        serde_csv::to_string(&Point(1, -2)).unwrap(),
+        "1,-2\r\n"
+    );
+
+    assert_eq!(
+        serde_csv::to_string(&vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap(),
+        "1,2,3\r\n4,5,6\r\n"
+    );
+
+    assert_eq!(
+        serde_csv::to_string(&["x", "", "z"]).unwrap(),
+        "x\r\n\"\"\r\nz\r\n"
+    );
+}
+
+#[test] fn serialize_escaped() {
+    assert_eq!(
+        serde_csv::to_string(&[
+            User { name: "a,b", age: None },
+            User { name: "say \"hi\"\r\n", age: None },
+        ]).unwrap(),
+        "name,age\r\n\"a,b\",\r\n\"say \"\"hi\"\"\r\n\",\r\n"
+    );
+}
+
+#[test] fn serialize_unsupported() {
+    #[derive(Serialize)]
+    struct Team {
+        members: Vec<&'static str>,
+    }
+    assert!(serde_csv::to_string(&[Team { members: vec!["a"] }]).is_err());
+
+    #[derive(Serialize)]
+    struct Other {
+        name: &'static str,
+    }
+    #[derive(Serialize)]
+    #[serde(untagged)]
+    enum Record {
+        User(User),
+        Other(Other),
+    }
+    assert!(serde_csv::to_string(&[
+        Record::User(User { name: "ohkami", age: None }),
+        Record::Other(Other { name: "serde" }),
+    ]).is_err());
+}
@@ -0,0 +1,283 @@
+use serde::ser::Impossible;
+use crate::serde_utf8;
+
+
+pub(crate) struct CSVSerializer {
+    output: String,
+
+    /// Keys of the first record, written as the header line if not empty
+    header: Option<Vec<String>>,
+
+    /// Whether the top-level sequence of records is being serialized
+    records: bool,
+    /// Whether a sequence or tuple record is being serialized
+    in_record: bool,
+
+    keys:   Vec<String>,
+    values: Vec<String>,
+}
+impl CSVSerializer {
+    #[inline]
+    pub(crate) const fn new() -> Self {
+        Self {
+            output:    String::new(),
+            header:    None,
+            records:   false,
+            in_record: false,
+            keys:      Vec::new(),
+            values:    Vec::new(),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn output(mut self) -> Result<String, super::Error> {
+        if !self.records {
+            self.flush_record()?;
+        }
+        Ok(self.output)
+    }
+
+    fn field(value: &(impl serde::Serialize + ?Sized)) -> Result<String, super::Error> {
+        serde_utf8::to_string(&value).map_err(|e| super::Error(format!("Unsupported CSV field: {e}")))
+    }
+
+    fn flush_record(&mut self) -> Result<(), super::Error> {
+        let keys   = std::mem::take(&mut self.keys);
+        let values = std::mem::take(&mut self.values);
+
+        match &self.header {
+            None => {
+                if !keys.is_empty() {
+                    write_line(&mut self.output, &keys);
+                }
+                self.header = Some(keys);
+            }
+            Some(header) => if *header != keys {
+                return Err(super::Error(format!(
+                    "All CSV records must have the same keys: expected [{}], found [{}]",
+                    header.join(", "), keys.join(", ")
+                )))
+            }
+        }
+        write_line(&mut self.output, &values);
+
+        Ok(())
+    }
+}
+
+fn write_line(output: &mut String, fields: &[String]) {
+    if let [field] = fields {
+        if field.is_empty() {// not to be an empty line
+            return output.push_str("\"\"\r\n")
+        }
+    }
+    for (i, field) in fields.iter().enumerate() {
+        if i > 0 {
+            output.push(',');
+        }
+        if field.contains([',', '"', '\r', '\n']) {
+            output.push('"');
+            output.push_str(&field.replace('"', "\"\""));
+            output.push('"');
+        } else {
+            output.push_str(field);
+        }
+    }
+    output.push_str("\r\n");
+}
+
+const _: () = {
+    impl serde::ser::SerializeSeq for &mut CSVSerializer {
+        type Ok    = ();
+        type Error = super::Error;
+
+        fn serialize_element<T: ?Sized + serde::Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+            if self.in_record {
+                self.values.push(CSVSerializer::field(value)?);
+                Ok(())
+            } else {
+                value.serialize(&mut **self)?;
+                self.flush_record()
+            }
+        }
+        fn end(self) -> Result<Self::Ok, Self::Error> {
+            self.in_record = false;
+            Ok(())
+        }
+    }
+    impl serde::ser::SerializeTuple for &mut CSVSerializer {
+        type Ok    = ();
+        type Error = super::Error;
+
+        fn serialize_element<T: ?Sized + serde::Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+            serde::ser::SerializeSeq::serialize_element(self, value)
+        }
+        fn end(self) -> Result<Self::Ok, Self::Error> {
+            serde::ser::SerializeSeq::end(self)
+        }
+    }
+    impl serde::ser::SerializeTupleStruct for &mut CSVSerializer {
+        type Ok    = ();
+        type Error = super::Error;
+
+        fn serialize_field<T: ?Sized + serde::Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+            self.values.push(CSVSerializer::field(value)?);
+            Ok(())
+        }
+        fn end(self) -> Result<Self::Ok, Self::Error> {
+            self.in_record = false;
+            Ok(())
+        }
+    }
+    impl serde::ser::SerializeMap for &mut CSVSerializer {
+        type Ok    = ();
+        type Error = super::Error;
+
+        fn serialize_key<T: ?Sized + serde::Serialize>(&mut self, key: &T) -> Result<(), Self::Error> {
+            self.keys.push(CSVSerializer::field(key)?);
+            Ok(())
+        }
+        fn serialize_value<T: ?Sized + serde::Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+            self.values.push(CSVSerializer::field(value)?);
+            Ok(())
+        }
+        fn end(self) -> Result<Self::Ok, Self::Error> {
+            Ok(())
+        }
+    }
+    impl serde::ser::SerializeStruct for &mut CSVSerializer {
+        type Ok    = ();
+        type Error = super::Error;
+
+        fn serialize_field<T: ?Sized + serde::Serialize>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error> {
+            self.keys.push(key.into());
+            self.values.push(CSVSerializer::field(value)?);
+            Ok(())
+        }
+        fn end(self) -> Result<Self::Ok, Self::Error> {
+            Ok(())
+        }
+    }
+};
+
+macro_rules! serialize_scalar {
+    ($( $method:ident($t:ty) ),* $(,)?) => {$(
+        fn $method(self, v: $t) -> Result<Self::Ok, Self::Error> {
+            self.values.push(CSVSerializer::field(&v)?);
+            Ok(())
+        }
+    )*};
+}
+
+impl serde::Serializer for &mut CSVSerializer {
+    type Ok    = ();
+    type Error = super::Error;
+
+    type SerializeSeq           = Self;
+    type SerializeTuple         = Self;
+    type SerializeTupleStruct   = Self;
+    type SerializeMap           = Self;
+    type SerializeStruct        = Self;
+    type SerializeTupleVariant  = Impossible<(), super::Error>;
+    type SerializeStructVariant = Impossible<(), super::Error>;
+
+    serialize_scalar! {
+        serialize_bool(bool),
+        serialize_i8(i8), serialize_i16(i16), serialize_i32(i32), serialize_i64(i64),
+        serialize_u8(u8), serialize_u16(u16), serialize_u32(u32), serialize_u64(u64),
+        serialize_f32(f32), serialize_f64(f64),
+        serialize_char(char),
+        serialize_str(&str),
+        serialize_bytes(&[u8]),
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        self.values.push(String::new());
+        Ok(())
+    }
+    fn serialize_some<T: ?Sized + serde::Serialize>(self, value: &T) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        self.serialize_none()
+    }
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+        self.serialize_none()
+    }
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + serde::Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+    fn serialize_newtype_variant<T: ?Sized + serde::Serialize>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+
+    /// The top-level sequence ( or array ) is records, and others are fields of a record
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        if self.records {
+            self.in_record = true;
+        } else {
+            self.records = true;
+        }
+        Ok(self)
+    }
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        self.serialize_seq(Some(len))
+    }
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        self.in_record = true;
+        Ok(self)
+    }
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Ok(self)
+    }
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Err(serde::ser::Error::custom("ohkami's builtin CSV serializer doesn't support enum with tuple variants !"))
+    }
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Err(serde::ser::Error::custom("ohkami's builtin CSV serializer doesn't support enum with struct variants !"))
+    }
+}
@@ -0,0 +1,84 @@
+use crate::serde_binary::{self, Encoding};
+
+#[cfg(test)]
+mod _test;
+
+pub use serde_binary::Error;
+
+
+/// Serialize `value` into MessagePack, with structs as maps keyed by
+/// the field names ( like `rmp_serde::to_vec_named` ).
+#[inline]
+pub fn to_vec(value: &impl serde::Serialize) -> Result<Vec<u8>, Error> {
+    serde_binary::to_vec::<MessagePack>(value)
+}
+
+
+struct MessagePack;
+
+impl MessagePack {
+    fn header(out: &mut Vec<u8>, len: usize, fix: Option<(u8, usize)>, [m8, m16, m32]: [Option<u8>; 3]) -> Option<()> {
+        match (fix, m8) {
+            (Some((fix, max)), _) if len <= max => out.push(fix | len as u8),
+            (_, Some(m8)) if len <= u8::MAX as usize => out.extend([m8, len as u8]),
+            _ => if len <= u16::MAX as usize {
+                out.push(m16?);
+                out.extend((len as u16).to_be_bytes());
+            } else {
+                out.push(m32?);
+                out.extend(u32::try_from(len).ok()?.to_be_bytes());
+            }
+        }
+        Some(())
+    }
+}
+
+impl Encoding for MessagePack {
+    const NAME: &'static str = "MessagePack";
+
+    fn nil(out: &mut Vec<u8>) {
+        out.push(0xc0)
+    }
+    fn bool(out: &mut Vec<u8>, v: bool) {
+        out.push(if v {0xc3} else {0xc2})
+    }
+    fn uint(out: &mut Vec<u8>, v: u64) {
+        match v {
+            0..=0x7f => out.push(v as u8),
+            _ if v <= u8::MAX  as u64 => out.extend([0xcc, v as u8]),
+            _ if v <= u16::MAX as u64 => {out.push(0xcd); out.extend((v as u16).to_be_bytes())}
+            _ if v <= u32::MAX as u64 => {out.push(0xce); out.extend((v as u32).to_be_bytes())}
+            _ => {out.push(0xcf); out.extend(v.to_be_bytes())}
+        }
+    }
+    fn negative_int(out: &mut Vec<u8>, v: i64) {
+        match v {
+            -32..=-1 => out.push(v as u8),
+            _ if v >= i8::MIN  as i64 => out.extend([0xd0, v as u8]),
+            _ if v >= i16::MIN as i64 => {out.push(0xd1); out.extend((v as i16).to_be_bytes())}
+            _ if v >= i32::MIN as i64 => {out.push(0xd2); out.extend((v as i32).to_be_bytes())}
+            _ => {out.push(0xd3); out.extend(v.to_be_bytes())}
+        }
+    }
+    fn f32(out: &mut Vec<u8>, v: f32) {
+        out.push(0xca);
+        out.extend(v.to_be_bytes())
+    }
+    fn f64(out: &mut Vec<u8>, v: f64) {
+        out.push(0xcb);
+        out.extend(v.to_be_bytes())
+    }
+
+    fn str_header(out: &mut Vec<u8>, len: usize) -> Option<()> {
+        Self::header(out, len, Some((0xa0, 31)), [Some(0xd9), Some(0xda), Some(0xdb)])
+    }
+    fn bytes_header(out: &mut Vec<u8>, len: usize) -> Option<()> {
+        Self::header(out, len, None, [Some(0xc4), Some(0xc5), Some(0xc6)])
+    }
+    fn array_header(out: &mut Vec<u8>, len: usize) -> Option<()> {
+        Self::header(out, len, Some((0x90, 15)), [None, Some(0xdc), Some(0xdd)])
+    }
+    fn map_header(out: &mut Vec<u8>, len: usize) -> Option<()> {
+        Self::header(out, len, Some((0x80, 15)), [None, Some(0xde), Some(0xdf)])
+    }
+}
@@ -0,0 +1,74 @@
+use crate::serde_msgpack;
+use serde::Serialize;
+
+
+struct Bytes(&'static [u8]);
+impl Serialize for Bytes {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(self.0)
+    }
+}
+
+#[test] fn serialize_scalars() {
+    for (value, expected) in [
+        (0,             &[0x00][..]),
+        (127,           &[0x7f]),
+        (128,           &[0xcc, 0x80]),
+        (256,           &[0xcd, 0x01, 0x00]),
+        (65536,         &[0xce, 0x00, 0x01, 0x00, 0x00]),
+        (-1,            &[0xff]),
+        (-32,           &[0xe0]),
+        (-33,           &[0xd0, 0xdf]),
+        (-129,          &[0xd1, 0xff, 0x7f]),
+        (i64::MIN,      &[0xd3, 0x80, 0, 0, 0, 0, 0, 0, 0]),
+    ] {
+        assert_eq!(serde_msgpack::to_vec(&value).unwrap(), expected, "{value}");
+    }
+    assert_eq!(serde_msgpack::to_vec(&u64::MAX).unwrap(), [0xcf, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]);
+
+    assert_eq!(serde_msgpack::to_vec(&None::<bool>).unwrap(), [0xc0]);
+    assert_eq!(serde_msgpack::to_vec(&Some(true)).unwrap(), [0xc3]);
+    assert_eq!(serde_msgpack::to_vec(&false).unwrap(), [0xc2]);
+    assert_eq!(serde_msgpack::to_vec(&1.5).unwrap(), [0xcb, 0x3f, 0xf8, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(serde_msgpack::to_vec(&1.5f32).unwrap(), [0xca, 0x3f, 0xc0, 0, 0]);
+
+    assert_eq!(serde_msgpack::to_vec(&"ohkami").unwrap(), b"\xa6ohkami");
+    assert_eq!(serde_msgpack::to_vec(&'狼').unwrap(), [0xa3, 0xe7, 0x8b, 0xbc]);
+    assert_eq!(serde_msgpack::to_vec(&"a".repeat(32)).unwrap()[..2], [0xd9, 32]);
+    assert_eq!(serde_msgpack::to_vec(&"a".repeat(256)).unwrap()[..3], [0xda, 0x01, 0x00]);
+    assert_eq!(serde_msgpack::to_vec(&Bytes(b"ab")).unwrap(), [0xc4, 2, b'a', b'b']);
+}
+
+#[test] fn serialize_compounds() {
+    #[derive(Serialize)]
+    struct User {
+        name: &'static str,
+        age:  Option<u8>,
+    }
+    assert_eq!(
+        serde_msgpack::to_vec(&User { name: "ohkami", age: Some(4) }).unwrap(),
+        b"\x82\xa4name\xa6ohkami\xa3age\x04"
+    );
+
+    assert_eq!(serde_msgpack::to_vec(&[1, 2, 3]).unwrap(), [0x93, 1, 2, 3]);
+    assert_eq!(serde_msgpack::to_vec(&(1, "a")).unwrap(), [0x92, 1, 0xa1, b'a']);
+    assert_eq!(serde_msgpack::to_vec(&vec![0; 16]).unwrap()[..3], [0xdc, 0x00, 16]);
+    assert_eq!(serde_msgpack::to_vec(&Vec::<u8>::new()).unwrap(), [0x90]);
+
+    let map = std::collections::BTreeMap::from([("a", vec![1]), ("b", vec![])]);
+    assert_eq!(serde_msgpack::to_vec(&map).unwrap(), [0x82, 0xa1, b'a', 0x91, 1, 0xa1, b'b', 0x90]);
+}
+
+#[test] fn serialize_enums() {
+    #[derive(Serialize)]
+    enum E {
+        A,
+        B(u8),
+        C(u8, u8),
+        D { x: u8 },
+    }
+    assert_eq!(serde_msgpack::to_vec(&E::A).unwrap(), [0xa1, b'A']);
+    assert_eq!(serde_msgpack::to_vec(&E::B(1)).unwrap(), [0x81, 0xa1, b'B', 1]);
+    assert_eq!(serde_msgpack::to_vec(&E::C(1, 2)).unwrap(), [0x81, 0xa1, b'C', 0x92, 1, 2]);
+    assert_eq!(serde_msgpack::to_vec(&E::D { x: 1 }).unwrap(), [0x81, 0xa1, b'D', 0x81, 0xa1, b'x', 1]);
+}
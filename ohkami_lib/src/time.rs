@@ -8,6 +8,45 @@
     UTCDateTime::from_unix_timestamp(unix_timestamp).into_imf_fixdate()
 }
 
+/// Parse **IMF-fixdate** like `Sun, 06 Nov 1994 08:49:37 GMT` into unix timestamp,
+/// used in `If-Modified-Since`, `If-Range` and so on.
+/// 
+/// Returns `None` for other formats or datetimes before the Unix epoch.
+pub fn parse_imf_fixdate(imf_fixdate: &str) -> Option<u64> {
+    const SHORT_MONTHS: [&[u8; 3]; 12] = [b"Jan", b"Feb", b"Mar", b"Apr", b"May", b"Jun", b"Jul", b"Aug", b"Sep", b"Oct", b"Nov", b"Dec"];
+
+    fn num(digits: &[u8]) -> Option<i64> {
+        digits.iter().try_fold(0, |n, b| b.is_ascii_digit().then(|| n * 10 + (b - b'0') as i64))
+    }
+
+    let b = imf_fixdate.as_bytes();
+    if b.len() != str::len("Sun, 06 Nov 1994 08:49:37 GMT")
+    || &b[3..5] != b", " || b[7] != b' ' || b[11] != b' ' || b[16] != b' '
+    || b[19] != b':' || b[22] != b':' || &b[25..] != b" GMT" {
+        return None
+    }
+
+    let day   = num(&b[5..7])?;
+    let month = SHORT_MONTHS.iter().position(|m| m[..] == b[8..11])? as i64 + 1;
+    let year  = num(&b[12..16])?;
+    let (hour, min, sec) = (num(&b[17..19])?, num(&b[20..22])?, num(&b[23..25])?);
+    if !(1..=31).contains(&day) || hour >= 24 || min >= 60 || sec >= 61 {
+        return None
+    }
+
+    /* days from civil ( <https://howardhinnant.github.io/date_algorithms.html#days_from_civil> ) */
+    let days = {
+        let year = if month <= 2 {year - 1} else {year};
+        let era  = year.div_euclid(400);
+        let yoe  = year - era * 400;
+        let doy  = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+        let doe  = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        era * 146_097 + doe - 719_468
+    };
+
+    u64::try_from(days * 86_400 + hour * 3_600 + min * 60 + sec).ok()
+}
+
 /// date time on UTC *to the second*
 pub struct UTCDateTime {
     date: Date,
@@ -394,4 +433,17 @@ impl Weekday {
         let (expected, n) = (correct_now(), super::imf_fixdate(system_now.as_secs()));
         assert_eq!(expected, n);
     }
+
+    #[test] fn test_parse_imf_fixdate() {
+        for unix_timestamp in [0, 784111777, 1703963126, 951782400, 4102444799] {
+            assert_eq!(
+                super::parse_imf_fixdate(&super::imf_fixdate(unix_timestamp)),
+                Some(unix_timestamp)
+            );
+        }
+        assert_eq!(super::parse_imf_fixdate("Sun, 06 Nov 1994 08:49:37 GMT"), Some(784111777));
+        assert_eq!(super::parse_imf_fixdate("Sunday, 06-Nov-94 08:49:37 GMT"), None);
+        assert_eq!(super::parse_imf_fixdate("Sun Nov  6 08:49:37 1994"), None);
+        assert_eq!(super::parse_imf_fixdate("Sun, 06 Nov 1994 08:49:37 JST"), None);
+    }
 }
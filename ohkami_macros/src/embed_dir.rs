@@ -0,0 +1,199 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{LitStr, LitByteStr, Ident, Token, parse::{Parse, ParseStream}};
+use std::path::{Path, PathBuf};
+use std::collections::BTreeMap;
+
+
+struct EmbedDirArgs {
+    dir:      LitStr,
+    compress: bool,
+}
+impl Parse for EmbedDirArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let dir: LitStr = input.parse()?;
+
+        let mut compress = false;
+        while input.peek(Token![,]) {
+            let _: Token![,] = input.parse()?;
+            if input.is_empty() {break}
+
+            let option: Ident = input.parse()?;
+            match &*option.to_string() {
+                "compress" => compress = true,
+                _ => return Err(syn::Error::new(option.span(), "expected `compress`"))
+            }
+        }
+
+        Ok(Self { dir, compress })
+    }
+}
+
+/// precompressed siblings' extension and their content-coding
+const PRECOMPRESSED: &[(&str, &str)] = &[
+    ("br",  "br"),
+    ("zst", "zstd"),
+    ("gz",  "gzip"),
+];
+
+pub(super) fn embed_dir(args: TokenStream) -> syn::Result<TokenStream> {
+    let EmbedDirArgs { dir, compress } = syn::parse2(args)?;
+
+    let error = |msg: String| syn::Error::new(dir.span(), msg);
+
+    let dir_path = {
+        let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")
+            .map_err(|e| error(format!("can't get `CARGO_MANIFEST_DIR`: {e}")))?;
+        Path::new(&manifest_dir).join(dir.value())
+            .canonicalize()
+            .map_err(|e| error(format!("can't find `{}`: {e}", dir.value())))?
+    };
+    if !dir_path.is_dir() {
+        return Err(error(format!("`{}` is not a directory", dir_path.display())))
+    }
+
+    /* relative path ( joined by `/` ) -> absolute path */
+    let mut files = BTreeMap::<String, PathBuf>::new(); {
+        let mut dirs = vec![dir_path.clone()];
+        while let Some(dir) = dirs.pop() {
+            let entries = dir.read_dir()
+                .map_err(|e| error(format!("can't read `{}`: {e}", dir.display())))?;
+            for entry in entries {
+                let entry = entry
+                    .map_err(|e| error(format!("can't read `{}`: {e}", dir.display())))?
+                    .path();
+
+                if entry.file_name().is_some_and(|name| name.as_encoded_bytes().starts_with(b".")) {
+                    /* hidden files and directories are not embedded */
+                    continue
+                }
+
+                if entry.is_dir() {
+                    dirs.push(entry)
+                } else if entry.is_file() {
+                    let relative = entry.strip_prefix(&dir_path).unwrap()
+                        .components()
+                        .map(|c| c.as_os_str().to_str().map(String::from))
+                        .collect::<Option<Vec<_>>>()
+                        .ok_or_else(|| error(format!("`{}` is not a UTF-8 path", entry.display())))?
+                        .join("/");
+                    files.insert(relative, entry);
+                }
+            }
+        }
+    }
+
+    /* precompressed siblings like `app.js.br` of `app.js` are embedded
+       as encoded variants of the original, not as independent files */
+    let variants = files.keys()
+        .filter_map(|path| PRECOMPRESSED.iter().find_map(|(ext, encoding)| {
+            let original = path.strip_suffix(ext)?.strip_suffix('.')?;
+            files.contains_key(original).then(|| (path.clone(), (original.to_string(), *encoding)))
+        }))
+        .collect::<BTreeMap<_, _>>();
+
+    let mut embedded_files = Vec::with_capacity(files.len());
+    for (path, abs_path) in &files {
+        if variants.contains_key(path) {
+            continue
+        }
+
+        let content = std::fs::read(abs_path)
+            .map_err(|e| error(format!("can't read `{}`: {e}", abs_path.display())))?;
+
+        let filename = path.rsplit('/').next().unwrap();
+        let mime = filename.rsplit_once('.')
+            .filter(|(stem, _)| !stem.is_empty())
+            .and_then(|(_, ext)| ohkami_lib::mime::get_by_extension(&ext.to_ascii_lowercase()))
+            .or_else(|| ohkami_lib::mime::sniff(&content))
+            .unwrap_or("application/octet-stream");
+
+        if mime.starts_with("text/") && std::str::from_utf8(&content).is_err() {
+            return Err(error(format!("`{path}`: Ohkami doesn't support non UTF-8 text file")))
+        }
+
+        let etag = {
+            /* stable across builds and toolchains, unlike `std`'s `DefaultHasher` */
+            use sha2::{Sha256, Digest};
+            let hash = Sha256::digest(&content);
+            let hash = u64::from_be_bytes(hash[..8].try_into().unwrap());
+            format!("\"{hash:016x}-{:x}\"", content.len())
+        };
+
+        let include = |abs_path: &Path| -> syn::Result<TokenStream> {
+            let abs_path = abs_path.to_str()
+                .ok_or_else(|| error(format!("`{}` is not a UTF-8 path", abs_path.display())))?;
+            Ok(quote! { ::core::include_bytes!(#abs_path) })
+        };
+
+        let mut encoded = Vec::new();
+        for (variant_path, (original, encoding)) in &variants {
+            if original == path {
+                let bytes = include(&files[variant_path])?;
+                encoded.push(quote! { (#encoding, #bytes) });
+            }
+        }
+        if encoded.is_empty() && compress && ohkami_lib::mime::is_compressible(mime) {
+            for (encoding, compressed) in self::compress(&content)
+                .map_err(|e| error(format!("failed to compress `{path}`: {e}")))?
+            {
+                if compressed.len() < content.len() {
+                    let bytes = LitByteStr::new(&compressed, Span::call_site());
+                    encoded.push(quote! { (#encoding, #bytes) });
+                }
+            }
+        }
+
+        let content = include(abs_path)?;
+        embedded_files.push(quote! {
+            ::ohkami::__internal__::EmbeddedFile {
+                path:    #path,
+                mime:    #mime,
+                etag:    #etag,
+                content: #content,
+                encoded: &[ #( #encoded ),* ],
+            }
+        });
+    }
+
+    Ok(quote! {{
+        static FILES: &[::ohkami::__internal__::EmbeddedFile] = &[ #( #embedded_files ),* ];
+        ::ohkami::__internal__::EmbeddedDir::new(FILES)
+    }})
+}
+
+/// Compress `content` with all the enabled encodings at their highest levels
+#[allow(unused_mut, unused_variables)]
+fn compress(content: &[u8]) -> std::io::Result<Vec<(&'static str, Vec<u8>)>> {
+    #[allow(unused_imports)]
+    use std::io::Write;
+
+    let mut compressed = Vec::new();
+
+    #[cfg(feature="br")] {
+        let mut out = Vec::new();
+        {
+            let mut e = brotli::CompressorWriter::new(&mut out, 4096, 11, 22);
+            e.write_all(content)?;
+            e.flush()?;
+        }
+        compressed.push(("br", out));
+    }
+    #[cfg(feature="zstd")] {
+        compressed.push(("zstd", zstd::encode_all(content, 19)?));
+    }
+    #[cfg(feature="gzip")] {
+        let mut e = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::best());
+        e.write_all(content)?;
+        compressed.push(("gzip", e.finish()?));
+    }
+
+    if compressed.is_empty() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "`compress` requires any of `gzip`, `br` or `zstd` feature of ohkami"
+        ))
+    }
+
+    Ok(compressed)
+}
@@ -2,6 +2,7 @@ mod util;
 
 mod serde;
 mod from_request;
+mod embed_dir;
 
 #[cfg(feature="openapi")]
 mod openapi;
@@ -395,6 +396,42 @@ pub fn derive_from_request(target: proc_macro::TokenStream) -> proc_macro::Token
         .into()
 }
 
+/// Embed static files of a directory into the binary at compile time,
+/// to be served by `Route::Dir` on any runtime ( including `rt_worker` )
+/// 
+/// - the directory path is relative to `CARGO_MANIFEST_DIR`
+/// - hidden files and directories ( like `.gitignore` ) are not embedded
+/// - MIME types and `ETag`s are computed at compile time
+/// - precompressed siblings ( `app.js.br`, `app.js.zst`, `app.js.gz` for `app.js` )
+///   are embedded as encoded variants of the original
+/// - with `compress`, compressible files that have no precompressed sibling are
+///   compressed at compile time with the enabled `gzip`, `br`, `zstd` features
+/// 
+/// Modifications of the embedded files trigger recompilation, but adding or
+/// removing files doesn't: touch the source file invoking this macro then.
+/// 
+/// <br>
+/// 
+/// *example.rs*
+/// ```ignore
+/// use ohkami::prelude::*;
+/// 
+/// fn my_ohkami() -> Ohkami {
+///     Ohkami::new((
+///         "/".Dir(ohkami::embed_dir!("./public"))
+///             .index_files(["index.html"]),
+///         "/assets".Dir(ohkami::embed_dir!("./assets", compress))
+///             .cache_control("public, max-age=31536000, immutable"),
+///     ))
+/// }
+/// ```
+#[proc_macro]
+pub fn embed_dir(args: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    embed_dir::embed_dir(args.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
 #[doc(hidden)]
 #[proc_macro_attribute]
 pub fn consume_struct(_: proc_macro::TokenStream, _: proc_macro::TokenStream) -> proc_macro::TokenStream {
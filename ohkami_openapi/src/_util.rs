@@ -62,6 +62,9 @@ impl<K:PartialEq+PartialOrd, V> Map<K, V> {
     pub(crate) fn values_mut(&mut self) -> impl Iterator<Item = &mut V> {
         self.0.iter_mut().map(|(_, v)| v)
     }
+    pub(crate) fn iter_mut(&mut self) -> impl Iterator<Item = (&K, &mut V)> {
+        self.0.iter_mut().map(|(k, v)| (&*k, v))
+    }
 }
 const _: () = {
     impl<K:PartialEq+PartialOrd, V> Serialize for Map<K, V>
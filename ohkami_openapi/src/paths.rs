@@ -212,6 +212,27 @@ impl Operation {
         self
     }
 
+    #[doc(hidden)]
+    /// Merge `variant`, another operation for the same path and method,
+    /// into this. When `media_type` is given, contents of `variant`'s
+    /// responses are documented under the media type.
+    pub fn merge_variant(&mut self, variant: Operation, media_type: Option<&'static str>) {
+        for param in variant.parameters {
+            if !self.parameters.iter().any(|p| p.is_same(&param)) {
+                self.parameters.push(param);
+            }
+        }
+        if self.requestBody.is_none() {
+            self.requestBody = variant.requestBody;
+        }
+        self.responses.merge_variant(variant.responses, media_type);
+    }
+
+    #[doc(hidden)]
+    pub fn get_operationId(&self) -> Option<&'static str> {
+        self.operationId
+    }
+
     #[doc(hidden)]
     pub fn assign_path_param_name(&mut self, name: impl Into<std::borrow::Cow<'static, str>>) {
         if let Some(empty_param) = self.parameters.iter_mut()
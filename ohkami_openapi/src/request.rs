@@ -36,6 +36,11 @@ impl Parameter {
     pub(crate) fn is_path(&self) -> bool {
         matches!(self.kind, ParameterKind::path)
     }
+
+    pub(crate) fn is_same(&self, another: &Self) -> bool {
+        std::mem::discriminant(&self.kind) == std::mem::discriminant(&another.kind)
+        && self.name == another.name
+    }
 }
 
 impl Parameter {
@@ -69,12 +69,40 @@ impl Responses {
         self
     }
 
+    /// add `header` to every success ( 2xx or 3xx ) response
+    pub fn header(mut self, name: &'static str, header: impl Into<ResponseHeader>) -> Self {
+        let header = header.into();
+        for (status, res) in self.0.iter_mut() {
+            if matches!(status, Status::Code(200..=399)) {
+                res.headers.insert(name, header.clone());
+            }
+        }
+        self
+    }
+
     pub fn merge(&mut self, another: Self) {
         for (code, res) in another.0 {
             self.0.insert(code, res);
         }
     }
 
+    /// merge responses of a variant operation, without overriding existing contents
+    pub(crate) fn merge_variant(&mut self, variant: Self, media_type: Option<&'static str>) {
+        for (status, mut res) in variant.0 {
+            if let Some(media_type) = media_type {
+                res.content = Map::from_iter(res.content.into_iter().map(|(_, content)| (media_type, content)));
+            }
+            match self.0.get_mut(&status) {
+                None => self.0.insert(status, res),
+                Some(existing) => for (media_type, content) in res.content {
+                    if existing.content.get(&media_type).is_none() {
+                        existing.content.insert(media_type, content);
+                    }
+                }
+            }
+        }
+    }
+
     pub(crate) fn refize_schemas(&mut self) -> impl Iterator<Item = RawSchema> + '_ {
         self.0.values_mut().map(Response::refize_schemas).flatten()
     }